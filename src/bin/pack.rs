@@ -0,0 +1,119 @@
+#[path = "../game/vfs/archive.rs"]
+#[allow(dead_code)]
+mod archive;
+
+use std::env;
+use std::error::Error;
+use std::fs::{self, File};
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
+use std::process;
+
+use self::archive::{Archive, Compression};
+
+const DEFAULT_ASSETS_DIRECTORY: &str = "assets";
+const DEFAULT_ARCHIVE_FILE_NAME: &str = "assets.pak";
+
+fn main() {
+    let mut arguments = env::args().skip(1);
+    let mut assets_directory = DEFAULT_ASSETS_DIRECTORY.to_string();
+    let mut archive_filepath = DEFAULT_ARCHIVE_FILE_NAME.to_string();
+    let mut allow_compression = true;
+
+    while let Some(argument) = arguments.next() {
+        match argument.as_str() {
+            "--no-compression" => allow_compression = false,
+            "--output" | "-o" => match arguments.next() {
+                Some(output) => archive_filepath = output,
+                None => exit_with_usage(),
+            },
+            "--help" | "-h" => exit_with_usage(),
+            _ => assets_directory = argument,
+        }
+    }
+
+    if let Err(error) = pack(&assets_directory, &archive_filepath, allow_compression) {
+        eprintln!("Could not pack {}: {}", assets_directory, error);
+        process::exit(1);
+    }
+}
+
+fn exit_with_usage() -> ! {
+    eprintln!("Usage: pack [--no-compression] [--output <archive>] [assets directory]");
+    process::exit(2);
+}
+
+fn pack(
+    assets_directory: &str,
+    archive_filepath: &str,
+    allow_compression: bool,
+) -> Result<(), Box<dyn Error>> {
+    let mut filepaths = vec![];
+    collect_files(Path::new(assets_directory), &mut filepaths)?;
+    filepaths.sort();
+
+    let files = filepaths
+        .iter()
+        .map(|filepath| {
+            let path = filepath
+                .to_str()
+                .ok_or_else(|| format!("{} is not valid UTF-8", filepath.display()))?
+                .replace('\\', "/");
+
+            Ok((path, fs::read(filepath)?))
+        })
+        .collect::<Result<Vec<(String, Vec<u8>)>, Box<dyn Error>>>()?;
+
+    let entries = {
+        let mut writer = BufWriter::new(File::create(archive_filepath)?);
+        archive::write_archive(&mut writer, &files, allow_compression)?
+    };
+
+    let archive = Archive::open(Path::new(archive_filepath))?;
+    let mut total_size = 0;
+    let mut total_stored_size = 0;
+
+    for entry in &entries {
+        archive.read(&entry.path)?;
+
+        total_size += entry.size;
+        total_stored_size += entry.stored_size;
+
+        println!(
+            "{:<48} {:>10} -> {:>10} {:016x}{}",
+            entry.path,
+            entry.size,
+            entry.stored_size,
+            entry.hash,
+            if entry.compression == Compression::None {
+                ""
+            } else {
+                " (compressed)"
+            }
+        );
+    }
+
+    println!(
+        "Packed {} files into {} ({} -> {} bytes)",
+        entries.len(),
+        archive_filepath,
+        total_size,
+        total_stored_size
+    );
+
+    Ok(())
+}
+
+fn collect_files(directory: &Path, filepaths: &mut Vec<PathBuf>) -> Result<(), Box<dyn Error>> {
+    for entry in fs::read_dir(directory)? {
+        let path = entry?.path();
+
+        if path.is_dir() {
+            collect_files(&path, filepaths)?;
+        } else {
+            filepaths.push(path);
+        }
+    }
+
+    Ok(())
+}
@@ -1,62 +1,158 @@
+pub mod animation;
+pub mod camera;
+pub mod capture;
+pub mod collision;
+pub mod console;
+pub mod crash_report;
+pub mod debug_overlay;
+pub mod ecs;
+pub mod error;
+pub mod hot_reload;
 pub mod input;
+pub mod logging;
+pub mod particles;
+pub mod post_processing;
+pub mod profiler;
+pub mod random;
+pub mod renderer;
+pub mod resources;
 pub mod scene;
+pub mod transition;
+pub mod vfs;
 
 use std::collections::VecDeque;
+use std::env;
 use std::error::Error;
 use std::fs;
-use std::time::Instant;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, Instant};
 
 use sdl2::{
-    image::{self, LoadTexture},
+    image,
     keyboard::{KeyboardState, Keycode, Scancode},
     mixer::{self, Channel},
     mouse::{MouseButton, MouseState},
+    pixels::PixelFormatEnum,
     render::{Texture, TextureCreator, WindowCanvas},
     ttf::{self, Font},
     EventPump, Sdl, VideoSubsystem,
 };
 
+use self::capture::{FrameCapture, HeadlessSettings};
+use self::console::Console;
+use self::debug_overlay::{DebugOverlay, FrameTimings};
+use self::error::EngineError;
+use self::hot_reload::HotReloader;
+use self::logging::{log_info, log_warn};
+use self::post_processing::PostProcessor;
+use self::profiler::profile_scope;
+use self::renderer::Renderer;
+use self::resources::{ReloadedResource, ResourceManager, ResourcePaths};
 use self::scene::Scene;
+use self::transition::SceneTransition;
 
 const CONFIG_FILE_NAME: &str = "config/config.json";
+const USER_DATA_DIRECTORY_NAME: &str = "space-invaders";
+const SAVES_DIRECTORY: &str = "saves";
+
+const LOADING_SCENE_DELAY: f32 = 0.2;
+const LOADING_POLL_INTERVAL: Duration = Duration::from_millis(4);
 
 struct Config {
     window_title: String,
     window_size: (u32, u32),
     enable_vsync: bool,
+    engine_font: String,
 }
 
-pub fn play(initial_scene: Box<dyn Scene>) {
-    let config = read_config_file().unwrap();
+pub fn play(
+    initial_scene: Box<dyn Scene>,
+    loading_scene: Box<dyn Scene>,
+) -> Result<(), EngineError> {
+    crash_report::install_panic_hook();
+    vfs::mount();
+    logging::initialise();
+
+    log_info!("Starting version {}", env!("CARGO_PKG_VERSION"));
+
+    let config =
+        read_config_file().map_err(|error| EngineError::config(CONFIG_FILE_NAME, error))?;
+
+    let headless = capture::headless_settings();
 
     let (sdl_context, _image_context, _mixer_context, ttf_context, video_subsystem) =
-        initialise_sdl().unwrap();
-    let mut canvas = initialise_canvas(&video_subsystem, &config).unwrap();
-    let mut event_pump = sdl_context.event_pump().unwrap();
+        initialise_sdl()?;
+    let mut canvas = initialise_canvas(&video_subsystem, &config, headless.is_some())
+        .map_err(|error| EngineError::sdl("Could not create the window", error))?;
+    let mut event_pump = sdl_context
+        .event_pump()
+        .map_err(|error| EngineError::sdl("Could not create the event pump", error))?;
 
     play_loop(
         &sdl_context,
-        initial_scene,
+        (initial_scene, loading_scene),
         &mut canvas,
-        &ttf_context,
+        (&ttf_context, &config.engine_font),
         &mut event_pump,
-    );
+        headless,
+    )
 }
 
 fn read_config_file() -> Result<Config, Box<dyn Error>> {
-    let json_config_string = fs::read_to_string(CONFIG_FILE_NAME)?;
+    let json_config_string = fs::read_to_string(vfs::resolve(CONFIG_FILE_NAME))?;
     let config_data: serde_json::Value = serde_json::from_str(&json_config_string[..])?;
 
     Ok(Config {
-        window_title: config_data["window"]["title"].as_str().unwrap().to_string(),
+        window_title: config_data["window"]["title"]
+            .as_str()
+            .ok_or("window.title must be a string")?
+            .to_string(),
         window_size: (
-            config_data["window"]["size"]["x"].as_u64().unwrap() as u32,
-            config_data["window"]["size"]["y"].as_u64().unwrap() as u32,
+            config_data["window"]["size"]["x"]
+                .as_u64()
+                .ok_or("window.size.x must be a positive integer")? as u32,
+            config_data["window"]["size"]["y"]
+                .as_u64()
+                .ok_or("window.size.y must be a positive integer")? as u32,
         ),
-        enable_vsync: config_data["enable-vsync"].as_bool().unwrap(),
+        enable_vsync: config_data["enable-vsync"]
+            .as_bool()
+            .ok_or("enable-vsync must be a boolean")?,
+        engine_font: config_data["engine-font"]
+            .as_str()
+            .ok_or("engine-font must be a string")?
+            .to_string(),
     })
 }
 
+pub fn read_config_section(section_name: &str) -> Result<serde_json::Value, Box<dyn Error>> {
+    let json_config_string = fs::read_to_string(vfs::resolve(CONFIG_FILE_NAME))?;
+    let mut config_data: serde_json::Value = serde_json::from_str(&json_config_string[..])?;
+
+    Ok(config_data[section_name].take())
+}
+
+pub fn user_data_directory() -> Option<PathBuf> {
+    let data_directory = if cfg!(target_os = "windows") {
+        env::var_os("APPDATA").map(PathBuf::from)
+    } else if cfg!(target_os = "macos") {
+        env::var_os("HOME").map(|home| PathBuf::from(home).join("Library/Application Support"))
+    } else {
+        env::var_os("XDG_DATA_HOME")
+            .map(PathBuf::from)
+            .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/share")))
+    };
+
+    data_directory.map(|data_directory| data_directory.join(USER_DATA_DIRECTORY_NAME))
+}
+
+pub fn saves_directory() -> PathBuf {
+    user_data_directory()
+        .unwrap_or_else(|| vfs::base_directory().to_path_buf())
+        .join(SAVES_DIRECTORY)
+}
+
 fn initialise_sdl() -> Result<
     (
         Sdl,
@@ -65,20 +161,27 @@ fn initialise_sdl() -> Result<
         ttf::Sdl2TtfContext,
         VideoSubsystem,
     ),
-    String,
+    EngineError,
 > {
-    let sdl_context = sdl2::init()?;
-    let image_context = image::init(image::InitFlag::PNG)?;
-    let mixer_context = mixer::init(mixer::InitFlag::MP3)?;
+    let sdl_context =
+        sdl2::init().map_err(|error| EngineError::sdl("Could not initialise SDL", error))?;
+    let image_context = image::init(image::InitFlag::PNG)
+        .map_err(|error| EngineError::sdl("Could not initialise SDL_image", error))?;
+    let mixer_context = mixer::init(mixer::InitFlag::MP3)
+        .map_err(|error| EngineError::sdl("Could not initialise SDL_mixer", error))?;
     mixer::open_audio(
         mixer::DEFAULT_FREQUENCY,
         mixer::DEFAULT_FORMAT,
         mixer::DEFAULT_CHANNELS,
         1024,
-    )?;
+    )
+    .map_err(|error| EngineError::sdl("Could not open the audio device", error))?;
 
-    let ttf_context = ttf::init().unwrap();
-    let video_subsystem = sdl_context.video()?;
+    let ttf_context =
+        ttf::init().map_err(|error| EngineError::sdl("Could not initialise SDL_ttf", error))?;
+    let video_subsystem = sdl_context
+        .video()
+        .map_err(|error| EngineError::sdl("Could not initialise the video subsystem", error))?;
 
     Ok((
         sdl_context,
@@ -92,18 +195,24 @@ fn initialise_sdl() -> Result<
 fn initialise_canvas(
     video_subsystem: &VideoSubsystem,
     config: &Config,
+    is_headless: bool,
 ) -> Result<WindowCanvas, Box<dyn Error>> {
-    let window = video_subsystem
-        .window(
-            &config.window_title[..],
-            config.window_size.0,
-            config.window_size.1,
-        )
-        .position_centered()
-        .allow_highdpi()
-        .build()?;
-
-    let canvas = if config.enable_vsync {
+    let mut window_builder = video_subsystem.window(
+        &config.window_title[..],
+        config.window_size.0,
+        config.window_size.1,
+    );
+    window_builder.position_centered().allow_highdpi();
+
+    if is_headless {
+        window_builder.hidden();
+    }
+
+    let window = window_builder.build()?;
+
+    let canvas = if is_headless {
+        window.into_canvas().software().build()?
+    } else if config.enable_vsync {
         window.into_canvas().accelerated().present_vsync().build()?
     } else {
         window.into_canvas().accelerated().build()?
@@ -114,21 +223,75 @@ fn initialise_canvas(
 
 fn play_loop(
     sdl_context: &sdl2::Sdl,
-    initial_scene: Box<dyn Scene>,
+    scenes: (Box<dyn Scene>, Box<dyn Scene>),
     canvas: &mut WindowCanvas,
-    ttf_context: &ttf::Sdl2TtfContext,
+    fonts: (&ttf::Sdl2TtfContext, &str),
     event_pump: &mut EventPump,
-) {
+    headless: Option<HeadlessSettings>,
+) -> Result<(), EngineError> {
+    let (initial_scene, mut loading_scene) = scenes;
+    let (ttf_context, engine_font) = fonts;
     let texture_creator = canvas.texture_creator();
     let sound_channel = Channel::all();
 
+    let mut resource_manager = ResourceManager::new(&texture_creator, ttf_context);
+    let engine_fonts = resource_manager
+        .load_now(ResourcePaths {
+            fonts: vec![engine_font.to_string()],
+            ..ResourcePaths::default()
+        })?
+        .fonts;
+    let mut hot_reloader = HotReloader::new(
+        &hot_reload::read_hot_reload_settings()
+            .map_err(|error| EngineError::config("hot-reload", error))?,
+    );
+    let mut console = Console::new(console::is_console_enabled());
+    let mut debug_overlay = DebugOverlay::new();
+    profiler::capture_from_arguments();
+
+    let mut offscreen_target = match &headless {
+        Some(_) => Some(
+            texture_creator
+                .create_texture_target(
+                    PixelFormatEnum::ARGB8888,
+                    canvas.viewport().width(),
+                    canvas.viewport().height(),
+                )
+                .map_err(|error| {
+                    EngineError::sdl("Could not create the offscreen target", error)
+                })?,
+        ),
+        None => None,
+    };
+    let mut frame_capture = FrameCapture::new(
+        capture::read_capture_settings().map_err(|error| EngineError::config("capture", error))?,
+        headless,
+    );
+
+    let loading_scene_resources =
+        resource_manager.load_now(loading_scene.on_load(sdl_context, canvas, None))?;
+    let mut loading_textures = loading_scene_resources.textures;
+    let loading_fonts = loading_scene_resources.fonts;
+    loading_scene.on_late_load(canvas, &loading_textures, &loading_fonts);
+
     let mut scene_queue = VecDeque::<Box<dyn Scene>>::new();
     let mut current_scene = initial_scene;
-    let (texture_paths, font_paths) = current_scene.on_load(sdl_context, &canvas, None);
+    log_info!("Loading {}", current_scene.name());
+    crash_report::set_context("scene", current_scene.name());
+    resource_manager.begin_load(current_scene.on_load(sdl_context, &canvas, None));
+
+    let mut textures = vec![];
+    let mut fonts = vec![];
+    let mut loading_time = 0.0;
+
+    let mut renderer = Renderer::new();
+    let mut post_processor = PostProcessor::new(
+        &texture_creator,
+        post_processing::read_post_processing_settings()
+            .map_err(|error| EngineError::config("post-processing", error))?,
+    );
 
-    let mut textures = create_textures(&texture_creator, &texture_paths);
-    let mut fonts = create_fonts(ttf_context, &font_paths);
-    current_scene.on_late_load(&canvas, &textures, &fonts);
+    let mut scene_transition: Option<SceneTransition> = None;
 
     let mut ticks_count = Instant::now();
     let mut is_running = true;
@@ -138,67 +301,267 @@ fn play_loop(
     let mut mouse_y_scroll_amount = 0;
 
     while is_running {
-        let delta_time = calculate_delta_time(&mut ticks_count);
+        profiler::begin_frame();
+
+        let delta_time = frame_capture
+            .fixed_delta_time()
+            .unwrap_or_else(|| calculate_delta_time(&mut ticks_count));
+
+        if resource_manager.is_loading() {
+            poll_events(
+                &mut loading_scene,
+                None,
+                event_pump,
+                canvas,
+                &mut is_running,
+                &mut mouse_y_scroll_amount,
+            );
+
+            resource_manager.poll();
+            loading_time += delta_time;
+
+            loading_scene.on_loading_progress(resource_manager.progress());
+            update(
+                &mut loading_scene,
+                delta_time,
+                &mut scene_queue,
+                canvas,
+                &sound_channel,
+            );
+
+            if let Some(resources) = resource_manager.finish_load()? {
+                textures = resources.textures;
+                fonts = resources.fonts;
+
+                current_scene.on_sounds_loaded(resources.sounds);
+                current_scene.on_late_load(canvas, &textures, &fonts);
+                console.bind_scene(&mut current_scene, canvas);
+                resource_manager.preload(&current_scene.preload());
+
+                post_processor.settings = post_processing::read_post_processing_settings()
+                    .map_err(|error| EngineError::config("post-processing", error))?;
+                loading_time = 0.0;
+
+                log_info!("Loaded {}", current_scene.name());
+            } else if loading_time >= LOADING_SCENE_DELAY {
+                draw(
+                    &mut loading_scene,
+                    (&mut renderer, &mut post_processor),
+                    (canvas, offscreen_target.as_mut()),
+                    &texture_creator,
+                    (&mut loading_textures, &loading_fonts),
+                    None,
+                    false,
+                );
+                canvas.present();
+            } else {
+                thread::sleep(LOADING_POLL_INTERVAL);
+            }
+
+            previous_keys = input::update_key_state(&event_pump.keyboard_state());
+            previous_mouse_buttons = input::update_mouse_button_state(&event_pump.mouse_state());
+            mouse_y_scroll_amount = 0;
+
+            continue;
+        }
 
         poll_events(
             &mut current_scene,
+            Some((&mut console, &mut debug_overlay, &mut frame_capture)),
             event_pump,
             canvas,
             &mut is_running,
             &mut mouse_y_scroll_amount,
         );
 
-        process_input(
-            &mut current_scene,
-            (&event_pump.keyboard_state(), &previous_keys),
-            (&event_pump.mouse_state(), &previous_mouse_buttons),
-            (event_pump.mouse_state().x(), event_pump.mouse_state().y()),
-            mouse_y_scroll_amount,
-        );
+        let is_scene_frozen = current_scene.is_done()
+            || console.is_visible()
+            || scene_transition
+                .as_ref()
+                .is_some_and(SceneTransition::is_outgoing_frozen);
+
+        let mut frame_timings = FrameTimings::default();
+
+        if !is_scene_frozen {
+            let update_start = Instant::now();
+
+            process_input(
+                &mut current_scene,
+                (&event_pump.keyboard_state(), &previous_keys),
+                (&event_pump.mouse_state(), &previous_mouse_buttons),
+                (event_pump.mouse_state().x(), event_pump.mouse_state().y()),
+                mouse_y_scroll_amount,
+            );
+
+            let scene_delta_time = delta_time * console.time_scale();
+
+            update(
+                &mut current_scene,
+                scene_delta_time,
+                &mut scene_queue,
+                &canvas,
+                &sound_channel,
+            );
+
+            late_update(
+                &mut current_scene,
+                scene_delta_time,
+                &mut scene_queue,
+                &canvas,
+                &sound_channel,
+            );
+
+            frame_timings.update = update_start.elapsed();
+
+            for changed_path in hot_reloader.poll(delta_time) {
+                let reload_result = hot_reload(
+                    &changed_path,
+                    &mut current_scene,
+                    &resource_manager,
+                    (&mut textures, &mut fonts),
+                    &mut post_processor,
+                );
+
+                hot_reloader.report(&changed_path, reload_result);
+            }
+        }
 
-        update(
-            &mut current_scene,
-            delta_time,
-            &mut scene_queue,
-            &canvas,
-            &sound_channel,
-        );
+        if let Some(transition) = &mut scene_transition {
+            transition.update(delta_time);
+        }
 
-        late_update(
-            &mut current_scene,
-            delta_time,
-            &mut scene_queue,
-            &canvas,
-            &sound_channel,
-        );
+        let draw_start = Instant::now();
 
         draw(
             &mut current_scene,
-            canvas,
+            (&mut renderer, &mut post_processor),
+            (canvas, offscreen_target.as_mut()),
             &texture_creator,
-            &textures,
-            &fonts,
+            (&mut textures, &fonts),
+            scene_transition.as_ref(),
+            debug_overlay.are_hitboxes_visible(),
         );
 
+        for capture_request in console.take_capture_requests() {
+            frame_capture.request(capture_request);
+        }
+
+        frame_capture.capture(canvas, offscreen_target.as_mut(), delta_time);
+
+        let render_stats = renderer.stats();
+        console.update(delta_time);
+
+        if hot_reloader.has_errors()
+            || console.is_visible()
+            || debug_overlay.is_visible()
+            || frame_capture.is_recording()
+        {
+            profile_scope!("draw_overlays");
+
+            renderer.begin(canvas.viewport());
+
+            if debug_overlay.is_visible() {
+                debug_overlay.draw(
+                    &mut renderer,
+                    &engine_fonts[0],
+                    0,
+                    [profiler::frame_summary(), current_scene.debug_lines()].concat(),
+                );
+            }
+
+            if hot_reloader.has_errors() {
+                hot_reloader.draw(&mut renderer, &engine_fonts[0], 0);
+            }
+
+            if frame_capture.is_recording() {
+                frame_capture.draw(&mut renderer, &engine_fonts[0], 0);
+            }
+
+            if console.is_visible() {
+                console.draw(&mut renderer, &engine_fonts[0], 0);
+            }
+
+            renderer.flush(canvas, &texture_creator, &mut [], &engine_fonts);
+        }
+
+        frame_timings.draw = draw_start.elapsed();
+
+        let present_start = Instant::now();
+        {
+            profile_scope!("present");
+            canvas.present();
+        }
+        frame_timings.present = present_start.elapsed();
+
+        debug_overlay.end_frame(frame_timings, render_stats);
+
+        if frame_capture.is_finished() {
+            is_running = false;
+        }
+
         previous_keys = input::update_key_state(&event_pump.keyboard_state());
         previous_mouse_buttons = input::update_mouse_button_state(&event_pump.mouse_state());
         mouse_y_scroll_amount = 0;
 
-        if let Some(new_scene_resources) = update_scene_queue(
-            sdl_context,
-            &mut current_scene,
-            &mut scene_queue,
-            &canvas,
-            &mut is_running,
-        ) {
-            let (texture_paths, font_paths) = new_scene_resources;
+        if scene_transition.is_none() && current_scene.is_done() {
+            let next_transition = scene_queue
+                .front()
+                .map(|next_scene| next_scene.transition());
+
+            if let Some(next_transition) = next_transition.filter(|next| !next.is_instant()) {
+                let mut transition = SceneTransition::new(
+                    &texture_creator,
+                    canvas.viewport().size(),
+                    next_transition,
+                );
+
+                capture_snapshot(
+                    &mut current_scene,
+                    &mut renderer,
+                    canvas,
+                    &texture_creator,
+                    &mut textures,
+                    &fonts,
+                    transition.snapshot_mut(),
+                );
+
+                scene_transition = Some(transition);
+            }
+        }
 
-            textures = create_textures(&texture_creator, &texture_paths);
-            fonts = create_fonts(ttf_context, &font_paths);
+        let is_switch_due = scene_transition
+            .as_ref()
+            .map_or(current_scene.is_done(), SceneTransition::is_switch_due);
 
-            current_scene.on_late_load(&canvas, &textures, &fonts);
+        if is_switch_due {
+            if let Some(transition) = &mut scene_transition {
+                transition.on_switched();
+            }
+
+            if let Some(resource_paths) = update_scene_queue(
+                sdl_context,
+                &mut current_scene,
+                &mut scene_queue,
+                &canvas,
+                &mut is_running,
+            ) {
+                log_info!("Loading {}", current_scene.name());
+                crash_report::set_context("scene", current_scene.name());
+                resource_manager.begin_load(resource_paths);
+            }
+        }
+
+        if scene_transition
+            .as_ref()
+            .is_some_and(SceneTransition::is_finished)
+        {
+            scene_transition = None;
         }
     }
+
+    log_info!("Shutting down");
+
+    Ok(())
 }
 
 fn calculate_delta_time(ticks_count: &mut Instant) -> f32 {
@@ -213,6 +576,7 @@ fn calculate_delta_time(ticks_count: &mut Instant) -> f32 {
 
 fn poll_events(
     current_scene: &mut Box<dyn Scene>,
+    mut tools: Option<(&mut Console, &mut DebugOverlay, &mut FrameCapture)>,
     event_pump: &mut EventPump,
     canvas: &mut WindowCanvas,
     is_running: &mut bool,
@@ -221,7 +585,34 @@ fn poll_events(
     use sdl2::event::Event::*;
     use sdl2::event::WindowEvent::*;
 
+    profile_scope!("poll_events");
+
     for event in event_pump.poll_iter() {
+        match &event {
+            KeyDown {
+                keycode: Some(keycode),
+                repeat: false,
+                ..
+            } => crash_report::record_input(format!("Key down {}", keycode)),
+            MouseButtonDown {
+                mouse_btn, x, y, ..
+            } => crash_report::record_input(format!(
+                "Mouse button down {:?} at ({}, {})",
+                mouse_btn, x, y
+            )),
+            _ => {}
+        }
+
+        if let Some((console, debug_overlay, frame_capture)) = &mut tools {
+            if console.handle_event(&event, current_scene, canvas)
+                || debug_overlay.handle_event(&event)
+                || frame_capture.handle_event(&event)
+                || profiler::handle_event(&event)
+            {
+                continue;
+            }
+        }
+
         match event {
             Quit { .. }
             | Window {
@@ -252,6 +643,8 @@ fn process_input(
     mouse_coordinates: (i32, i32),
     mouse_y_scroll_amount: i32,
 ) {
+    profile_scope!("process_input");
+
     let (current_keys, previous_keys) = key_states;
     let (current_mouse_buttons, previous_mouse_buttons) = mouse_states;
     let (mouse_x, mouse_y) = mouse_coordinates;
@@ -275,6 +668,7 @@ fn update(
     canvas: &WindowCanvas,
     sound_channel: &Channel,
 ) {
+    profile_scope!("update");
     current_scene.update(delta_time, scene_queue, canvas, sound_channel);
 }
 
@@ -285,19 +679,104 @@ fn late_update(
     canvas: &WindowCanvas,
     sound_channel: &Channel,
 ) {
+    profile_scope!("late_update");
     current_scene.late_update(delta_time, scene_queue, canvas, sound_channel);
 }
 
 fn draw(
     current_scene: &mut Box<dyn Scene>,
+    renderers: (&mut Renderer, &mut PostProcessor),
+    render_target: (&mut WindowCanvas, Option<&mut Texture>),
+    texture_creator: &TextureCreator<sdl2::video::WindowContext>,
+    resources: (&mut [Texture], &[Font]),
+    scene_transition: Option<&SceneTransition>,
+    are_hitboxes_visible: bool,
+) {
+    profile_scope!("draw");
+
+    let (renderer, post_processor) = renderers;
+    let (canvas, offscreen_target) = render_target;
+    let (textures, fonts) = resources;
+    let viewport = canvas.viewport();
+
+    renderer.begin(viewport);
+
+    if !scene_transition.is_some_and(SceneTransition::is_outgoing_frozen) {
+        current_scene.draw(renderer, fonts);
+
+        if are_hitboxes_visible {
+            current_scene.draw_hitboxes(renderer);
+        }
+    }
+
+    let mut draw_frame = |frame_canvas: &mut WindowCanvas| {
+        renderer.flush(frame_canvas, texture_creator, textures, fonts);
+
+        if let Some(transition) = scene_transition {
+            transition.draw(frame_canvas);
+        }
+    };
+
+    if let Some(offscreen_target) = offscreen_target {
+        canvas
+            .with_texture_canvas(offscreen_target, draw_frame)
+            .unwrap();
+        canvas.copy(offscreen_target, None, None).unwrap();
+    } else if post_processor.settings.is_enabled() {
+        canvas
+            .with_texture_canvas(post_processor.frame(viewport.size()), draw_frame)
+            .unwrap();
+
+        post_processor.present(canvas);
+    } else {
+        draw_frame(canvas);
+    }
+}
+
+fn capture_snapshot(
+    current_scene: &mut Box<dyn Scene>,
+    renderer: &mut Renderer,
     canvas: &mut WindowCanvas,
     texture_creator: &TextureCreator<sdl2::video::WindowContext>,
-    textures: &[Texture],
+    textures: &mut [Texture],
     fonts: &[Font],
+    snapshot: &mut Texture,
 ) {
-    current_scene.draw(canvas, texture_creator, textures, fonts);
+    renderer.begin(canvas.viewport());
+    current_scene.draw(renderer, fonts);
+
+    canvas
+        .with_texture_canvas(snapshot, |snapshot_canvas| {
+            renderer.flush(snapshot_canvas, texture_creator, textures, fonts);
+        })
+        .unwrap();
+}
+
+fn hot_reload<'a>(
+    changed_path: &Path,
+    current_scene: &mut Box<dyn Scene>,
+    resource_manager: &ResourceManager<'a>,
+    resources: (&mut Vec<Texture<'a>>, &mut Vec<Font<'a, 'a>>),
+    post_processor: &mut PostProcessor,
+) -> Result<(), Box<dyn Error>> {
+    profile_scope!("hot_reload");
+
+    let (textures, fonts) = resources;
+
+    match resource_manager.reload(changed_path, textures, fonts)? {
+        Some(ReloadedResource::Texture) => current_scene.on_textures_reloaded(textures),
+        Some(ReloadedResource::Font) => (),
+        Some(ReloadedResource::Sounds(sounds)) => current_scene.on_sounds_loaded(sounds),
+        None => {
+            if changed_path == Path::new(CONFIG_FILE_NAME) {
+                post_processor.settings = post_processing::read_post_processing_settings()?;
+            }
+
+            current_scene.on_file_changed(changed_path, textures)?;
+        }
+    }
 
-    canvas.present();
+    Ok(())
 }
 
 fn update_scene_queue(
@@ -306,7 +785,7 @@ fn update_scene_queue(
     scene_queue: &mut VecDeque<Box<dyn Scene>>,
     canvas: &WindowCanvas,
     is_running: &mut bool,
-) -> Option<(Vec<String>, Vec<String>)> {
+) -> Option<ResourcePaths> {
     if current_scene.is_done() {
         let previous_scene_payload = current_scene.on_unload(sdl_context);
 
@@ -323,45 +802,18 @@ fn update_scene_queue(
     }
 }
 
-fn create_textures<'a>(
-    texture_creator: &'a TextureCreator<sdl2::video::WindowContext>,
-    texture_filepaths: &[String],
-) -> Vec<Texture<'a>> {
-    let mut textures = vec![];
-
-    for texture_path in texture_filepaths {
-        textures.push(texture_creator.load_texture(texture_path).unwrap());
-    }
-
-    textures
-}
-
-fn create_fonts<'a>(
-    ttf_context: &'a ttf::Sdl2TtfContext,
-    font_filepaths: &[String],
-) -> Vec<Font<'a, 'a>> {
-    let mut fonts = vec![];
-
-    for font_path in font_filepaths {
-        fonts.push(ttf_context.load_font(font_path, 128).unwrap());
-    }
-
-    fonts
-}
-
 fn toggle_fullscreen(canvas: &mut WindowCanvas) {
     use sdl2::video::FullscreenType;
 
     let window = canvas.window_mut();
 
-    match window.fullscreen_state() {
-        FullscreenType::True | FullscreenType::Desktop => {
-            window.set_fullscreen(FullscreenType::Off).unwrap();
-            window.set_bordered(true);
-        }
-        FullscreenType::Off => {
-            window.set_fullscreen(FullscreenType::True).unwrap();
-            window.set_bordered(false);
-        }
+    let (fullscreen_type, is_bordered) = match window.fullscreen_state() {
+        FullscreenType::True | FullscreenType::Desktop => (FullscreenType::Off, true),
+        FullscreenType::Off => (FullscreenType::True, false),
+    };
+
+    match window.set_fullscreen(fullscreen_type) {
+        Ok(()) => window.set_bordered(is_bordered),
+        Err(error) => log_warn!("Could not toggle fullscreen: {}", error),
     }
 }
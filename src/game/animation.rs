@@ -0,0 +1,257 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs;
+
+use sdl2::rect::Rect;
+use sdl2::render::Texture;
+
+use super::vfs;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum LoopMode {
+    Loop,
+    Once,
+    PingPong,
+}
+
+impl LoopMode {
+    pub fn from_name(name: &str) -> Option<LoopMode> {
+        match name {
+            "loop" => Some(LoopMode::Loop),
+            "once" => Some(LoopMode::Once),
+            "ping-pong" => Some(LoopMode::PingPong),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct AnimationClip {
+    pub frames: Vec<u32>,
+    pub frame_duration: f32,
+    pub loop_mode: LoopMode,
+}
+
+#[derive(Clone, Debug)]
+pub struct SpriteSheet {
+    pub frame_width: u32,
+    pub frame_height: u32,
+    pub columns: u32,
+
+    clips: HashMap<String, AnimationClip>,
+}
+
+impl SpriteSheet {
+    pub fn single_frame(width: u32, height: u32) -> SpriteSheet {
+        SpriteSheet {
+            frame_width: width,
+            frame_height: height,
+            columns: 1,
+            clips: HashMap::new(),
+        }
+    }
+
+    pub fn clip(&self, name: &str) -> Option<&AnimationClip> {
+        self.clips.get(name)
+    }
+
+    pub fn frame_rect(&self, frame: u32) -> Rect {
+        Rect::new(
+            ((frame % self.columns) * self.frame_width) as i32,
+            ((frame / self.columns) * self.frame_height) as i32,
+            self.frame_width,
+            self.frame_height,
+        )
+    }
+}
+
+pub struct SpriteSheetDefinition {
+    pub frame_size: (u32, u32),
+    pub clips: HashMap<String, AnimationClip>,
+}
+
+impl SpriteSheetDefinition {
+    pub fn create_sprite_sheet(&self, texture_width: u32) -> SpriteSheet {
+        SpriteSheet {
+            frame_width: self.frame_size.0,
+            frame_height: self.frame_size.1,
+            columns: u32::max(1, texture_width / self.frame_size.0),
+            clips: self.clips.clone(),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Animator {
+    clip_name: String,
+    frame_position: usize,
+    frame_time: f32,
+    is_reversing: bool,
+
+    pub is_finished: bool,
+}
+
+impl Animator {
+    pub fn new(clip_name: &str) -> Animator {
+        Animator {
+            clip_name: clip_name.to_string(),
+            frame_position: 0,
+            frame_time: 0.0,
+            is_reversing: false,
+            is_finished: false,
+        }
+    }
+
+    pub fn play(&mut self, clip_name: &str) {
+        if self.clip_name != clip_name {
+            *self = Animator::new(clip_name);
+        }
+    }
+
+    pub fn update(&mut self, delta_time: f32, sprite_sheet: &SpriteSheet) {
+        let clip = match sprite_sheet.clip(&self.clip_name) {
+            Some(clip) if clip.frame_duration > 0.0 => clip,
+            _ => return,
+        };
+
+        self.frame_time += delta_time;
+
+        while self.frame_time >= clip.frame_duration && !self.is_finished {
+            self.frame_time -= clip.frame_duration;
+            self.advance(clip);
+        }
+    }
+
+    pub fn step(&mut self, sprite_sheet: &SpriteSheet) {
+        if let Some(clip) = sprite_sheet.clip(&self.clip_name) {
+            self.advance(clip);
+        }
+    }
+
+    pub fn current_frame(&self, sprite_sheet: &SpriteSheet) -> u32 {
+        sprite_sheet
+            .clip(&self.clip_name)
+            .and_then(|clip| clip.frames.get(self.frame_position))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    pub fn source_rect(&self, sprite_sheet: &SpriteSheet) -> Rect {
+        sprite_sheet.frame_rect(self.current_frame(sprite_sheet))
+    }
+
+    fn advance(&mut self, clip: &AnimationClip) {
+        let last_position = clip.frames.len().saturating_sub(1);
+
+        match clip.loop_mode {
+            LoopMode::Loop => {
+                self.frame_position = (self.frame_position + 1) % clip.frames.len().max(1);
+            }
+            LoopMode::Once => {
+                if self.frame_position < last_position {
+                    self.frame_position += 1;
+                } else {
+                    self.is_finished = true;
+                }
+            }
+            LoopMode::PingPong => {
+                if last_position == 0 {
+                    return;
+                }
+
+                if self.frame_position == last_position {
+                    self.is_reversing = true;
+                } else if self.frame_position == 0 {
+                    self.is_reversing = false;
+                }
+
+                if self.is_reversing {
+                    self.frame_position -= 1;
+                } else {
+                    self.frame_position += 1;
+                }
+            }
+        }
+    }
+}
+
+pub fn read_animations_file(
+    filepath: &str,
+) -> Result<HashMap<String, SpriteSheetDefinition>, Box<dyn Error>> {
+    let json_string = fs::read_to_string(vfs::resolve(filepath))?;
+    let animation_data: serde_json::Value = serde_json::from_str(&json_string[..])?;
+
+    let mut definitions = HashMap::new();
+
+    for sheet_data in animation_data["sprite-sheets"]
+        .as_array()
+        .ok_or("Animations file is missing a \"sprite-sheets\" array.")?
+    {
+        let texture_name = sheet_data["texture"]
+            .as_str()
+            .ok_or("Sprite sheet is missing a texture.")?
+            .to_string();
+        let frame_size = (
+            sheet_data["frame-size"]["x"].as_u64().unwrap_or(0) as u32,
+            sheet_data["frame-size"]["y"].as_u64().unwrap_or(0) as u32,
+        );
+
+        if frame_size.0 == 0 || frame_size.1 == 0 {
+            return Err(format!(
+                "Sprite sheet \"{}\" has an invalid frame size.",
+                texture_name
+            )
+            .into());
+        }
+
+        let mut clips = HashMap::new();
+
+        for clip_data in sheet_data["clips"].as_array().unwrap_or(&vec![]) {
+            let clip_name = clip_data["name"]
+                .as_str()
+                .ok_or_else(|| format!("Clip in \"{}\" is missing a name.", texture_name))?;
+            let loop_mode_name = clip_data["loop-mode"].as_str().unwrap_or("loop");
+
+            let clip = AnimationClip {
+                frames: clip_data["frames"]
+                    .as_array()
+                    .ok_or_else(|| format!("Clip \"{}\" is missing its frames.", clip_name))?
+                    .iter()
+                    .map(|frame| frame.as_u64().unwrap_or(0) as u32)
+                    .collect(),
+                frame_duration: clip_data["frame-duration"].as_f64().unwrap_or(0.0) as f32,
+                loop_mode: LoopMode::from_name(loop_mode_name).ok_or_else(|| {
+                    format!(
+                        "Clip \"{}\" has unknown loop mode \"{}\".",
+                        clip_name, loop_mode_name
+                    )
+                })?,
+            };
+
+            clips.insert(clip_name.to_string(), clip);
+        }
+
+        definitions.insert(texture_name, SpriteSheetDefinition { frame_size, clips });
+    }
+
+    Ok(definitions)
+}
+
+pub fn create_sprite_sheets(
+    texture_names: &[String],
+    textures: &[Texture],
+    definitions: &HashMap<String, SpriteSheetDefinition>,
+) -> Vec<SpriteSheet> {
+    texture_names
+        .iter()
+        .zip(textures)
+        .map(|(texture_name, texture)| {
+            let texture_data = texture.query();
+
+            match definitions.get(texture_name) {
+                Some(definition) => definition.create_sprite_sheet(texture_data.width),
+                None => SpriteSheet::single_frame(texture_data.width, texture_data.height),
+            }
+        })
+        .collect()
+}
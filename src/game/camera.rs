@@ -0,0 +1,115 @@
+use std::error::Error;
+
+use super::renderer::CameraTransform;
+
+const DEFAULT_SHAKE_INTENSITY: f32 = 1.0;
+const DEFAULT_ZOOM_INTENSITY: f32 = 1.0;
+const DEFAULT_MAX_SHAKE_OFFSET: f32 = 24.0;
+const DEFAULT_TRAUMA_DECAY: f32 = 1.5;
+
+const SHAKE_FREQUENCY: f32 = 35.0;
+const ZOOM_PUNCH_TIME: f32 = 0.8;
+
+#[derive(Clone, Copy, Debug)]
+pub struct CameraSettings {
+    pub is_enabled: bool,
+    pub shake_intensity: f32,
+    pub zoom_intensity: f32,
+    pub max_shake_offset: f32,
+    pub trauma_decay: f32,
+}
+
+impl Default for CameraSettings {
+    fn default() -> CameraSettings {
+        CameraSettings {
+            is_enabled: true,
+            shake_intensity: DEFAULT_SHAKE_INTENSITY,
+            zoom_intensity: DEFAULT_ZOOM_INTENSITY,
+            max_shake_offset: DEFAULT_MAX_SHAKE_OFFSET,
+            trauma_decay: DEFAULT_TRAUMA_DECAY,
+        }
+    }
+}
+
+pub struct Camera {
+    settings: CameraSettings,
+
+    trauma: f32,
+    shake_time: f32,
+
+    zoom_punch: f32,
+    zoom_timer: f32,
+}
+
+impl Camera {
+    pub fn new(settings: CameraSettings) -> Camera {
+        Camera {
+            settings,
+            trauma: 0.0,
+            shake_time: 0.0,
+            zoom_punch: 0.0,
+            zoom_timer: 0.0,
+        }
+    }
+
+    pub fn add_trauma(&mut self, amount: f32) {
+        self.trauma = f32::min(self.trauma + amount, 1.0);
+    }
+
+    pub fn punch_zoom(&mut self, amount: f32) {
+        self.zoom_punch = amount;
+        self.zoom_timer = ZOOM_PUNCH_TIME;
+    }
+
+    pub fn update(&mut self, delta_time: f32) {
+        self.trauma = f32::max(self.trauma - self.settings.trauma_decay * delta_time, 0.0);
+        self.zoom_timer = f32::max(self.zoom_timer - delta_time, 0.0);
+        self.shake_time += delta_time;
+    }
+
+    pub fn transform(&self) -> CameraTransform {
+        if !self.settings.is_enabled {
+            return CameraTransform::default();
+        }
+
+        let shake = self.trauma
+            * self.trauma
+            * self.settings.max_shake_offset
+            * self.settings.shake_intensity;
+        let time = self.shake_time * SHAKE_FREQUENCY;
+
+        let zoom_progress = 1.0 - self.zoom_timer / ZOOM_PUNCH_TIME;
+        let zoom = if self.zoom_timer > 0.0 {
+            self.zoom_punch
+                * self.settings.zoom_intensity
+                * (zoom_progress * std::f32::consts::PI).sin()
+        } else {
+            0.0
+        };
+
+        CameraTransform {
+            offset: (
+                shake * (0.6 * time.sin() + 0.4 * (time * 2.3 + 1.7).sin()),
+                shake * (0.6 * (time * 1.3 + 0.5).cos() + 0.4 * (time * 2.9).sin()),
+            ),
+            zoom: 1.0 + zoom,
+        }
+    }
+}
+
+pub fn read_camera_settings() -> Result<CameraSettings, Box<dyn Error>> {
+    let camera_data = super::read_config_section("camera")?;
+    let read_f32 = |name: &str, default: f32| {
+        camera_data[name]
+            .as_f64()
+            .map_or(default, |value| f64::max(value, 0.0) as f32)
+    };
+
+    Ok(CameraSettings {
+        is_enabled: camera_data["enabled"].as_bool().unwrap_or(true),
+        shake_intensity: read_f32("shake-intensity", DEFAULT_SHAKE_INTENSITY),
+        zoom_intensity: read_f32("zoom-intensity", DEFAULT_ZOOM_INTENSITY),
+        max_shake_offset: read_f32("max-shake-offset", DEFAULT_MAX_SHAKE_OFFSET),
+        trauma_decay: read_f32("trauma-decay", DEFAULT_TRAUMA_DECAY),
+    })
+}
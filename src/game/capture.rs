@@ -0,0 +1,470 @@
+use std::collections::VecDeque;
+use std::env;
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use sdl2::{
+    event::Event,
+    image::SaveSurface,
+    keyboard::Keycode,
+    pixels::{Color as Colour, PixelFormatEnum},
+    rect::{Point, Rect},
+    render::{Texture, WindowCanvas},
+    surface::Surface,
+    ttf::Font,
+};
+
+use super::logging::{log_info, log_warn};
+use super::renderer::{Anchor, Layer, Renderer, TextCommand};
+
+const CAPTURES_DIRECTORY: &str = "captures";
+const CAPTURE_PIXEL_FORMAT: PixelFormatEnum = PixelFormatEnum::ARGB8888;
+
+const HEADLESS_FLAG: &str = "--headless";
+const FRAMES_FLAG: &str = "--frames";
+const CAPTURE_FRAME_FLAG: &str = "--capture-frame";
+const CAPTURE_DIRECTORY_FLAG: &str = "--capture-directory";
+const DEFAULT_HEADLESS_FRAME_COUNT: u64 = 60;
+const HEADLESS_DELTA_TIME: f32 = 1.0 / 60.0;
+
+const RECORDING_INDICATOR_OFFSET: i32 = 10;
+const RECORDING_INDICATOR_SIZE: u32 = 12;
+const RECORDING_INDICATOR_TEXT_SCALE: f32 = 0.15;
+const RECORDING_INDICATOR_COLOUR: Colour = Colour::RGB(230, 40, 40);
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CaptureRequest {
+    Screenshot,
+    ToggleRecording,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct CaptureSettings {
+    pub recording_seconds: f32,
+    pub recording_fps: f32,
+    pub recording_scale: f32,
+}
+
+impl Default for CaptureSettings {
+    fn default() -> CaptureSettings {
+        CaptureSettings {
+            recording_seconds: 5.0,
+            recording_fps: 15.0,
+            recording_scale: 0.5,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct HeadlessSettings {
+    frame_count: u64,
+    capture_frames: Vec<u64>,
+    capture_directory: Option<PathBuf>,
+}
+
+struct CapturedFrame {
+    width: u32,
+    height: u32,
+    pitch: u32,
+    pixels: Vec<u8>,
+}
+
+impl CapturedFrame {
+    fn save_png(&mut self, filepath: &Path) -> Result<(), String> {
+        Surface::from_data(
+            &mut self.pixels,
+            self.width,
+            self.height,
+            self.pitch,
+            CAPTURE_PIXEL_FORMAT,
+        )?
+        .save(filepath)
+    }
+
+    fn scaled(mut self, scale: f32) -> Result<CapturedFrame, String> {
+        let width = u32::max((self.width as f32 * scale) as u32, 1);
+        let height = u32::max((self.height as f32 * scale) as u32, 1);
+
+        if (width, height) == (self.width, self.height) {
+            return Ok(self);
+        }
+
+        let source = Surface::from_data(
+            &mut self.pixels,
+            self.width,
+            self.height,
+            self.pitch,
+            CAPTURE_PIXEL_FORMAT,
+        )?;
+        let mut scaled = Surface::new(width, height, CAPTURE_PIXEL_FORMAT)?;
+        source.blit_scaled(None, &mut scaled, None)?;
+
+        Ok(CapturedFrame {
+            width,
+            height,
+            pitch: scaled.pitch(),
+            pixels: scaled.with_lock(|pixels| pixels.to_vec()),
+        })
+    }
+}
+
+struct Recording {
+    frames: VecDeque<CapturedFrame>,
+    frame_timer: f32,
+    elapsed_time: f32,
+}
+
+pub struct FrameCapture {
+    settings: CaptureSettings,
+    headless: Option<HeadlessSettings>,
+    captures_directory: PathBuf,
+
+    frame_index: u64,
+    is_screenshot_requested: bool,
+    recording: Option<Recording>,
+}
+
+impl FrameCapture {
+    pub fn new(settings: CaptureSettings, headless: Option<HeadlessSettings>) -> FrameCapture {
+        let captures_directory = headless
+            .as_ref()
+            .and_then(|headless| headless.capture_directory.clone())
+            .or_else(|| {
+                super::user_data_directory().map(|directory| directory.join(CAPTURES_DIRECTORY))
+            })
+            .unwrap_or_else(|| PathBuf::from(CAPTURES_DIRECTORY));
+
+        FrameCapture {
+            settings,
+            headless,
+            captures_directory,
+            frame_index: 0,
+            is_screenshot_requested: false,
+            recording: None,
+        }
+    }
+
+    pub fn fixed_delta_time(&self) -> Option<f32> {
+        self.headless.as_ref().map(|_| HEADLESS_DELTA_TIME)
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.headless
+            .as_ref()
+            .is_some_and(|headless| self.frame_index >= headless.frame_count)
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.recording.is_some()
+    }
+
+    pub fn handle_event(&mut self, event: &Event) -> bool {
+        match event {
+            Event::KeyDown {
+                keycode: Some(Keycode::F12),
+                repeat: false,
+                ..
+            } => {
+                self.request(CaptureRequest::Screenshot);
+
+                true
+            }
+            Event::KeyDown {
+                keycode: Some(Keycode::F9),
+                repeat: false,
+                ..
+            } => {
+                self.request(CaptureRequest::ToggleRecording);
+
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub fn request(&mut self, request: CaptureRequest) {
+        match request {
+            CaptureRequest::Screenshot => self.is_screenshot_requested = true,
+            CaptureRequest::ToggleRecording => match self.recording.take() {
+                Some(recording) => self.write_recording(recording),
+                None => {
+                    self.recording = Some(Recording {
+                        frames: VecDeque::new(),
+                        frame_timer: 0.0,
+                        elapsed_time: 0.0,
+                    });
+
+                    log_info!(
+                        "Recording, the last {} seconds are kept",
+                        self.settings.recording_seconds
+                    );
+                }
+            },
+        }
+    }
+
+    pub fn capture(
+        &mut self,
+        canvas: &mut WindowCanvas,
+        offscreen_target: Option<&mut Texture>,
+        delta_time: f32,
+    ) {
+        self.frame_index += 1;
+
+        let is_headless_capture_due = self
+            .headless
+            .as_ref()
+            .is_some_and(|headless| headless.capture_frames.contains(&self.frame_index));
+        let frame_interval = 1.0 / f32::max(self.settings.recording_fps, 1.0);
+        let is_recording_frame_due = match &mut self.recording {
+            Some(recording) => {
+                recording.elapsed_time += delta_time;
+                recording.frame_timer += delta_time;
+
+                recording.frames.is_empty() || recording.frame_timer >= frame_interval
+            }
+            None => false,
+        };
+
+        if !self.is_screenshot_requested && !is_headless_capture_due && !is_recording_frame_due {
+            return;
+        }
+
+        let mut frame = match read_frame(canvas, offscreen_target) {
+            Ok(frame) => frame,
+            Err(error) => {
+                log_warn!("Could not read back the frame: {}", error);
+                self.is_screenshot_requested = false;
+
+                return;
+            }
+        };
+
+        if self.is_screenshot_requested {
+            self.is_screenshot_requested = false;
+            self.save_frame(&mut frame, &format!("screenshot-{}.png", timestamp()));
+        }
+
+        if is_headless_capture_due {
+            self.save_frame(&mut frame, &format!("frame-{:06}.png", self.frame_index));
+        }
+
+        if is_recording_frame_due {
+            let max_frame_count =
+                (self.settings.recording_seconds * self.settings.recording_fps).ceil() as usize;
+            let recording_scale = self.settings.recording_scale;
+
+            if let Some(recording) = &mut self.recording {
+                recording.frame_timer %= frame_interval;
+
+                match frame.scaled(recording_scale) {
+                    Ok(frame) => {
+                        if recording.frames.len() >= max_frame_count {
+                            recording.frames.pop_front();
+                        }
+
+                        recording.frames.push_back(frame);
+                    }
+                    Err(error) => log_warn!("Could not scale a recorded frame: {}", error),
+                }
+            }
+        }
+    }
+
+    pub fn draw(&self, renderer: &mut Renderer, font: &Font, font_index: usize) {
+        let recording = match &self.recording {
+            Some(recording) => recording,
+            None => return,
+        };
+
+        let viewport = renderer.viewport();
+        let indicator_x =
+            viewport.width() as i32 - RECORDING_INDICATOR_OFFSET - RECORDING_INDICATOR_SIZE as i32;
+        let line_height = (font.height() as f32 * RECORDING_INDICATOR_TEXT_SCALE) as i32;
+        let line_y = viewport.height() as i32 - RECORDING_INDICATOR_OFFSET - line_height;
+
+        renderer.fill_rect(
+            Layer::Interface,
+            Rect::new(
+                indicator_x,
+                line_y + (line_height - RECORDING_INDICATOR_SIZE as i32) / 2,
+                RECORDING_INDICATOR_SIZE,
+                RECORDING_INDICATOR_SIZE,
+            ),
+            RECORDING_INDICATOR_COLOUR,
+        );
+        renderer.draw_text(
+            Layer::Interface,
+            TextCommand::new(
+                format!(
+                    "REC {:.1}s (F9 to save)",
+                    f32::min(recording.elapsed_time, self.settings.recording_seconds)
+                ),
+                font_index,
+                RECORDING_INDICATOR_COLOUR,
+                Point::new(indicator_x - RECORDING_INDICATOR_OFFSET, line_y),
+            )
+            .with_anchor(Anchor::TopRight)
+            .with_scale(RECORDING_INDICATOR_TEXT_SCALE),
+        );
+    }
+
+    fn save_frame(&self, frame: &mut CapturedFrame, file_name: &str) {
+        let filepath = self.captures_directory.join(file_name);
+        let result = fs::create_dir_all(&self.captures_directory)
+            .map_err(|error| error.to_string())
+            .and_then(|_| frame.save_png(&filepath));
+
+        match result {
+            Ok(()) => log_info!("Saved {}", filepath.display()),
+            Err(error) => log_warn!("Could not save {}: {}", filepath.display(), error),
+        }
+    }
+
+    fn write_recording(&self, recording: Recording) {
+        let recording_directory = self
+            .captures_directory
+            .join(format!("recording-{}", timestamp()));
+        let frame_count = recording.frames.len();
+
+        let writer = thread::Builder::new()
+            .name("recording-writer".to_string())
+            .spawn(move || {
+                if let Err(error) = fs::create_dir_all(&recording_directory) {
+                    log_warn!(
+                        "Could not create {}: {}",
+                        recording_directory.display(),
+                        error
+                    );
+
+                    return;
+                }
+
+                for (frame_index, mut frame) in recording.frames.into_iter().enumerate() {
+                    let filepath =
+                        recording_directory.join(format!("frame-{:04}.png", frame_index + 1));
+
+                    if let Err(error) = frame.save_png(&filepath) {
+                        log_warn!("Could not save {}: {}", filepath.display(), error);
+
+                        return;
+                    }
+                }
+
+                log_info!(
+                    "Saved {} recorded frames to {}",
+                    frame_count,
+                    recording_directory.display()
+                );
+            });
+
+        if let Err(error) = writer {
+            log_warn!("Could not start writing the recording: {}", error);
+        }
+    }
+}
+
+fn read_frame(
+    canvas: &mut WindowCanvas,
+    offscreen_target: Option<&mut Texture>,
+) -> Result<CapturedFrame, String> {
+    let read_pixels = |target_canvas: &WindowCanvas| {
+        let viewport = target_canvas.viewport();
+
+        target_canvas
+            .read_pixels(viewport, CAPTURE_PIXEL_FORMAT)
+            .map(|pixels| CapturedFrame {
+                width: viewport.width(),
+                height: viewport.height(),
+                pitch: viewport.width() * CAPTURE_PIXEL_FORMAT.byte_size_per_pixel() as u32,
+                pixels,
+            })
+    };
+
+    match offscreen_target {
+        Some(offscreen_target) => {
+            let mut frame = Err("The offscreen target was not read".to_string());
+
+            canvas
+                .with_texture_canvas(offscreen_target, |target_canvas| {
+                    frame = read_pixels(target_canvas);
+                })
+                .map_err(|error| error.to_string())?;
+
+            frame
+        }
+        None => read_pixels(canvas),
+    }
+}
+
+fn timestamp() -> String {
+    let duration = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+
+    format!("{}-{:03}", duration.as_secs(), duration.subsec_millis())
+}
+
+pub fn read_capture_settings() -> Result<CaptureSettings, Box<dyn Error>> {
+    let capture_data = super::read_config_section("capture")?;
+    let default = CaptureSettings::default();
+
+    let read_positive = |name: &str, default: f32| {
+        capture_data[name]
+            .as_f64()
+            .map_or(default, |value| f64::max(value, 0.01) as f32)
+    };
+
+    Ok(CaptureSettings {
+        recording_seconds: read_positive("recording-seconds", default.recording_seconds),
+        recording_fps: read_positive("recording-fps", default.recording_fps),
+        recording_scale: f32::min(
+            read_positive("recording-scale", default.recording_scale),
+            1.0,
+        ),
+    })
+}
+
+pub fn headless_settings() -> Option<HeadlessSettings> {
+    let arguments: Vec<String> = env::args().collect();
+
+    if !arguments.iter().any(|argument| argument == HEADLESS_FLAG) {
+        return None;
+    }
+
+    let values = |flag: &str| {
+        arguments
+            .windows(2)
+            .filter(|pair| pair[0] == flag)
+            .map(|pair| pair[1].clone())
+            .collect::<Vec<String>>()
+    };
+
+    let mut capture_frames: Vec<u64> = values(CAPTURE_FRAME_FLAG)
+        .iter()
+        .filter_map(|frame| frame.parse().ok())
+        .filter(|frame| *frame > 0)
+        .collect();
+    let frame_count = values(FRAMES_FLAG)
+        .iter()
+        .find_map(|frame_count| frame_count.parse().ok())
+        .filter(|frame_count| *frame_count > 0)
+        .or_else(|| capture_frames.iter().copied().max())
+        .unwrap_or(DEFAULT_HEADLESS_FRAME_COUNT);
+
+    if capture_frames.is_empty() {
+        capture_frames.push(frame_count);
+    }
+
+    Some(HeadlessSettings {
+        frame_count,
+        capture_frames,
+        capture_directory: values(CAPTURE_DIRECTORY_FLAG)
+            .into_iter()
+            .next()
+            .map(PathBuf::from),
+    })
+}
@@ -0,0 +1,664 @@
+use std::collections::{BTreeMap, HashSet};
+use std::error::Error;
+use std::path::Path;
+use std::rc::Rc;
+
+use sdl2::{
+    image::ImageRWops,
+    pixels::{Color as Colour, PixelFormatEnum},
+    rect::{Point, Rect},
+    rwops::RWops,
+};
+
+use super::animation::SpriteSheet;
+use super::renderer::{Layer, Renderer};
+use super::vfs;
+
+const DEFAULT_ALPHA_THRESHOLD: u8 = 128;
+const DEBUG_CIRCLE_SEGMENTS: u32 = 16;
+
+#[derive(Clone, Copy, Debug)]
+pub struct CollisionSettings {
+    pub use_pixel_masks: bool,
+    pub alpha_threshold: u8,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Bounds {
+    pub left: f32,
+    pub top: f32,
+    pub right: f32,
+    pub bottom: f32,
+}
+
+impl Bounds {
+    pub fn from_center(center: (f32, f32), size: (f32, f32)) -> Bounds {
+        Bounds {
+            left: center.0 - size.0 / 2.0,
+            top: center.1 - size.1 / 2.0,
+            right: center.0 + size.0 / 2.0,
+            bottom: center.1 + size.1 / 2.0,
+        }
+    }
+
+    pub fn intersects(&self, other: &Bounds) -> bool {
+        self.left < other.right
+            && other.left < self.right
+            && self.top < other.bottom
+            && other.top < self.bottom
+    }
+
+    pub fn union(&self, other: &Bounds) -> Bounds {
+        Bounds {
+            left: f32::min(self.left, other.left),
+            top: f32::min(self.top, other.top),
+            right: f32::max(self.right, other.right),
+            bottom: f32::max(self.bottom, other.bottom),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct PixelMask {
+    width: u32,
+    height: u32,
+    pixel_size: u32,
+    pixels: Vec<bool>,
+}
+
+impl PixelMask {
+    pub fn from_rows(rows: &[Vec<bool>], pixel_size: u32) -> PixelMask {
+        let width = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+        let mut pixels = vec![false; width * rows.len()];
+
+        for (y, row) in rows.iter().enumerate() {
+            for (x, is_set) in row.iter().enumerate() {
+                pixels[y * width + x] = *is_set;
+            }
+        }
+
+        PixelMask {
+            width: width as u32,
+            height: rows.len() as u32,
+            pixel_size,
+            pixels,
+        }
+    }
+
+    pub fn from_alpha(
+        alpha_values: &[u8],
+        width: u32,
+        height: u32,
+        alpha_threshold: u8,
+    ) -> PixelMask {
+        PixelMask {
+            width,
+            height,
+            pixel_size: 1,
+            pixels: alpha_values
+                .iter()
+                .map(|alpha| *alpha >= alpha_threshold)
+                .collect(),
+        }
+    }
+
+    pub fn size(&self) -> (f32, f32) {
+        (
+            (self.width * self.pixel_size) as f32,
+            (self.height * self.pixel_size) as f32,
+        )
+    }
+
+    pub fn is_set(&self, x: u32, y: u32) -> bool {
+        x < self.width && y < self.height && self.pixels[(y * self.width + x) as usize]
+    }
+
+    pub fn draw_outline(&self, renderer: &mut Renderer, colour: Colour, center: (f32, f32)) {
+        let (width, height) = self.size();
+        let origin = (
+            (center.0 - width / 2.0) as i32,
+            (center.1 - height / 2.0) as i32,
+        );
+        let pixel_size = self.pixel_size as i32;
+
+        for y in 0..self.height {
+            for x in 0..self.width {
+                if !self.is_set(x, y) {
+                    continue;
+                }
+
+                let left = origin.0 + x as i32 * pixel_size;
+                let top = origin.1 + y as i32 * pixel_size;
+                let (right, bottom) = (left + pixel_size, top + pixel_size);
+
+                for (is_edge, start, end) in &[
+                    (
+                        x == 0 || !self.is_set(x - 1, y),
+                        (left, top),
+                        (left, bottom),
+                    ),
+                    (!self.is_set(x + 1, y), (right, top), (right, bottom)),
+                    (y == 0 || !self.is_set(x, y - 1), (left, top), (right, top)),
+                    (!self.is_set(x, y + 1), (left, bottom), (right, bottom)),
+                ] {
+                    if *is_edge {
+                        renderer.draw_lines(
+                            Layer::Debug,
+                            vec![Point::from(*start), Point::from(*end)],
+                            colour,
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    fn overlaps(
+        &self,
+        center: (f32, f32),
+        other: &ColliderShape,
+        other_center: (f32, f32),
+    ) -> bool {
+        let mask_bounds = Bounds::from_center(center, self.size());
+        let other_bounds = other.bounds_at(other_center);
+
+        if !mask_bounds.intersects(&other_bounds) {
+            return false;
+        }
+
+        let pixel_size = self.pixel_size as f32;
+        let to_pixel = |pixel: f32, limit: u32| f32::min(f32::max(pixel, 0.0), limit as f32) as u32;
+
+        let first_x = to_pixel(
+            ((other_bounds.left - mask_bounds.left) / pixel_size).floor(),
+            self.width,
+        );
+        let last_x = to_pixel(
+            ((other_bounds.right - mask_bounds.left) / pixel_size).ceil(),
+            self.width,
+        );
+        let first_y = to_pixel(
+            ((other_bounds.top - mask_bounds.top) / pixel_size).floor(),
+            self.height,
+        );
+        let last_y = to_pixel(
+            ((other_bounds.bottom - mask_bounds.top) / pixel_size).ceil(),
+            self.height,
+        );
+
+        for y in first_y..last_y {
+            for x in first_x..last_x {
+                if !self.is_set(x, y) {
+                    continue;
+                }
+
+                let pixel_center = (
+                    mask_bounds.left + (x as f32 + 0.5) * pixel_size,
+                    mask_bounds.top + (y as f32 + 0.5) * pixel_size,
+                );
+                let pixel_shape = ColliderShape::Aabb {
+                    width: pixel_size,
+                    height: pixel_size,
+                };
+
+                if shapes_overlap(&pixel_shape, pixel_center, other, other_center) {
+                    return true;
+                }
+            }
+        }
+
+        false
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum ColliderShape {
+    Aabb { width: f32, height: f32 },
+    Circle { radius: f32 },
+    Mask(Rc<PixelMask>),
+}
+
+impl ColliderShape {
+    pub fn size(&self) -> (f32, f32) {
+        match self {
+            ColliderShape::Aabb { width, height } => (*width, *height),
+            ColliderShape::Circle { radius } => (radius * 2.0, radius * 2.0),
+            ColliderShape::Mask(mask) => mask.size(),
+        }
+    }
+
+    pub fn bounds_at(&self, center: (f32, f32)) -> Bounds {
+        Bounds::from_center(center, self.size())
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Collider<T> {
+    pub tag: T,
+    pub shape: ColliderShape,
+
+    pub position: (f32, f32),
+    pub previous_position: (f32, f32),
+
+    pub layer: u32,
+    pub collides_with: u32,
+}
+
+impl<T> Collider<T> {
+    pub fn new(
+        tag: T,
+        shape: ColliderShape,
+        position: (f32, f32),
+        layer: u32,
+        collides_with: u32,
+    ) -> Collider<T> {
+        Collider {
+            tag,
+            shape,
+            position,
+            previous_position: position,
+            layer,
+            collides_with,
+        }
+    }
+
+    pub fn moving_from(mut self, previous_position: (f32, f32)) -> Collider<T> {
+        self.previous_position = previous_position;
+
+        self
+    }
+
+    pub fn position_at(&self, time: f32) -> (f32, f32) {
+        (
+            self.previous_position.0 + (self.position.0 - self.previous_position.0) * time,
+            self.previous_position.1 + (self.position.1 - self.previous_position.1) * time,
+        )
+    }
+
+    pub fn swept_bounds(&self) -> Bounds {
+        self.shape
+            .bounds_at(self.previous_position)
+            .union(&self.shape.bounds_at(self.position))
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct CollisionEvent<T> {
+    pub first: T,
+    pub second: T,
+
+    pub time: f32,
+    pub first_position: (f32, f32),
+}
+
+pub struct CollisionWorld<T> {
+    cell_size: f32,
+    colliders: Vec<Collider<T>>,
+    grid: BTreeMap<(i32, i32), Vec<usize>>,
+    events: Vec<CollisionEvent<T>>,
+}
+
+impl<T: Copy> CollisionWorld<T> {
+    pub fn new(cell_size: f32) -> CollisionWorld<T> {
+        CollisionWorld {
+            cell_size,
+            colliders: vec![],
+            grid: BTreeMap::new(),
+            events: vec![],
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.colliders.clear();
+        self.events.clear();
+    }
+
+    pub fn add(&mut self, collider: Collider<T>) {
+        self.colliders.push(collider);
+    }
+
+    pub fn collider_count(&self) -> usize {
+        self.colliders.len()
+    }
+
+    pub fn draw_debug(&self, renderer: &mut Renderer, colour: Colour) {
+        for collider in &self.colliders {
+            match &collider.shape {
+                ColliderShape::Aabb { width, height } => {
+                    renderer.draw_rect(
+                        Layer::Debug,
+                        Rect::from_center(
+                            Point::new(collider.position.0 as i32, collider.position.1 as i32),
+                            *width as u32,
+                            *height as u32,
+                        ),
+                        colour,
+                    );
+                }
+                ColliderShape::Circle { radius } => {
+                    let points: Vec<Point> = (0..=DEBUG_CIRCLE_SEGMENTS)
+                        .map(|segment| {
+                            let angle = segment as f32 / DEBUG_CIRCLE_SEGMENTS as f32
+                                * std::f32::consts::PI
+                                * 2.0;
+
+                            Point::new(
+                                (collider.position.0 + angle.cos() * radius) as i32,
+                                (collider.position.1 + angle.sin() * radius) as i32,
+                            )
+                        })
+                        .collect();
+
+                    renderer.draw_lines(Layer::Debug, points, colour);
+                }
+                ColliderShape::Mask(mask) => mask.draw_outline(renderer, colour, collider.position),
+            }
+        }
+    }
+
+    pub fn detect(&mut self) -> &[CollisionEvent<T>] {
+        self.grid.clear();
+        self.events.clear();
+
+        for (collider_index, collider) in self.colliders.iter().enumerate() {
+            let bounds = collider.swept_bounds();
+            let first_cell = (
+                (bounds.left / self.cell_size).floor() as i32,
+                (bounds.top / self.cell_size).floor() as i32,
+            );
+            let last_cell = (
+                (bounds.right / self.cell_size).floor() as i32,
+                (bounds.bottom / self.cell_size).floor() as i32,
+            );
+
+            for cell_y in first_cell.1..=last_cell.1 {
+                for cell_x in first_cell.0..=last_cell.0 {
+                    self.grid
+                        .entry((cell_x, cell_y))
+                        .or_default()
+                        .push(collider_index);
+                }
+            }
+        }
+
+        let mut tested_pairs = HashSet::new();
+
+        for cell_colliders in self.grid.values() {
+            for (cell_index, first_index) in cell_colliders.iter().enumerate() {
+                for second_index in &cell_colliders[cell_index + 1..] {
+                    let (first, second) = (
+                        &self.colliders[*first_index],
+                        &self.colliders[*second_index],
+                    );
+
+                    let (first, second) = if first.collides_with & second.layer != 0 {
+                        (first, second)
+                    } else if second.collides_with & first.layer != 0 {
+                        (second, first)
+                    } else {
+                        continue;
+                    };
+
+                    let pair = (
+                        usize::min(*first_index, *second_index),
+                        usize::max(*first_index, *second_index),
+                    );
+
+                    if !tested_pairs.insert(pair) {
+                        continue;
+                    }
+
+                    if let Some(time) = sweep(first, second) {
+                        self.events.push(CollisionEvent {
+                            first: first.tag,
+                            second: second.tag,
+                            time,
+                            first_position: first.position_at(time),
+                        });
+                    }
+                }
+            }
+        }
+
+        self.events
+            .sort_by(|first, second| first.time.total_cmp(&second.time));
+
+        &self.events
+    }
+}
+
+fn sweep<T>(first: &Collider<T>, second: &Collider<T>) -> Option<f32> {
+    let relative_motion = (
+        (first.position.0 - first.previous_position.0)
+            - (second.position.0 - second.previous_position.0),
+        (first.position.1 - first.previous_position.1)
+            - (second.position.1 - second.previous_position.1),
+    );
+
+    if let (
+        ColliderShape::Aabb {
+            width: first_width,
+            height: first_height,
+        },
+        ColliderShape::Aabb {
+            width: second_width,
+            height: second_height,
+        },
+    ) = (&first.shape, &second.shape)
+    {
+        return sweep_aabb(
+            (
+                first.previous_position.0 - second.previous_position.0,
+                first.previous_position.1 - second.previous_position.1,
+            ),
+            relative_motion,
+            (
+                (first_width + second_width) / 2.0,
+                (first_height + second_height) / 2.0,
+            ),
+        );
+    }
+
+    let smallest_extent = [first.shape.size(), second.shape.size()]
+        .iter()
+        .map(|size| f32::min(size.0, size.1))
+        .fold(f32::INFINITY, f32::min);
+    let step_length = f32::max(smallest_extent / 2.0, 1.0);
+    let distance = relative_motion.0.hypot(relative_motion.1);
+    let step_count = u32::max((distance / step_length).ceil() as u32, 1);
+
+    (0..=step_count)
+        .map(|step| step as f32 / step_count as f32)
+        .find(|time| {
+            shapes_overlap(
+                &first.shape,
+                first.position_at(*time),
+                &second.shape,
+                second.position_at(*time),
+            )
+        })
+}
+
+fn sweep_aabb(start: (f32, f32), motion: (f32, f32), half_extents: (f32, f32)) -> Option<f32> {
+    let mut entry_time: f32 = 0.0;
+    let mut exit_time: f32 = 1.0;
+
+    for (start, motion, half_extent) in &[
+        (start.0, motion.0, half_extents.0),
+        (start.1, motion.1, half_extents.1),
+    ] {
+        if *motion == 0.0 {
+            if start.abs() >= *half_extent {
+                return None;
+            }
+
+            continue;
+        }
+
+        let first_time = (-half_extent - start) / motion;
+        let second_time = (half_extent - start) / motion;
+
+        entry_time = f32::max(entry_time, f32::min(first_time, second_time));
+        exit_time = f32::min(exit_time, f32::max(first_time, second_time));
+
+        if entry_time >= exit_time {
+            return None;
+        }
+    }
+
+    Some(entry_time)
+}
+
+fn shapes_overlap(
+    first: &ColliderShape,
+    first_center: (f32, f32),
+    second: &ColliderShape,
+    second_center: (f32, f32),
+) -> bool {
+    use ColliderShape::*;
+
+    match (first, second) {
+        (Mask(mask), _) => mask.overlaps(first_center, second, second_center),
+        (_, Mask(mask)) => mask.overlaps(second_center, first, first_center),
+        (
+            Circle {
+                radius: first_radius,
+            },
+            Circle {
+                radius: second_radius,
+            },
+        ) => {
+            let distance =
+                (first_center.0 - second_center.0).hypot(first_center.1 - second_center.1);
+
+            distance < first_radius + second_radius
+        }
+        (Circle { radius }, Aabb { .. }) => {
+            circle_overlaps_bounds(first_center, *radius, &second.bounds_at(second_center))
+        }
+        (Aabb { .. }, Circle { radius }) => {
+            circle_overlaps_bounds(second_center, *radius, &first.bounds_at(first_center))
+        }
+        (Aabb { .. }, Aabb { .. }) => first
+            .bounds_at(first_center)
+            .intersects(&second.bounds_at(second_center)),
+    }
+}
+
+fn circle_overlaps_bounds(center: (f32, f32), radius: f32, bounds: &Bounds) -> bool {
+    let closest_point = (
+        f32::min(f32::max(center.0, bounds.left), bounds.right),
+        f32::min(f32::max(center.1, bounds.top), bounds.bottom),
+    );
+
+    (center.0 - closest_point.0).hypot(center.1 - closest_point.1) < radius
+}
+
+pub fn create_sprite_masks(
+    texture_filepath: &Path,
+    sprite_sheet: &SpriteSheet,
+    alpha_threshold: u8,
+) -> Result<Vec<Rc<PixelMask>>, Box<dyn Error>> {
+    let texture_data = vfs::read(&texture_filepath.to_string_lossy())?;
+    let surface = RWops::from_bytes(&texture_data)?
+        .load()?
+        .convert_format(PixelFormatEnum::RGBA32)?;
+    let pitch = surface.pitch() as usize;
+    let frame_count = sprite_sheet.columns * (surface.height() / sprite_sheet.frame_height);
+
+    let mut masks = vec![];
+
+    surface.with_lock(|pixels| {
+        for frame in 0..frame_count {
+            let frame_rect = sprite_sheet.frame_rect(frame);
+            let mut alpha_values =
+                Vec::with_capacity((frame_rect.width() * frame_rect.height()) as usize);
+
+            for y in frame_rect.y() as usize..frame_rect.bottom() as usize {
+                for x in frame_rect.x() as usize..frame_rect.right() as usize {
+                    alpha_values.push(pixels[y * pitch + x * 4 + 3]);
+                }
+            }
+
+            masks.push(Rc::new(PixelMask::from_alpha(
+                &alpha_values,
+                frame_rect.width(),
+                frame_rect.height(),
+                alpha_threshold,
+            )));
+        }
+    });
+
+    Ok(masks)
+}
+
+pub fn read_collision_settings() -> Result<CollisionSettings, Box<dyn Error>> {
+    let collision_data = super::read_config_section("collision")?;
+
+    Ok(CollisionSettings {
+        use_pixel_masks: collision_data["pixel-masks"].as_bool().unwrap_or(false),
+        alpha_threshold: collision_data["alpha-threshold"]
+            .as_u64()
+            .map_or(DEFAULT_ALPHA_THRESHOLD, |threshold| {
+                u64::min(threshold, 255) as u8
+            }),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CELL_SIZE: f32 = 32.0;
+
+    fn detected_pairs(colliders: &[Collider<usize>]) -> Vec<(usize, usize)> {
+        let mut collision_world = CollisionWorld::new(CELL_SIZE);
+
+        for collider in colliders {
+            collision_world.add(collider.clone());
+        }
+
+        collision_world
+            .detect()
+            .iter()
+            .map(|event| (event.first, event.second))
+            .collect()
+    }
+
+    #[test]
+    fn event_order_is_stable() {
+        let mut colliders = vec![Collider::new(
+            0,
+            ColliderShape::Aabb {
+                width: 640.0,
+                height: 640.0,
+            },
+            (320.0, 320.0),
+            1,
+            2,
+        )];
+
+        for index in 1..=40 {
+            let position = (
+                (index % 8) as f32 * 80.0 + 16.0,
+                (index / 8) as f32 * 80.0 + 16.0,
+            );
+            colliders.push(Collider::new(
+                index,
+                ColliderShape::Circle { radius: 8.0 },
+                position,
+                2,
+                0,
+            ));
+        }
+
+        let first_run = detected_pairs(&colliders);
+
+        assert_eq!(first_run.len(), 40);
+        assert!(first_run.iter().all(|(first, _)| *first == 0));
+
+        for _ in 0..10 {
+            assert_eq!(detected_pairs(&colliders), first_run);
+        }
+    }
+}
@@ -0,0 +1,548 @@
+use std::collections::{BTreeMap, VecDeque};
+use std::env;
+use std::fs;
+
+use sdl2::{
+    event::Event,
+    keyboard::{Keycode, Scancode},
+    pixels::Color as Colour,
+    rect::{Point, Rect},
+    render::WindowCanvas,
+    ttf::Font,
+};
+
+use super::capture::CaptureRequest;
+use super::renderer::{Anchor, Layer, Renderer, TextCommand};
+use super::scene::Scene;
+use super::vfs;
+
+const AUTOEXEC_FILE_NAME: &str = "config/autoexec.cfg";
+const CONSOLE_FLAG: &str = "--console";
+const TIME_SCALE_VARIABLE: &str = "timescale";
+
+const MAX_OUTPUT_LINES: usize = 256;
+const MAX_HISTORY_LENGTH: usize = 64;
+const MAX_EXEC_DEPTH: usize = 8;
+
+const CONSOLE_HEIGHT_FRACTION: f32 = 0.45;
+const CONSOLE_SLIDE_SPEED: f32 = 6.0;
+const CARET_BLINK_INTERVAL: f32 = 0.5;
+const CONSOLE_OFFSET: i32 = 10;
+const CONSOLE_TEXT_SCALE: f32 = 0.15;
+const CONSOLE_BACKGROUND_COLOUR: Colour = Colour::RGBA(10, 10, 30, 225);
+const CONSOLE_ECHO_COLOUR: Colour = Colour::RGB(140, 140, 160);
+const CONSOLE_ERROR_COLOUR: Colour = Colour::RGB(255, 110, 110);
+
+const BUILT_IN_COMMANDS: [ConsoleCommand; 7] = [
+    ConsoleCommand {
+        name: "help",
+        usage: "help - List commands and variables.",
+    },
+    ConsoleCommand {
+        name: "clear",
+        usage: "clear - Clear the console output.",
+    },
+    ConsoleCommand {
+        name: "cvars",
+        usage: "cvars - List variables with their values.",
+    },
+    ConsoleCommand {
+        name: "set",
+        usage: "set <variable> <value> - Set a variable, creating it if needed.",
+    },
+    ConsoleCommand {
+        name: "exec",
+        usage: "exec <file> - Run each line of a script file.",
+    },
+    ConsoleCommand {
+        name: "screenshot",
+        usage: "screenshot - Save the next frame as a PNG.",
+    },
+    ConsoleCommand {
+        name: "record",
+        usage: "record - Start recording, or save the recorded frames.",
+    },
+];
+
+#[derive(Clone, Copy, Debug)]
+pub struct ConsoleCommand {
+    pub name: &'static str,
+    pub usage: &'static str,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ConsoleVariable {
+    pub name: &'static str,
+    pub default: f32,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct VariableValue {
+    value: f32,
+    default: f32,
+}
+
+pub struct Console {
+    is_enabled: bool,
+    is_open: bool,
+    openness: f32,
+    caret_timer: f32,
+
+    input: String,
+    output: VecDeque<(String, Colour)>,
+    scroll_offset: usize,
+    history: Vec<String>,
+    history_index: Option<usize>,
+
+    variables: BTreeMap<String, VariableValue>,
+    scene_variables: Vec<&'static str>,
+    pending_commands: Vec<String>,
+    exec_depth: usize,
+    capture_requests: Vec<CaptureRequest>,
+}
+
+impl Console {
+    pub fn new(is_enabled: bool) -> Console {
+        let mut variables = BTreeMap::new();
+        variables.insert(
+            TIME_SCALE_VARIABLE.to_string(),
+            VariableValue {
+                value: 1.0,
+                default: 1.0,
+            },
+        );
+
+        Console {
+            is_enabled,
+            is_open: false,
+            openness: 0.0,
+            caret_timer: 0.0,
+            input: String::new(),
+            output: VecDeque::new(),
+            scroll_offset: 0,
+            history: vec![],
+            history_index: None,
+            variables,
+            scene_variables: vec![],
+            pending_commands: if is_enabled {
+                vec![format!("exec {}", AUTOEXEC_FILE_NAME)]
+            } else {
+                vec![]
+            },
+            exec_depth: 0,
+            capture_requests: vec![],
+        }
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.openness > 0.0
+    }
+
+    pub fn time_scale(&self) -> f32 {
+        self.variables
+            .get(TIME_SCALE_VARIABLE)
+            .map_or(1.0, |variable| f32::max(variable.value, 0.0))
+    }
+
+    pub fn take_capture_requests(&mut self) -> Vec<CaptureRequest> {
+        std::mem::take(&mut self.capture_requests)
+    }
+
+    pub fn bind_scene(&mut self, scene: &mut Box<dyn Scene>, canvas: &WindowCanvas) {
+        let scene_variables = scene.console_variables();
+        self.scene_variables = scene_variables
+            .iter()
+            .map(|variable| variable.name)
+            .collect();
+
+        for variable in scene_variables {
+            let value = self
+                .variables
+                .entry(variable.name.to_string())
+                .or_insert(VariableValue {
+                    value: variable.default,
+                    default: variable.default,
+                });
+            value.default = variable.default;
+
+            scene.on_console_variable_changed(variable.name, value.value);
+        }
+
+        for command in std::mem::take(&mut self.pending_commands) {
+            self.execute(&command, scene, canvas);
+        }
+    }
+
+    pub fn handle_event(
+        &mut self,
+        event: &Event,
+        scene: &mut Box<dyn Scene>,
+        canvas: &WindowCanvas,
+    ) -> bool {
+        if !self.is_enabled {
+            return false;
+        }
+
+        match event {
+            Event::KeyDown {
+                scancode: Some(Scancode::Grave),
+                repeat: false,
+                ..
+            } => {
+                self.is_open = !self.is_open;
+                self.history_index = None;
+
+                true
+            }
+            Event::KeyDown {
+                keycode: Some(keycode),
+                ..
+            } if self.is_open => {
+                self.handle_key(*keycode, scene, canvas);
+
+                true
+            }
+            Event::TextInput { text, .. } if self.is_open => {
+                self.input.extend(
+                    text.chars()
+                        .filter(|character| !matches!(character, '`' | '~')),
+                );
+                self.caret_timer = 0.0;
+
+                true
+            }
+            Event::KeyDown { .. }
+            | Event::KeyUp { .. }
+            | Event::TextInput { .. }
+            | Event::MouseButtonDown { .. }
+            | Event::MouseButtonUp { .. }
+            | Event::MouseWheel { .. } => self.is_open,
+            _ => false,
+        }
+    }
+
+    pub fn update(&mut self, delta_time: f32) {
+        let target_openness = if self.is_open { 1.0 } else { 0.0 };
+        let step = CONSOLE_SLIDE_SPEED * delta_time;
+
+        self.openness = if self.openness < target_openness {
+            f32::min(self.openness + step, target_openness)
+        } else {
+            f32::max(self.openness - step, target_openness)
+        };
+        self.caret_timer = (self.caret_timer + delta_time) % (CARET_BLINK_INTERVAL * 2.0);
+    }
+
+    pub fn draw(&self, renderer: &mut Renderer, font: &Font, font_index: usize) {
+        let viewport = renderer.viewport();
+        let line_height = (font.height() as f32 * CONSOLE_TEXT_SCALE) as i32;
+        let full_height = (viewport.height() as f32 * CONSOLE_HEIGHT_FRACTION) as i32;
+        let top = ((self.openness - 1.0) * full_height as f32) as i32;
+        let input_y = top + full_height - CONSOLE_OFFSET - line_height;
+
+        renderer.fill_rect(
+            Layer::Interface,
+            Rect::new(0, top, viewport.width(), full_height as u32),
+            CONSOLE_BACKGROUND_COLOUR,
+        );
+
+        let caret = if self.is_open && self.caret_timer < CARET_BLINK_INTERVAL {
+            "_"
+        } else {
+            ""
+        };
+
+        renderer.draw_text(
+            Layer::Interface,
+            TextCommand::new(
+                format!("> {}{}", self.input, caret),
+                font_index,
+                Colour::WHITE,
+                Point::new(CONSOLE_OFFSET, input_y),
+            )
+            .with_anchor(Anchor::TopLeft)
+            .with_scale(CONSOLE_TEXT_SCALE),
+        );
+
+        let visible_line_count =
+            (i32::max((full_height - CONSOLE_OFFSET * 2) / line_height, 1) - 1) as usize;
+
+        for (line_index, (line, colour)) in self
+            .output
+            .iter()
+            .rev()
+            .skip(self.scroll_offset)
+            .take(visible_line_count)
+            .enumerate()
+        {
+            if line.is_empty() {
+                continue;
+            }
+
+            renderer.draw_text(
+                Layer::Interface,
+                TextCommand::new(
+                    line.clone(),
+                    font_index,
+                    *colour,
+                    Point::new(
+                        CONSOLE_OFFSET,
+                        input_y - line_height * (line_index as i32 + 1),
+                    ),
+                )
+                .with_anchor(Anchor::TopLeft)
+                .with_scale(CONSOLE_TEXT_SCALE),
+            );
+        }
+    }
+
+    fn handle_key(&mut self, keycode: Keycode, scene: &mut Box<dyn Scene>, canvas: &WindowCanvas) {
+        match keycode {
+            Keycode::Return | Keycode::KpEnter => {
+                let line = std::mem::take(&mut self.input);
+                let trimmed_line = line.trim();
+
+                if !trimmed_line.is_empty()
+                    && self.history.last().map(String::as_str) != Some(trimmed_line)
+                {
+                    self.history.push(trimmed_line.to_string());
+
+                    if self.history.len() > MAX_HISTORY_LENGTH {
+                        self.history.remove(0);
+                    }
+                }
+
+                self.history_index = None;
+                self.scroll_offset = 0;
+                self.print(format!("> {}", line), CONSOLE_ECHO_COLOUR);
+                self.execute(&line, scene, canvas);
+            }
+            Keycode::Backspace => {
+                self.input.pop();
+            }
+            Keycode::Escape => self.is_open = false,
+            Keycode::Tab => self.complete(scene.as_ref()),
+            Keycode::Up => self.recall_history(true),
+            Keycode::Down => self.recall_history(false),
+            Keycode::PageUp => {
+                self.scroll_offset = usize::min(self.scroll_offset + 1, self.output.len());
+            }
+            Keycode::PageDown => {
+                self.scroll_offset = self.scroll_offset.saturating_sub(1);
+            }
+            _ => (),
+        }
+
+        self.caret_timer = 0.0;
+    }
+
+    fn recall_history(&mut self, is_older: bool) {
+        if self.history.is_empty() {
+            return;
+        }
+
+        self.history_index = match (self.history_index, is_older) {
+            (None, true) => Some(self.history.len() - 1),
+            (None, false) => None,
+            (Some(index), true) => Some(index.saturating_sub(1)),
+            (Some(index), false) if index + 1 < self.history.len() => Some(index + 1),
+            (Some(_), false) => None,
+        };
+
+        self.input = self
+            .history_index
+            .map_or_else(String::new, |index| self.history[index].clone());
+    }
+
+    fn complete(&mut self, scene: &dyn Scene) {
+        if self.input.contains(char::is_whitespace) {
+            return;
+        }
+
+        let mut candidates: Vec<String> = BUILT_IN_COMMANDS
+            .iter()
+            .chain(scene.console_commands().iter())
+            .map(|command| command.name.to_string())
+            .chain(self.variables.keys().cloned())
+            .filter(|name| name.starts_with(&self.input))
+            .collect();
+        candidates.sort();
+        candidates.dedup();
+
+        match candidates.as_slice() {
+            [] => (),
+            [candidate] => self.input = format!("{} ", candidate),
+            [first, rest @ ..] => {
+                let common_length = rest.iter().fold(first.len(), |length, candidate| {
+                    first
+                        .bytes()
+                        .zip(candidate.bytes())
+                        .take(length)
+                        .take_while(|(left, right)| left == right)
+                        .count()
+                });
+
+                self.input = first[..common_length].to_string();
+                self.print(candidates.join("  "), Colour::GREY);
+            }
+        }
+    }
+
+    fn execute(&mut self, line: &str, scene: &mut Box<dyn Scene>, canvas: &WindowCanvas) {
+        for command_line in line.split(';') {
+            let tokens: Vec<&str> = command_line.split_whitespace().collect();
+
+            let (name, arguments) = match tokens.split_first() {
+                Some((name, arguments)) => (*name, arguments),
+                None => continue,
+            };
+
+            match self.run_command(name, arguments, scene, canvas) {
+                Ok(message) if message.is_empty() => (),
+                Ok(message) => self.print(message, Colour::WHITE),
+                Err(message) => self.print(message, CONSOLE_ERROR_COLOUR),
+            }
+        }
+    }
+
+    fn run_command(
+        &mut self,
+        name: &str,
+        arguments: &[&str],
+        scene: &mut Box<dyn Scene>,
+        canvas: &WindowCanvas,
+    ) -> Result<String, String> {
+        match (name, arguments) {
+            ("help", _) => {
+                let mut lines: Vec<&str> = BUILT_IN_COMMANDS
+                    .iter()
+                    .map(|command| command.usage)
+                    .collect();
+                let scene_commands = scene.console_commands();
+                lines.extend(scene_commands.iter().map(|command| command.usage));
+                lines.push("<variable> [value] - Show or change a variable.");
+
+                Ok(lines.join("\n"))
+            }
+            ("clear", _) => {
+                self.output.clear();
+                self.scroll_offset = 0;
+
+                Ok(String::new())
+            }
+            ("cvars", _) => Ok(self
+                .variables
+                .iter()
+                .map(|(name, variable)| {
+                    format!(
+                        "{} = {} (default {})",
+                        name, variable.value, variable.default
+                    )
+                })
+                .collect::<Vec<String>>()
+                .join("\n")),
+            ("set", [variable_name, value]) => {
+                let value = parse_value(value)?;
+
+                self.variables
+                    .entry(variable_name.to_string())
+                    .or_insert(VariableValue {
+                        value,
+                        default: value,
+                    });
+                self.set_variable(variable_name, value, scene)
+            }
+            ("set", _) => Err("Usage: set <variable> <value>".to_string()),
+            ("exec", [_]) if self.exec_depth >= MAX_EXEC_DEPTH => {
+                Err("Scripts are nested too deeply.".to_string())
+            }
+            ("exec", [file_name]) => {
+                let script = fs::read_to_string(vfs::resolve(file_name))
+                    .map_err(|error| format!("Could not read {}: {}", file_name, error))?;
+
+                self.exec_depth += 1;
+
+                for script_line in script.lines().map(str::trim) {
+                    if !script_line.is_empty() && !script_line.starts_with('#') {
+                        self.execute(script_line, scene, canvas);
+                    }
+                }
+
+                self.exec_depth -= 1;
+
+                Ok(String::new())
+            }
+            ("exec", _) => Err("Usage: exec <file>".to_string()),
+            ("screenshot", _) => {
+                self.capture_requests.push(CaptureRequest::Screenshot);
+
+                Ok("Saving a screenshot of the next frame.".to_string())
+            }
+            ("record", _) => {
+                self.capture_requests.push(CaptureRequest::ToggleRecording);
+
+                Ok(String::new())
+            }
+            (_, []) if self.variables.contains_key(name) => {
+                let variable = self.variables[name];
+
+                Ok(format!(
+                    "{} = {} (default {})",
+                    name, variable.value, variable.default
+                ))
+            }
+            (_, [value]) if self.variables.contains_key(name) => {
+                self.set_variable(name, parse_value(value)?, scene)
+            }
+            _ if scene
+                .console_commands()
+                .iter()
+                .any(|command| command.name == name) =>
+            {
+                scene.run_console_command(name, arguments, canvas)
+            }
+            _ => Err(format!("Unknown command \"{}\".", name)),
+        }
+    }
+
+    fn set_variable(
+        &mut self,
+        name: &str,
+        value: f32,
+        scene: &mut Box<dyn Scene>,
+    ) -> Result<String, String> {
+        if let Some(variable) = self.variables.get_mut(name) {
+            variable.value = value;
+        }
+
+        if let Some(scene_variable) = self
+            .scene_variables
+            .iter()
+            .find(|variable| **variable == name)
+        {
+            scene.on_console_variable_changed(scene_variable, value);
+        }
+
+        Ok(format!("{} = {}", name, value))
+    }
+
+    fn print(&mut self, message: String, colour: Colour) {
+        for line in message.lines() {
+            self.output.push_back((line.to_string(), colour));
+        }
+
+        while self.output.len() > MAX_OUTPUT_LINES {
+            self.output.pop_front();
+        }
+    }
+}
+
+fn parse_value(value: &str) -> Result<f32, String> {
+    value
+        .parse()
+        .map_err(|_| format!("\"{}\" is not a number.", value))
+}
+
+pub fn is_console_enabled() -> bool {
+    cfg!(debug_assertions) || env::args().any(|argument| argument == CONSOLE_FLAG)
+}
@@ -0,0 +1,121 @@
+use std::backtrace::Backtrace;
+use std::collections::{BTreeMap, VecDeque};
+use std::fmt::Write as _;
+use std::fs;
+use std::io;
+use std::panic::{self, PanicHookInfo};
+use std::path::PathBuf;
+use std::sync::{Mutex, MutexGuard};
+use std::thread;
+
+use super::logging::{self, log_error};
+
+const CRASHES_DIRECTORY: &str = "crashes";
+const RECENT_INPUT_COUNT: usize = 32;
+
+struct CrashContext {
+    values: BTreeMap<&'static str, String>,
+    recent_input: VecDeque<String>,
+}
+
+static CRASH_CONTEXT: Mutex<CrashContext> = Mutex::new(CrashContext {
+    values: BTreeMap::new(),
+    recent_input: VecDeque::new(),
+});
+
+fn crash_context() -> MutexGuard<'static, CrashContext> {
+    CRASH_CONTEXT
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+pub fn set_context(key: &'static str, value: impl ToString) {
+    crash_context().values.insert(key, value.to_string());
+}
+
+pub fn record_input(description: impl ToString) {
+    let mut context = crash_context();
+
+    if context.recent_input.len() == RECENT_INPUT_COUNT {
+        context.recent_input.pop_front();
+    }
+
+    context.recent_input.push_back(format!(
+        "[{:>10.3}] {}",
+        logging::elapsed_time(),
+        description.to_string()
+    ));
+}
+
+pub fn install_panic_hook() {
+    panic::set_hook(Box::new(|panic_info| {
+        log_error!(
+            "Thread '{}' {}",
+            thread::current().name().unwrap_or("unnamed"),
+            panic_info
+        );
+
+        match write_crash_report(panic_info) {
+            Ok(crash_report_filepath) => log_error!(
+                "A crash report was written to {}",
+                crash_report_filepath.display()
+            ),
+            Err(error) => log_error!("Could not write a crash report: {}", error),
+        }
+    }));
+}
+
+fn write_crash_report(panic_info: &PanicHookInfo) -> io::Result<PathBuf> {
+    let crashes_directory = super::user_data_directory()
+        .unwrap_or_default()
+        .join(CRASHES_DIRECTORY);
+    fs::create_dir_all(&crashes_directory)?;
+
+    let crash_report_filepath =
+        crashes_directory.join(format!("crash-{}.txt", logging::unix_time()));
+    fs::write(&crash_report_filepath, crash_report(panic_info))?;
+
+    Ok(crash_report_filepath)
+}
+
+fn crash_report(panic_info: &PanicHookInfo) -> String {
+    let mut report = String::new();
+    let context = crash_context();
+
+    let _ = writeln!(report, "Crash report");
+    let _ = writeln!(report, "Version: {}", env!("CARGO_PKG_VERSION"));
+    let _ = writeln!(report, "Time: {} (Unix time)", logging::unix_time());
+    let _ = writeln!(
+        report,
+        "Thread: {}",
+        thread::current().name().unwrap_or("unnamed")
+    );
+    let _ = writeln!(report, "\n{}", panic_info);
+
+    let _ = writeln!(report, "\nContext:");
+    for (key, value) in &context.values {
+        let _ = writeln!(report, "    {}: {}", key, value);
+    }
+
+    let _ = writeln!(report, "\nRecent input:");
+    for input in &context.recent_input {
+        let _ = writeln!(report, "    {}", input);
+    }
+
+    let _ = writeln!(report, "\nRecent log:");
+    for line in logging::recent_lines() {
+        let _ = writeln!(report, "    {}", line);
+    }
+
+    let _ = writeln!(report, "\nConfig ({}):", super::CONFIG_FILE_NAME);
+    match fs::read_to_string(super::vfs::resolve(super::CONFIG_FILE_NAME)) {
+        Ok(config) => report.push_str(&config),
+        Err(error) => {
+            let _ = writeln!(report, "    Could not read config: {}", error);
+        }
+    }
+
+    let _ = writeln!(report, "\n\nBacktrace:\n{}", Backtrace::force_capture());
+
+    report
+}
@@ -0,0 +1,239 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use sdl2::{
+    event::Event,
+    keyboard::Keycode,
+    pixels::Color as Colour,
+    rect::{Point, Rect},
+    ttf::Font,
+};
+
+use super::renderer::{Anchor, Layer, RenderStats, Renderer, TextCommand};
+
+const FRAME_HISTORY_LENGTH: usize = 120;
+const FPS_UPDATE_INTERVAL: f32 = 0.5;
+
+const TARGET_FRAME_TIME: f32 = 1000.0 / 60.0;
+const SLOW_FRAME_TIME: f32 = 1000.0 / 30.0;
+
+const OVERLAY_OFFSET: i32 = 10;
+const OVERLAY_TEXT_SCALE: f32 = 0.15;
+const OVERLAY_TEXT_COLOUR: Colour = Colour::RGB(255, 120, 255);
+const OVERLAY_BACKGROUND_COLOUR: Colour = Colour::RGBA(0, 0, 0, 170);
+
+const GRAPH_BAR_WIDTH: u32 = 2;
+const GRAPH_HEIGHT: u32 = 60;
+const GRAPH_MAX_FRAME_TIME: f32 = SLOW_FRAME_TIME * 1.5;
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct FrameTimings {
+    pub update: Duration,
+    pub draw: Duration,
+    pub present: Duration,
+}
+
+pub struct DebugOverlay {
+    is_visible: bool,
+    are_hitboxes_visible: bool,
+
+    last_frame: Instant,
+    frame_times: VecDeque<f32>,
+    timings: FrameTimings,
+    render_stats: RenderStats,
+
+    fps: f32,
+    fps_timer: f32,
+    fps_frame_count: u32,
+}
+
+impl DebugOverlay {
+    pub fn new() -> DebugOverlay {
+        DebugOverlay {
+            is_visible: false,
+            are_hitboxes_visible: false,
+            last_frame: Instant::now(),
+            frame_times: VecDeque::with_capacity(FRAME_HISTORY_LENGTH),
+            timings: FrameTimings::default(),
+            render_stats: RenderStats::default(),
+            fps: 0.0,
+            fps_timer: 0.0,
+            fps_frame_count: 0,
+        }
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.is_visible
+    }
+
+    pub fn are_hitboxes_visible(&self) -> bool {
+        self.are_hitboxes_visible
+    }
+
+    pub fn handle_event(&mut self, event: &Event) -> bool {
+        match event {
+            Event::KeyDown {
+                keycode: Some(Keycode::F3),
+                repeat: false,
+                ..
+            } => {
+                self.is_visible = !self.is_visible;
+
+                true
+            }
+            Event::KeyDown {
+                keycode: Some(Keycode::F4),
+                repeat: false,
+                ..
+            } => {
+                self.are_hitboxes_visible = !self.are_hitboxes_visible;
+
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub fn end_frame(&mut self, timings: FrameTimings, render_stats: RenderStats) {
+        let frame_time = self.last_frame.elapsed().as_secs_f32();
+        self.last_frame = Instant::now();
+
+        if self.frame_times.len() == FRAME_HISTORY_LENGTH {
+            self.frame_times.pop_front();
+        }
+
+        self.frame_times.push_back(frame_time * 1000.0);
+        self.timings = timings;
+        self.render_stats = render_stats;
+
+        self.fps_timer += frame_time;
+        self.fps_frame_count += 1;
+
+        if self.fps_timer >= FPS_UPDATE_INTERVAL {
+            self.fps = self.fps_frame_count as f32 / self.fps_timer;
+            self.fps_timer = 0.0;
+            self.fps_frame_count = 0;
+        }
+    }
+
+    pub fn draw(
+        &self,
+        renderer: &mut Renderer,
+        font: &Font,
+        font_index: usize,
+        scene_lines: Vec<String>,
+    ) {
+        let milliseconds = |duration: Duration| duration.as_secs_f32() * 1000.0;
+        let last_frame_time = self.frame_times.back().copied().unwrap_or(0.0);
+        let max_frame_time = self.frame_times.iter().copied().fold(0.0, f32::max);
+
+        let mut lines = vec![
+            format!(
+                "FPS: {:.0}  Frame: {:.1} ms (max {:.1} ms)",
+                self.fps, last_frame_time, max_frame_time
+            ),
+            format!(
+                "Update: {:.2} ms  Draw: {:.2} ms  Present: {:.2} ms",
+                milliseconds(self.timings.update),
+                milliseconds(self.timings.draw),
+                milliseconds(self.timings.present)
+            ),
+            format!(
+                "Draw commands: {}  Batches: {}  Draw calls: {}",
+                self.render_stats.commands, self.render_stats.batches, self.render_stats.draw_calls
+            ),
+            format!(
+                "Hitboxes: {} (F4)",
+                if self.are_hitboxes_visible {
+                    "on"
+                } else {
+                    "off"
+                }
+            ),
+        ];
+        lines.extend(scene_lines);
+
+        let viewport = renderer.viewport();
+        let line_height = (font.height() as f32 * OVERLAY_TEXT_SCALE) as i32;
+        let panel_width = u32::max(
+            GRAPH_BAR_WIDTH * FRAME_HISTORY_LENGTH as u32,
+            viewport.width() / 2,
+        ) + OVERLAY_OFFSET as u32 * 2;
+        let panel_height =
+            (line_height * lines.len() as i32 + OVERLAY_OFFSET * 3) as u32 + GRAPH_HEIGHT;
+        let panel_top = viewport.height() as i32 - OVERLAY_OFFSET - panel_height as i32;
+
+        renderer.fill_rect(
+            Layer::Interface,
+            Rect::new(OVERLAY_OFFSET, panel_top, panel_width, panel_height),
+            OVERLAY_BACKGROUND_COLOUR,
+        );
+
+        self.draw_frame_graph(
+            renderer,
+            Point::new(
+                OVERLAY_OFFSET * 2,
+                panel_top + OVERLAY_OFFSET + GRAPH_HEIGHT as i32,
+            ),
+        );
+
+        let mut line_y = viewport.height() as i32 - OVERLAY_OFFSET * 2;
+
+        for line in lines.into_iter().rev() {
+            renderer.draw_text(
+                Layer::Interface,
+                TextCommand::new(
+                    line,
+                    font_index,
+                    OVERLAY_TEXT_COLOUR,
+                    Point::new(OVERLAY_OFFSET * 2, line_y),
+                )
+                .with_anchor(Anchor::BottomLeft)
+                .with_scale(OVERLAY_TEXT_SCALE),
+            );
+
+            line_y -= line_height;
+        }
+    }
+
+    fn draw_frame_graph(&self, renderer: &mut Renderer, bottom_left: Point) {
+        let bar_height = |frame_time: f32| {
+            (f32::min(frame_time / GRAPH_MAX_FRAME_TIME, 1.0) * GRAPH_HEIGHT as f32) as u32
+        };
+
+        for (frame_index, frame_time) in self.frame_times.iter().enumerate() {
+            let height = u32::max(bar_height(*frame_time), 1);
+            let colour = if *frame_time <= TARGET_FRAME_TIME {
+                Colour::GREEN
+            } else if *frame_time <= SLOW_FRAME_TIME {
+                Colour::YELLOW
+            } else {
+                Colour::RED
+            };
+
+            renderer.fill_rect(
+                Layer::Interface,
+                Rect::new(
+                    bottom_left.x() + (frame_index as u32 * GRAPH_BAR_WIDTH) as i32,
+                    bottom_left.y() - height as i32,
+                    GRAPH_BAR_WIDTH,
+                    height,
+                ),
+                colour,
+            );
+        }
+
+        let target_y = bottom_left.y() - bar_height(TARGET_FRAME_TIME) as i32;
+
+        renderer.fill_rect(
+            Layer::Interface,
+            Rect::new(
+                bottom_left.x(),
+                target_y,
+                GRAPH_BAR_WIDTH * FRAME_HISTORY_LENGTH as u32,
+                1,
+            ),
+            Colour::WHITE,
+        );
+    }
+}
@@ -0,0 +1,336 @@
+pub mod components;
+
+use std::any::{Any, TypeId};
+use std::cell::{Ref, RefCell, RefMut};
+use std::collections::HashMap;
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Entity {
+    index: u32,
+    generation: u32,
+}
+
+pub struct Storage<T> {
+    components: Vec<Option<(Entity, T)>>,
+}
+
+impl<T> Storage<T> {
+    fn new() -> Storage<T> {
+        Storage { components: vec![] }
+    }
+
+    fn insert(&mut self, entity: Entity, component: T) {
+        let index = entity.index as usize;
+
+        if index >= self.components.len() {
+            self.components.resize_with(index + 1, || None);
+        }
+
+        self.components[index] = Some((entity, component));
+    }
+
+    fn remove(&mut self, entity: Entity) -> Option<T> {
+        match self.components.get_mut(entity.index as usize) {
+            Some(slot) if slot.as_ref().map(|(owner, _)| *owner) == Some(entity) => {
+                slot.take().map(|(_, component)| component)
+            }
+            _ => None,
+        }
+    }
+
+    pub fn get(&self, entity: Entity) -> Option<&T> {
+        match self.components.get(entity.index as usize) {
+            Some(Some((owner, component))) if *owner == entity => Some(component),
+            _ => None,
+        }
+    }
+
+    pub fn get_mut(&mut self, entity: Entity) -> Option<&mut T> {
+        match self.components.get_mut(entity.index as usize) {
+            Some(Some((owner, component))) if *owner == entity => Some(component),
+            _ => None,
+        }
+    }
+
+    pub fn contains(&self, entity: Entity) -> bool {
+        self.get(entity).is_some()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Entity, &T)> {
+        self.components.iter().filter_map(|slot| {
+            slot.as_ref()
+                .map(|(entity, component)| (*entity, component))
+        })
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Entity, &mut T)> {
+        self.components.iter_mut().filter_map(|slot| {
+            slot.as_mut()
+                .map(|(entity, component)| (*entity, component))
+        })
+    }
+}
+
+trait AnyStorage {
+    fn remove_entity(&self, entity: Entity);
+    fn clear(&self);
+    fn as_any(&self) -> &dyn Any;
+}
+
+impl<T: 'static> AnyStorage for RefCell<Storage<T>> {
+    fn remove_entity(&self, entity: Entity) {
+        self.borrow_mut().remove(entity);
+    }
+
+    fn clear(&self) {
+        self.borrow_mut().components.clear();
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+#[derive(Default)]
+pub struct World {
+    generations: Vec<u32>,
+    is_alive: Vec<bool>,
+    free_indices: Vec<u32>,
+
+    storages: HashMap<TypeId, Box<dyn AnyStorage>>,
+    resources: HashMap<TypeId, Box<dyn Any>>,
+}
+
+impl World {
+    pub fn new() -> World {
+        World::default()
+    }
+
+    pub fn spawn(&mut self) -> Entity {
+        match self.free_indices.pop() {
+            Some(index) => {
+                self.is_alive[index as usize] = true;
+
+                Entity {
+                    index,
+                    generation: self.generations[index as usize],
+                }
+            }
+            None => {
+                self.generations.push(0);
+                self.is_alive.push(true);
+
+                Entity {
+                    index: self.generations.len() as u32 - 1,
+                    generation: 0,
+                }
+            }
+        }
+    }
+
+    pub fn despawn(&mut self, entity: Entity) {
+        if !self.is_alive(entity) {
+            return;
+        }
+
+        for storage in self.storages.values() {
+            storage.remove_entity(entity);
+        }
+
+        let index = entity.index as usize;
+        self.is_alive[index] = false;
+        self.generations[index] = self.generations[index].wrapping_add(1);
+        self.free_indices.push(entity.index);
+    }
+
+    pub fn is_alive(&self, entity: Entity) -> bool {
+        let index = entity.index as usize;
+
+        index < self.is_alive.len()
+            && self.is_alive[index]
+            && self.generations[index] == entity.generation
+    }
+
+    pub fn clear(&mut self) {
+        for storage in self.storages.values() {
+            storage.clear();
+        }
+
+        self.free_indices.clear();
+
+        for (index, is_alive) in self.is_alive.iter_mut().enumerate() {
+            if *is_alive {
+                *is_alive = false;
+                self.generations[index] = self.generations[index].wrapping_add(1);
+            }
+
+            self.free_indices.push(index as u32);
+        }
+
+        self.free_indices.reverse();
+    }
+
+    pub fn register<T: 'static>(&mut self) {
+        self.storages
+            .entry(TypeId::of::<T>())
+            .or_insert_with(|| Box::new(RefCell::new(Storage::<T>::new())));
+    }
+
+    pub fn insert<T: 'static>(&mut self, entity: Entity, component: T) {
+        if !self.is_alive(entity) {
+            return;
+        }
+
+        self.register::<T>();
+        self.storage::<T>().borrow_mut().insert(entity, component);
+    }
+
+    pub fn read<T: 'static>(&self) -> Ref<'_, Storage<T>> {
+        self.storage::<T>().borrow()
+    }
+
+    pub fn write<T: 'static>(&self) -> RefMut<'_, Storage<T>> {
+        self.storage::<T>().borrow_mut()
+    }
+
+    pub fn insert_resource<T: 'static>(&mut self, resource: T) {
+        self.resources
+            .insert(TypeId::of::<T>(), Box::new(RefCell::new(resource)));
+    }
+
+    pub fn resource<T: 'static>(&self) -> Ref<'_, T> {
+        self.resource_cell::<T>().borrow()
+    }
+
+    pub fn resource_mut<T: 'static>(&self) -> RefMut<'_, T> {
+        self.resource_cell::<T>().borrow_mut()
+    }
+
+    fn storage<T: 'static>(&self) -> &RefCell<Storage<T>> {
+        self.storages
+            .get(&TypeId::of::<T>())
+            .and_then(|storage| storage.as_any().downcast_ref())
+            .unwrap_or_else(|| {
+                panic!(
+                    "Component {} has not been registered.",
+                    std::any::type_name::<T>()
+                )
+            })
+    }
+
+    fn resource_cell<T: 'static>(&self) -> &RefCell<T> {
+        self.resources
+            .get(&TypeId::of::<T>())
+            .and_then(|resource| resource.downcast_ref())
+            .unwrap_or_else(|| {
+                panic!(
+                    "Resource {} has not been inserted.",
+                    std::any::type_name::<T>()
+                )
+            })
+    }
+}
+
+pub type System = fn(&mut World, f32);
+
+#[derive(Default)]
+pub struct Schedule {
+    systems: Vec<System>,
+}
+
+impl Schedule {
+    pub fn new() -> Schedule {
+        Schedule::default()
+    }
+
+    pub fn with_system(mut self, system: System) -> Schedule {
+        self.systems.push(system);
+
+        self
+    }
+
+    pub fn run(&self, world: &mut World, delta_time: f32) {
+        for system in &self.systems {
+            system(world, delta_time);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    struct Marker(u32);
+
+    #[test]
+    fn spawn_and_despawn() {
+        let mut world = World::new();
+        let first = world.spawn();
+        let second = world.spawn();
+
+        assert_ne!(first, second);
+        assert!(world.is_alive(first) && world.is_alive(second));
+
+        world.insert(first, Marker(1));
+        world.despawn(first);
+
+        assert!(!world.is_alive(first));
+        assert!(world.is_alive(second));
+        assert!(!world.read::<Marker>().contains(first));
+
+        world.insert(first, Marker(2));
+        assert!(!world.read::<Marker>().contains(first));
+    }
+
+    #[test]
+    fn despawned_index_is_reused_with_new_generation() {
+        let mut world = World::new();
+        let entity = world.spawn();
+
+        world.despawn(entity);
+        let reused = world.spawn();
+
+        assert_eq!(reused.index, entity.index);
+        assert_eq!(reused.generation, entity.generation + 1);
+        assert!(world.is_alive(reused));
+        assert!(!world.is_alive(entity));
+
+        world.despawn(entity);
+        assert!(world.is_alive(reused));
+    }
+
+    #[test]
+    fn stale_entity_does_not_read_component() {
+        let mut world = World::new();
+        let entity = world.spawn();
+        world.insert(entity, Marker(1));
+
+        world.despawn(entity);
+        let reused = world.spawn();
+        world.insert(reused, Marker(2));
+
+        let markers = world.read::<Marker>();
+        assert_eq!(markers.get(entity), None);
+        assert_eq!(markers.get(reused), Some(&Marker(2)));
+    }
+
+    #[test]
+    fn clear_removes_entities_and_components() {
+        let mut world = World::new();
+        let entities = (0..3).map(|_| world.spawn()).collect::<Vec<_>>();
+
+        for (index, entity) in entities.iter().enumerate() {
+            world.insert(*entity, Marker(index as u32));
+        }
+
+        world.clear();
+
+        assert!(entities.iter().all(|entity| !world.is_alive(*entity)));
+        assert_eq!(world.read::<Marker>().iter().count(), 0);
+
+        let reused = world.spawn();
+        assert_eq!(reused.index, entities[0].index);
+        assert_eq!(reused.generation, 1);
+    }
+}
@@ -0,0 +1,61 @@
+use sdl2::rect::{Point, Rect};
+
+use crate::game::animation::Animator;
+
+#[derive(Clone, Copy, Debug)]
+pub struct Transform {
+    pub x: f32,
+    pub y: f32,
+    pub previous_position: (f32, f32),
+}
+
+impl Transform {
+    pub fn new(x: f32, y: f32) -> Transform {
+        Transform {
+            x,
+            y,
+            previous_position: (x, y),
+        }
+    }
+
+    pub fn position(&self) -> (f32, f32) {
+        (self.x, self.y)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Velocity {
+    pub x: f32,
+    pub y: f32,
+}
+
+#[derive(Clone, Debug)]
+pub struct Sprite {
+    pub texture_index: usize,
+    pub size: (u32, u32),
+    pub animator: Animator,
+}
+
+impl Sprite {
+    pub fn new(texture_index: usize, size: (u32, u32)) -> Sprite {
+        Sprite {
+            texture_index,
+            size,
+            animator: Animator::new("idle"),
+        }
+    }
+
+    pub fn rect_at(&self, position: (f32, f32)) -> Rect {
+        Rect::from_center(
+            Point::new(position.0 as i32, position.1 as i32),
+            self.size.0,
+            self.size.1,
+        )
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Lifetime {
+    pub age: f32,
+    pub is_expired: bool,
+}
@@ -0,0 +1,52 @@
+use std::error::Error;
+use std::fmt;
+
+#[derive(Debug)]
+pub enum EngineError {
+    Config { context: String, message: String },
+    Sdl { context: String, message: String },
+    Asset { path: String, message: String },
+}
+
+impl EngineError {
+    pub fn config(context: &str, error: impl fmt::Display) -> EngineError {
+        EngineError::Config {
+            context: context.to_string(),
+            message: error.to_string(),
+        }
+    }
+
+    pub fn sdl(context: &str, error: impl fmt::Display) -> EngineError {
+        EngineError::Sdl {
+            context: context.to_string(),
+            message: error.to_string(),
+        }
+    }
+
+    pub fn asset(path: &str, error: impl fmt::Display) -> EngineError {
+        EngineError::Asset {
+            path: path.to_string(),
+            message: error.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for EngineError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            EngineError::Config { context, message } => {
+                write!(
+                    formatter,
+                    "Invalid configuration ({}): {}",
+                    context, message
+                )
+            }
+            EngineError::Sdl { context, message } => write!(formatter, "{}: {}", context, message),
+            EngineError::Asset { path, message } => {
+                write!(formatter, "Could not load {}: {}", path, message)
+            }
+        }
+    }
+}
+
+impl Error for EngineError {}
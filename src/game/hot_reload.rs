@@ -0,0 +1,228 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use sdl2::{
+    pixels::Color as Colour,
+    rect::{Point, Rect},
+    ttf::Font,
+};
+
+use super::logging::log_warn;
+use super::renderer::{Anchor, Layer, Renderer, TextCommand};
+use super::vfs;
+
+const DEFAULT_POLL_INTERVAL: f32 = 0.5;
+const ERROR_DISPLAY_TIME: f32 = 15.0;
+
+const OVERLAY_OFFSET: i32 = 10;
+const OVERLAY_TEXT_SCALE: f32 = 0.15;
+const OVERLAY_BACKGROUND_COLOUR: Colour = Colour::RGBA(90, 0, 0, 200);
+
+#[derive(Clone, Debug)]
+pub struct HotReloadSettings {
+    pub is_enabled: bool,
+    pub poll_interval: f32,
+    pub directories: Vec<String>,
+}
+
+struct FileWatcher {
+    directories: Vec<PathBuf>,
+    modified_times: HashMap<PathBuf, SystemTime>,
+
+    poll_interval: f32,
+    poll_timer: f32,
+}
+
+impl FileWatcher {
+    fn new(directories: &[String], poll_interval: f32) -> FileWatcher {
+        let mut file_watcher = FileWatcher {
+            directories: directories.iter().map(PathBuf::from).collect(),
+            modified_times: HashMap::new(),
+            poll_interval,
+            poll_timer: poll_interval,
+        };
+        file_watcher.scan();
+
+        file_watcher
+    }
+
+    fn poll(&mut self, delta_time: f32) -> Vec<PathBuf> {
+        self.poll_timer -= delta_time;
+
+        if self.poll_timer > 0.0 {
+            return vec![];
+        }
+
+        self.poll_timer = self.poll_interval;
+        self.scan()
+    }
+
+    fn scan(&mut self) -> Vec<PathBuf> {
+        let mut modified_times = HashMap::new();
+
+        for directory in &self.directories {
+            scan_directory(&vfs::resolve(directory), &mut modified_times);
+        }
+
+        let mut changed_paths: Vec<PathBuf> = modified_times
+            .iter()
+            .filter(|(path, modified_time)| self.modified_times.get(*path) != Some(modified_time))
+            .map(|(path, _)| {
+                path.strip_prefix(vfs::base_directory())
+                    .map_or_else(|_| path.clone(), Path::to_path_buf)
+            })
+            .collect();
+        changed_paths.sort();
+
+        self.modified_times = modified_times;
+
+        changed_paths
+    }
+}
+
+fn scan_directory(directory: &Path, modified_times: &mut HashMap<PathBuf, SystemTime>) {
+    let entries = match fs::read_dir(directory) {
+        Ok(entries) => entries,
+        Err(_) => return,
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let metadata = match entry.metadata() {
+            Ok(metadata) => metadata,
+            Err(_) => continue,
+        };
+
+        if metadata.is_dir() {
+            scan_directory(&path, modified_times);
+        } else if let Ok(modified_time) = metadata.modified() {
+            modified_times.insert(path, modified_time);
+        }
+    }
+}
+
+struct ReloadError {
+    path: PathBuf,
+    message: String,
+    timer: f32,
+}
+
+pub struct HotReloader {
+    file_watcher: Option<FileWatcher>,
+    errors: Vec<ReloadError>,
+}
+
+impl HotReloader {
+    pub fn new(settings: &HotReloadSettings) -> HotReloader {
+        HotReloader {
+            file_watcher: if settings.is_enabled {
+                Some(FileWatcher::new(
+                    &settings.directories,
+                    settings.poll_interval,
+                ))
+            } else {
+                None
+            },
+            errors: vec![],
+        }
+    }
+
+    pub fn poll(&mut self, delta_time: f32) -> Vec<PathBuf> {
+        for error in &mut self.errors {
+            error.timer -= delta_time;
+        }
+
+        self.errors.retain(|error| error.timer > 0.0);
+
+        match &mut self.file_watcher {
+            Some(file_watcher) => file_watcher.poll(delta_time),
+            None => vec![],
+        }
+    }
+
+    pub fn report(&mut self, path: &Path, result: Result<(), Box<dyn Error>>) {
+        self.errors.retain(|error| error.path != path);
+
+        if let Err(error) = result {
+            log_warn!("Could not reload {}: {}", path.display(), error);
+
+            self.errors.push(ReloadError {
+                path: path.to_path_buf(),
+                message: error.to_string(),
+                timer: ERROR_DISPLAY_TIME,
+            });
+        }
+    }
+
+    pub fn has_errors(&self) -> bool {
+        !self.errors.is_empty()
+    }
+
+    pub fn draw(&self, renderer: &mut Renderer, font: &Font, font_index: usize) {
+        let line_height = (font.height() as f32 * OVERLAY_TEXT_SCALE) as i32;
+        let lines: Vec<(String, Colour)> = self
+            .errors
+            .iter()
+            .flat_map(|error| {
+                vec![
+                    (
+                        format!("Could not reload {}", error.path.display()),
+                        Colour::YELLOW,
+                    ),
+                    (format!("  {}", error.message), Colour::WHITE),
+                ]
+            })
+            .collect();
+
+        renderer.fill_rect(
+            Layer::Interface,
+            Rect::new(
+                0,
+                0,
+                renderer.viewport().width(),
+                (line_height * lines.len() as i32 + OVERLAY_OFFSET * 2) as u32,
+            ),
+            OVERLAY_BACKGROUND_COLOUR,
+        );
+
+        for (line_index, (line, colour)) in lines.into_iter().enumerate() {
+            renderer.draw_text(
+                Layer::Interface,
+                TextCommand::new(
+                    line,
+                    font_index,
+                    colour,
+                    Point::new(
+                        OVERLAY_OFFSET,
+                        OVERLAY_OFFSET + line_height * line_index as i32,
+                    ),
+                )
+                .with_anchor(Anchor::TopLeft)
+                .with_scale(OVERLAY_TEXT_SCALE),
+            );
+        }
+    }
+}
+
+pub fn read_hot_reload_settings() -> Result<HotReloadSettings, Box<dyn Error>> {
+    let hot_reload_data = super::read_config_section("hot-reload")?;
+
+    Ok(HotReloadSettings {
+        is_enabled: cfg!(debug_assertions) && hot_reload_data["enabled"].as_bool().unwrap_or(true),
+        poll_interval: hot_reload_data["poll-interval"]
+            .as_f64()
+            .map_or(DEFAULT_POLL_INTERVAL, |value| f64::max(value, 0.05) as f32),
+        directories: hot_reload_data["directories"].as_array().map_or_else(
+            || vec!["assets".to_string(), "config".to_string()],
+            |directories| {
+                directories
+                    .iter()
+                    .filter_map(|directory| directory.as_str().map(str::to_string))
+                    .collect()
+            },
+        ),
+    })
+}
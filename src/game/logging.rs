@@ -0,0 +1,257 @@
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, LineWriter, Write};
+use std::path::Path;
+use std::sync::{Mutex, MutexGuard, OnceLock};
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+const LOGS_DIRECTORY: &str = "logs";
+const LOG_FILE_NAME: &str = "game.log";
+const PREVIOUS_LOG_FILE_NAME: &str = "game.previous.log";
+const RECENT_LINE_COUNT: usize = 200;
+
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub enum Level {
+    Error,
+    Warn,
+    Info,
+    Debug,
+}
+
+impl Level {
+    fn name(self) -> &'static str {
+        match self {
+            Level::Error => "ERROR",
+            Level::Warn => "WARN",
+            Level::Info => "INFO",
+            Level::Debug => "DEBUG",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Level> {
+        match name.to_lowercase().as_str() {
+            "error" => Some(Level::Error),
+            "warn" | "warning" => Some(Level::Warn),
+            "info" => Some(Level::Info),
+            "debug" => Some(Level::Debug),
+            _ => None,
+        }
+    }
+}
+
+struct Logger {
+    level: Level,
+    module_levels: HashMap<String, Level>,
+    start: Instant,
+    file: Option<LineWriter<File>>,
+    recent_lines: VecDeque<String>,
+}
+
+impl Logger {
+    fn level_for(&self, module: &str) -> Level {
+        self.module_levels
+            .iter()
+            .filter(|(prefix, _)| {
+                module == prefix.as_str()
+                    || module
+                        .strip_prefix(prefix.as_str())
+                        .is_some_and(|rest| rest.starts_with("::"))
+            })
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map_or(self.level, |(_, level)| *level)
+    }
+}
+
+macro_rules! log_error {
+    ($($argument:tt)*) => {
+        $crate::game::logging::log(
+            $crate::game::logging::Level::Error,
+            module_path!(),
+            format_args!($($argument)*),
+        )
+    };
+}
+
+macro_rules! log_warn {
+    ($($argument:tt)*) => {
+        $crate::game::logging::log(
+            $crate::game::logging::Level::Warn,
+            module_path!(),
+            format_args!($($argument)*),
+        )
+    };
+}
+
+macro_rules! log_info {
+    ($($argument:tt)*) => {
+        $crate::game::logging::log(
+            $crate::game::logging::Level::Info,
+            module_path!(),
+            format_args!($($argument)*),
+        )
+    };
+}
+
+macro_rules! log_debug {
+    ($($argument:tt)*) => {
+        $crate::game::logging::log(
+            $crate::game::logging::Level::Debug,
+            module_path!(),
+            format_args!($($argument)*),
+        )
+    };
+}
+
+pub(crate) use {log_debug, log_error, log_info, log_warn};
+
+static LOGGER: OnceLock<Mutex<Logger>> = OnceLock::new();
+
+fn logger() -> MutexGuard<'static, Logger> {
+    LOGGER
+        .get_or_init(|| {
+            Mutex::new(Logger {
+                level: if cfg!(debug_assertions) {
+                    Level::Debug
+                } else {
+                    Level::Info
+                },
+                module_levels: HashMap::new(),
+                start: Instant::now(),
+                file: None,
+                recent_lines: VecDeque::with_capacity(RECENT_LINE_COUNT),
+            })
+        })
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+pub fn initialise() {
+    let settings = super::read_config_section("logging").unwrap_or_default();
+    let mut invalid_levels = vec![];
+
+    let mut parse_level = |name: &str, value: &serde_json::Value| {
+        let level = value.as_str().and_then(Level::from_name);
+
+        if level.is_none() {
+            invalid_levels.push(format!("{} = {}", name, value));
+        }
+
+        level
+    };
+
+    let level = settings
+        .get("level")
+        .and_then(|level| parse_level("level", level));
+    let module_levels = settings["modules"]
+        .as_object()
+        .into_iter()
+        .flatten()
+        .filter_map(|(module, level)| Some((module.clone(), parse_level(module, level)?)))
+        .collect::<HashMap<_, _>>();
+
+    let log_directory =
+        super::user_data_directory().map(|directory| directory.join(LOGS_DIRECTORY));
+    let (mut file, file_error) = match log_directory.as_deref().map(open_log_file) {
+        Some(Ok(file)) => (Some(file), None),
+        Some(Err(error)) => (None, Some(error)),
+        None => (None, None),
+    };
+
+    {
+        let mut logger = logger();
+
+        if let Some(level) = level {
+            logger.level = level;
+        }
+        logger.module_levels = module_levels;
+
+        if let Some(file) = &mut file {
+            for line in &logger.recent_lines {
+                let _ = writeln!(file, "{}", line);
+            }
+        }
+
+        logger.file = file;
+    }
+
+    for invalid_level in invalid_levels {
+        log_warn!("Ignoring invalid log level {}", invalid_level);
+    }
+
+    match (log_directory, file_error) {
+        (Some(log_directory), None) => {
+            log_info!("Logging to {}", log_directory.join(LOG_FILE_NAME).display());
+        }
+        (Some(log_directory), Some(error)) => {
+            log_warn!(
+                "Could not open a log file in {}: {}",
+                log_directory.display(),
+                error
+            );
+        }
+        (None, _) => log_warn!("No user data directory, logging to stderr only"),
+    }
+}
+
+fn open_log_file(log_directory: &Path) -> io::Result<LineWriter<File>> {
+    fs::create_dir_all(log_directory)?;
+
+    let log_filepath = log_directory.join(LOG_FILE_NAME);
+
+    if log_filepath.exists() {
+        fs::rename(&log_filepath, log_directory.join(PREVIOUS_LOG_FILE_NAME))?;
+    }
+
+    let mut file = LineWriter::new(File::create(log_filepath)?);
+    writeln!(file, "Log started at {} (Unix time)", unix_time())?;
+
+    Ok(file)
+}
+
+pub fn log(level: Level, module: &str, message: fmt::Arguments) {
+    let module = module
+        .split_once("::")
+        .map_or(module, |(_, module_path)| module_path);
+
+    if level > logger().level_for(module) {
+        return;
+    }
+
+    let message = message.to_string();
+    let mut logger = logger();
+
+    let line = format!(
+        "[{:>10.3}] {:<5} {}: {}",
+        logger.start.elapsed().as_secs_f32(),
+        level.name(),
+        module,
+        message
+    );
+
+    let _ = writeln!(io::stderr(), "{}", line);
+
+    if let Some(file) = &mut logger.file {
+        let _ = writeln!(file, "{}", line);
+    }
+
+    if logger.recent_lines.len() == RECENT_LINE_COUNT {
+        logger.recent_lines.pop_front();
+    }
+
+    logger.recent_lines.push_back(line);
+}
+
+pub fn recent_lines() -> Vec<String> {
+    logger().recent_lines.iter().cloned().collect()
+}
+
+pub fn elapsed_time() -> f32 {
+    logger().start.elapsed().as_secs_f32()
+}
+
+pub fn unix_time() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs())
+}
@@ -0,0 +1,396 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs;
+
+use rand::Rng;
+use sdl2::{pixels::Color as Colour, rect::Rect};
+
+use super::random;
+use super::renderer::{Layer, Renderer};
+use super::vfs;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum StarfieldMode {
+    Off,
+    Layered,
+    Replace,
+}
+
+impl StarfieldMode {
+    pub fn from_name(name: &str) -> Option<StarfieldMode> {
+        match name {
+            "off" => Some(StarfieldMode::Off),
+            "layered" => Some(StarfieldMode::Layered),
+            "replace" => Some(StarfieldMode::Replace),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct EmitterSettings {
+    pub spawn_rate: f32,
+    pub burst_count: u32,
+
+    pub lifetime: (f32, f32),
+    pub speed: (f32, f32),
+    pub direction: f32,
+    pub spread: f32,
+    pub size: (f32, f32),
+    pub gravity: f32,
+
+    pub start_colour: Colour,
+    pub end_colour: Colour,
+    pub is_additive: bool,
+}
+
+#[derive(Clone, Debug)]
+struct Particle {
+    x: f32,
+    y: f32,
+    x_velocity: f32,
+    y_velocity: f32,
+    gravity: f32,
+
+    age: f32,
+    lifetime: f32,
+    size: f32,
+
+    start_colour: Colour,
+    end_colour: Colour,
+    is_additive: bool,
+}
+
+impl Particle {
+    fn colour(&self) -> Colour {
+        let progress = f32::min(self.age / self.lifetime, 1.0);
+        let lerp =
+            |start: u8, end: u8| (start as f32 + (end as f32 - start as f32) * progress) as u8;
+
+        Colour::RGBA(
+            lerp(self.start_colour.r, self.end_colour.r),
+            lerp(self.start_colour.g, self.end_colour.g),
+            lerp(self.start_colour.b, self.end_colour.b),
+            lerp(self.start_colour.a, self.end_colour.a),
+        )
+    }
+}
+
+pub struct ParticleSettings {
+    pub max_count: usize,
+    pub starfield_mode: StarfieldMode,
+    pub emitters: HashMap<String, EmitterSettings>,
+    pub star_layers: Vec<StarLayerSettings>,
+}
+
+pub struct ParticleSystem {
+    particles: Vec<Particle>,
+    active_count: usize,
+    max_count: usize,
+
+    emitters: HashMap<String, EmitterSettings>,
+}
+
+impl ParticleSystem {
+    pub fn new(max_count: usize, emitters: HashMap<String, EmitterSettings>) -> ParticleSystem {
+        ParticleSystem {
+            particles: Vec::with_capacity(max_count),
+            active_count: 0,
+            max_count,
+            emitters,
+        }
+    }
+
+    pub fn active_count(&self) -> usize {
+        self.active_count
+    }
+
+    pub fn burst(&mut self, emitter_name: &str, x: f32, y: f32) {
+        if let Some(burst_count) = self
+            .emitters
+            .get(emitter_name)
+            .map(|settings| settings.burst_count)
+        {
+            self.spawn(emitter_name, x, y, burst_count);
+        }
+    }
+
+    pub fn spawn(&mut self, emitter_name: &str, x: f32, y: f32, count: u32) {
+        let settings = match self.emitters.get(emitter_name) {
+            Some(settings) => settings,
+            None => return,
+        };
+
+        let mut rng = random::cosmetic_rng();
+
+        for _ in 0..count {
+            if self.active_count >= self.max_count {
+                return;
+            }
+
+            let angle = (settings.direction
+                + random_in_range(&mut *rng, (-settings.spread / 2.0, settings.spread / 2.0)))
+            .to_radians();
+            let speed = random_in_range(&mut *rng, settings.speed);
+
+            let particle = Particle {
+                x,
+                y,
+                x_velocity: angle.cos() * speed,
+                y_velocity: angle.sin() * speed,
+                gravity: settings.gravity,
+                age: 0.0,
+                lifetime: f32::max(random_in_range(&mut *rng, settings.lifetime), 0.01),
+                size: random_in_range(&mut *rng, settings.size),
+                start_colour: settings.start_colour,
+                end_colour: settings.end_colour,
+                is_additive: settings.is_additive,
+            };
+
+            if self.active_count < self.particles.len() {
+                self.particles[self.active_count] = particle;
+            } else {
+                self.particles.push(particle);
+            }
+
+            self.active_count += 1;
+        }
+    }
+
+    pub fn update(&mut self, delta_time: f32) {
+        let mut index = 0;
+
+        while index < self.active_count {
+            let particle = &mut self.particles[index];
+
+            particle.age += delta_time;
+            particle.y_velocity += particle.gravity * delta_time;
+            particle.x += particle.x_velocity * delta_time;
+            particle.y += particle.y_velocity * delta_time;
+
+            if particle.age >= particle.lifetime {
+                self.active_count -= 1;
+                self.particles.swap(index, self.active_count);
+            } else {
+                index += 1;
+            }
+        }
+    }
+
+    pub fn draw(&self, renderer: &mut Renderer) {
+        for particle in &self.particles[..self.active_count] {
+            let size = f32::max(particle.size, 1.0) as u32;
+            let particle_rect = Rect::new(
+                particle.x as i32 - size as i32 / 2,
+                particle.y as i32 - size as i32 / 2,
+                size,
+                size,
+            );
+
+            if particle.is_additive {
+                renderer.fill_rect_additive(Layer::Effects, particle_rect, particle.colour());
+            } else {
+                renderer.fill_rect(Layer::Effects, particle_rect, particle.colour());
+            }
+        }
+    }
+}
+
+pub struct Emitter {
+    emitter_name: String,
+    spawn_accumulator: f32,
+
+    pub x: f32,
+    pub y: f32,
+    pub is_emitting: bool,
+}
+
+impl Emitter {
+    pub fn new(emitter_name: &str) -> Emitter {
+        Emitter {
+            emitter_name: emitter_name.to_string(),
+            spawn_accumulator: 0.0,
+            x: 0.0,
+            y: 0.0,
+            is_emitting: false,
+        }
+    }
+
+    pub fn update(&mut self, delta_time: f32, particle_system: &mut ParticleSystem) {
+        if !self.is_emitting {
+            self.spawn_accumulator = 0.0;
+
+            return;
+        }
+
+        let spawn_rate = match particle_system.emitters.get(&self.emitter_name) {
+            Some(settings) => settings.spawn_rate,
+            None => return,
+        };
+
+        self.spawn_accumulator += delta_time * spawn_rate;
+        let spawn_count = self.spawn_accumulator as u32;
+        self.spawn_accumulator -= spawn_count as f32;
+
+        particle_system.spawn(&self.emitter_name, self.x, self.y, spawn_count);
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct StarLayerSettings {
+    pub count: usize,
+    pub velocity: f32,
+    pub size: u32,
+    pub colour: Colour,
+}
+
+pub struct Starfield {
+    layers: Vec<(StarLayerSettings, Vec<(f32, f32)>)>,
+    size: (u32, u32),
+}
+
+impl Starfield {
+    pub fn new(
+        layer_settings: &[StarLayerSettings],
+        star_budget: usize,
+        size: (u32, u32),
+    ) -> Starfield {
+        let mut rng = random::cosmetic_rng();
+        let mut remaining_budget = star_budget;
+        let mut layers = vec![];
+
+        for settings in layer_settings {
+            let count = usize::min(settings.count, remaining_budget);
+            remaining_budget -= count;
+
+            let stars = (0..count)
+                .map(|_| {
+                    (
+                        rng.gen::<f32>() * size.0 as f32,
+                        rng.gen::<f32>() * size.1 as f32,
+                    )
+                })
+                .collect();
+
+            layers.push((settings.clone(), stars));
+        }
+
+        Starfield { layers, size }
+    }
+
+    pub fn star_count(&self) -> usize {
+        self.layers.iter().map(|(_, stars)| stars.len()).sum()
+    }
+
+    pub fn update(&mut self, delta_time: f32) {
+        let mut rng = random::cosmetic_rng();
+        let (width, height) = (self.size.0 as f32, self.size.1 as f32);
+
+        for (settings, stars) in &mut self.layers {
+            for star in stars.iter_mut() {
+                star.1 += settings.velocity * delta_time;
+
+                if star.1 > height {
+                    star.1 -= height;
+                    star.0 = rng.gen::<f32>() * width;
+                }
+            }
+        }
+    }
+
+    pub fn draw(&self, renderer: &mut Renderer) {
+        for (settings, stars) in &self.layers {
+            for star in stars {
+                renderer.fill_rect_additive(
+                    Layer::Background,
+                    Rect::new(star.0 as i32, star.1 as i32, settings.size, settings.size),
+                    settings.colour,
+                );
+            }
+        }
+    }
+}
+
+pub fn read_particle_settings(
+    particles_filepath: &str,
+) -> Result<ParticleSettings, Box<dyn Error>> {
+    let config_data = super::read_config_section("particles")?;
+
+    let starfield_mode_name = config_data["starfield"].as_str().unwrap_or("off");
+    let starfield_mode = StarfieldMode::from_name(starfield_mode_name)
+        .ok_or_else(|| format!("Unknown starfield mode \"{}\".", starfield_mode_name))?;
+
+    let json_string = fs::read_to_string(vfs::resolve(particles_filepath))?;
+    let particle_data: serde_json::Value = serde_json::from_str(&json_string[..])?;
+
+    let mut emitters = HashMap::new();
+
+    for emitter_data in particle_data["emitters"]
+        .as_array()
+        .ok_or("Particles file is missing an \"emitters\" array.")?
+    {
+        let name = emitter_data["name"]
+            .as_str()
+            .ok_or("Particle emitter is missing a name.")?;
+
+        emitters.insert(
+            name.to_string(),
+            EmitterSettings {
+                spawn_rate: emitter_data["spawn-rate"].as_f64().unwrap_or(0.0) as f32,
+                burst_count: emitter_data["burst-count"].as_u64().unwrap_or(0) as u32,
+                lifetime: read_range(&emitter_data["lifetime"]),
+                speed: read_range(&emitter_data["speed"]),
+                direction: emitter_data["direction"].as_f64().unwrap_or(0.0) as f32,
+                spread: emitter_data["spread"].as_f64().unwrap_or(360.0) as f32,
+                size: read_range(&emitter_data["size"]),
+                gravity: emitter_data["gravity"].as_f64().unwrap_or(0.0) as f32,
+                start_colour: read_colour(&emitter_data["start-colour"]),
+                end_colour: read_colour(&emitter_data["end-colour"]),
+                is_additive: emitter_data["additive"].as_bool().unwrap_or(false),
+            },
+        );
+    }
+
+    let star_layers = particle_data["starfield"]["layers"]
+        .as_array()
+        .unwrap_or(&vec![])
+        .iter()
+        .map(|layer_data| StarLayerSettings {
+            count: layer_data["count"].as_u64().unwrap_or(0) as usize,
+            velocity: layer_data["velocity"].as_f64().unwrap_or(0.0) as f32,
+            size: layer_data["size"].as_u64().unwrap_or(1) as u32,
+            colour: read_colour(&layer_data["colour"]),
+        })
+        .collect();
+
+    Ok(ParticleSettings {
+        max_count: config_data["max-count"].as_u64().unwrap_or(1000) as usize,
+        starfield_mode,
+        emitters,
+        star_layers,
+    })
+}
+
+pub fn read_colour(colour_data: &serde_json::Value) -> Colour {
+    Colour::RGBA(
+        colour_data["r"].as_u64().unwrap_or(255) as u8,
+        colour_data["g"].as_u64().unwrap_or(255) as u8,
+        colour_data["b"].as_u64().unwrap_or(255) as u8,
+        colour_data["a"].as_u64().unwrap_or(255) as u8,
+    )
+}
+
+fn read_range(range_data: &serde_json::Value) -> (f32, f32) {
+    (
+        range_data["min"].as_f64().unwrap_or(0.0) as f32,
+        range_data["max"].as_f64().unwrap_or(0.0) as f32,
+    )
+}
+
+fn random_in_range<R: Rng>(rng: &mut R, range: (f32, f32)) -> f32 {
+    if range.1 > range.0 {
+        rng.gen_range(range.0, range.1)
+    } else {
+        range.0
+    }
+}
@@ -0,0 +1,368 @@
+use std::error::Error;
+use std::fs;
+
+use sdl2::{
+    pixels::{Color as Colour, PixelFormatEnum},
+    rect::Rect,
+    render::{BlendMode, Texture, TextureCreator, WindowCanvas},
+    video::WindowContext,
+};
+
+const POST_PROCESSING_FILE_NAME: &str = "post_processing.json";
+
+const SCANLINE_SPACING: u32 = 3;
+const SCANLINE_COLOUR: Colour = Colour::RGBA(0, 0, 0, 90);
+const CURVATURE_STRIP_COUNT: u32 = 48;
+const CURVATURE_AMOUNT: f32 = 0.04;
+const BLOOM_DOWNSCALE: u32 = 4;
+const BLOOM_STRENGTH: u8 = 110;
+
+const OVERLAY_RED_BAND: (f32, f32) = (0.05, 0.18);
+const OVERLAY_GREEN_BAND: (f32, f32) = (0.72, 1.0);
+const OVERLAY_RED_COLOUR: Colour = Colour::RGB(255, 90, 90);
+const OVERLAY_GREEN_COLOUR: Colour = Colour::RGB(90, 255, 90);
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ColourFilter {
+    Off,
+    Protanopia,
+    Deuteranopia,
+    Tritanopia,
+}
+
+impl ColourFilter {
+    pub fn from_name(name: &str) -> Option<ColourFilter> {
+        match name {
+            "off" => Some(ColourFilter::Off),
+            "protanopia" => Some(ColourFilter::Protanopia),
+            "deuteranopia" => Some(ColourFilter::Deuteranopia),
+            "tritanopia" => Some(ColourFilter::Tritanopia),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            ColourFilter::Off => "off",
+            ColourFilter::Protanopia => "protanopia",
+            ColourFilter::Deuteranopia => "deuteranopia",
+            ColourFilter::Tritanopia => "tritanopia",
+        }
+    }
+
+    pub fn next(self) -> ColourFilter {
+        match self {
+            ColourFilter::Off => ColourFilter::Protanopia,
+            ColourFilter::Protanopia => ColourFilter::Deuteranopia,
+            ColourFilter::Deuteranopia => ColourFilter::Tritanopia,
+            ColourFilter::Tritanopia => ColourFilter::Off,
+        }
+    }
+
+    fn simulation_matrix(self) -> Option<[[f32; 3]; 3]> {
+        match self {
+            ColourFilter::Off => None,
+            ColourFilter::Protanopia => Some([
+                [0.567, 0.433, 0.0],
+                [0.558, 0.442, 0.0],
+                [0.0, 0.242, 0.758],
+            ]),
+            ColourFilter::Deuteranopia => {
+                Some([[0.625, 0.375, 0.0], [0.7, 0.3, 0.0], [0.0, 0.3, 0.7]])
+            }
+            ColourFilter::Tritanopia => {
+                Some([[0.95, 0.05, 0.0], [0.0, 0.433, 0.567], [0.0, 0.475, 0.525]])
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PostProcessingSettings {
+    pub scanlines: bool,
+    pub curvature: bool,
+    pub bloom: bool,
+    pub cabinet_overlay: bool,
+    pub colour_filter: ColourFilter,
+}
+
+impl Default for PostProcessingSettings {
+    fn default() -> PostProcessingSettings {
+        PostProcessingSettings {
+            scanlines: false,
+            curvature: false,
+            bloom: false,
+            cabinet_overlay: false,
+            colour_filter: ColourFilter::Off,
+        }
+    }
+}
+
+impl PostProcessingSettings {
+    pub fn is_enabled(&self) -> bool {
+        self.scanlines
+            || self.curvature
+            || self.bloom
+            || self.cabinet_overlay
+            || self.colour_filter != ColourFilter::Off
+    }
+}
+
+pub struct PostProcessor<'a> {
+    texture_creator: &'a TextureCreator<WindowContext>,
+    pub settings: PostProcessingSettings,
+
+    frame: Option<Texture<'a>>,
+    filtered_frame: Option<Texture<'a>>,
+    glow: Option<Texture<'a>>,
+    frame_size: (u32, u32),
+}
+
+impl<'a> PostProcessor<'a> {
+    pub fn new(
+        texture_creator: &'a TextureCreator<WindowContext>,
+        settings: PostProcessingSettings,
+    ) -> PostProcessor<'a> {
+        PostProcessor {
+            texture_creator,
+            settings,
+            frame: None,
+            filtered_frame: None,
+            glow: None,
+            frame_size: (0, 0),
+        }
+    }
+
+    pub fn frame(&mut self, size: (u32, u32)) -> &mut Texture<'a> {
+        if self.frame.is_none() || self.frame_size != size {
+            self.create_textures(size);
+        }
+
+        self.frame.as_mut().unwrap()
+    }
+
+    fn create_textures(&mut self, size: (u32, u32)) {
+        let (width, height) = size;
+
+        self.frame = Some(
+            self.texture_creator
+                .create_texture_target(PixelFormatEnum::ARGB8888, width, height)
+                .unwrap(),
+        );
+        self.filtered_frame = Some(
+            self.texture_creator
+                .create_texture_streaming(PixelFormatEnum::ARGB8888, width, height)
+                .unwrap(),
+        );
+
+        sdl2::hint::set("SDL_RENDER_SCALE_QUALITY", "linear");
+        let mut glow = self
+            .texture_creator
+            .create_texture_target(
+                PixelFormatEnum::ARGB8888,
+                u32::max(width / BLOOM_DOWNSCALE, 1),
+                u32::max(height / BLOOM_DOWNSCALE, 1),
+            )
+            .unwrap();
+        sdl2::hint::set("SDL_RENDER_SCALE_QUALITY", "nearest");
+
+        glow.set_blend_mode(BlendMode::Add);
+        glow.set_alpha_mod(BLOOM_STRENGTH);
+
+        self.glow = Some(glow);
+        self.frame_size = size;
+    }
+
+    pub fn present(&mut self, canvas: &mut WindowCanvas) {
+        let frame = self.frame.as_mut().unwrap();
+        let filtered_frame = self.filtered_frame.as_mut().unwrap();
+        let glow = self.glow.as_mut().unwrap();
+        let (width, height) = self.frame_size;
+
+        let source = match self.settings.colour_filter.simulation_matrix() {
+            Some(simulation_matrix) => {
+                let mut pixels = vec![];
+
+                canvas
+                    .with_texture_canvas(frame, |frame_canvas| {
+                        pixels = frame_canvas
+                            .read_pixels(None, PixelFormatEnum::ARGB8888)
+                            .unwrap();
+                    })
+                    .unwrap();
+
+                if pixels.len() == (width * height * 4) as usize {
+                    apply_colour_correction(&mut pixels, &simulation_matrix);
+                    filtered_frame
+                        .update(None, &pixels[..], width as usize * 4)
+                        .unwrap();
+                }
+
+                &*filtered_frame
+            }
+            None => &*frame,
+        };
+
+        canvas.set_draw_color(Colour::BLACK);
+        canvas.clear();
+
+        if self.settings.curvature {
+            for strip_index in 0..CURVATURE_STRIP_COUNT {
+                let strip_top = height * strip_index / CURVATURE_STRIP_COUNT;
+                let strip_bottom = height * (strip_index + 1) / CURVATURE_STRIP_COUNT;
+                let strip_height = strip_bottom - strip_top;
+
+                let distance_from_center =
+                    (strip_top + strip_height / 2) as f32 / height as f32 * 2.0 - 1.0;
+                let strip_width = (width as f32
+                    * (1.0 - CURVATURE_AMOUNT * distance_from_center * distance_from_center))
+                    as u32;
+
+                canvas
+                    .copy(
+                        source,
+                        Rect::new(0, strip_top as i32, width, strip_height),
+                        Rect::new(
+                            (width - strip_width) as i32 / 2,
+                            strip_top as i32,
+                            strip_width,
+                            strip_height,
+                        ),
+                    )
+                    .unwrap();
+            }
+        } else {
+            canvas.copy(source, None, None).unwrap();
+        }
+
+        if self.settings.bloom {
+            canvas
+                .with_texture_canvas(glow, |glow_canvas| {
+                    glow_canvas.copy(source, None, None).unwrap();
+                })
+                .unwrap();
+
+            canvas.copy(glow, None, None).unwrap();
+        }
+
+        let previous_blend_mode = canvas.blend_mode();
+
+        if self.settings.cabinet_overlay {
+            canvas.set_blend_mode(BlendMode::Mod);
+
+            for ((band_top, band_bottom), colour) in &[
+                (OVERLAY_RED_BAND, OVERLAY_RED_COLOUR),
+                (OVERLAY_GREEN_BAND, OVERLAY_GREEN_COLOUR),
+            ] {
+                let band_top = (height as f32 * band_top) as i32;
+                let band_bottom = (height as f32 * band_bottom) as i32;
+
+                canvas.set_draw_color(*colour);
+                canvas
+                    .fill_rect(Rect::new(
+                        0,
+                        band_top,
+                        width,
+                        (band_bottom - band_top) as u32,
+                    ))
+                    .unwrap();
+            }
+        }
+
+        if self.settings.scanlines {
+            let scanlines: Vec<Rect> = (0..height / SCANLINE_SPACING)
+                .map(|line_index| Rect::new(0, (line_index * SCANLINE_SPACING) as i32, width, 1))
+                .collect();
+
+            canvas.set_blend_mode(BlendMode::Blend);
+            canvas.set_draw_color(SCANLINE_COLOUR);
+            canvas.fill_rects(&scanlines[..]).unwrap();
+        }
+
+        canvas.set_blend_mode(previous_blend_mode);
+    }
+}
+
+fn apply_colour_correction(pixels: &mut [u8], simulation_matrix: &[[f32; 3]; 3]) {
+    for pixel in pixels.chunks_exact_mut(4) {
+        let argb = u32::from_ne_bytes([pixel[0], pixel[1], pixel[2], pixel[3]]);
+        let colour = [
+            ((argb >> 16) & 0xff) as f32,
+            ((argb >> 8) & 0xff) as f32,
+            (argb & 0xff) as f32,
+        ];
+
+        let simulate =
+            |row: &[f32; 3]| row[0] * colour[0] + row[1] * colour[1] + row[2] * colour[2];
+        let error = [
+            colour[0] - simulate(&simulation_matrix[0]),
+            colour[1] - simulate(&simulation_matrix[1]),
+            colour[2] - simulate(&simulation_matrix[2]),
+        ];
+
+        let corrected = [
+            colour[0],
+            colour[1] + 0.7 * error[0] + error[1],
+            colour[2] + 0.7 * error[0] + error[2],
+        ];
+        let to_channel = |value: f32| value.clamp(0.0, 255.0) as u32;
+
+        let argb = (argb & 0xff00_0000)
+            | to_channel(corrected[0]) << 16
+            | to_channel(corrected[1]) << 8
+            | to_channel(corrected[2]);
+
+        pixel.copy_from_slice(&argb.to_ne_bytes());
+    }
+}
+
+pub fn read_post_processing_settings() -> Result<PostProcessingSettings, Box<dyn Error>> {
+    let mut settings_data = super::read_config_section("post-processing")?;
+
+    let saved_settings_filepath = super::saves_directory().join(POST_PROCESSING_FILE_NAME);
+
+    if saved_settings_filepath.exists() {
+        let saved_settings_string = fs::read_to_string(saved_settings_filepath)?;
+        settings_data = serde_json::from_str(&saved_settings_string[..])?;
+    }
+
+    let default = PostProcessingSettings::default();
+
+    Ok(PostProcessingSettings {
+        scanlines: settings_data["scanlines"]
+            .as_bool()
+            .unwrap_or(default.scanlines),
+        curvature: settings_data["curvature"]
+            .as_bool()
+            .unwrap_or(default.curvature),
+        bloom: settings_data["bloom"].as_bool().unwrap_or(default.bloom),
+        cabinet_overlay: settings_data["cabinet-overlay"]
+            .as_bool()
+            .unwrap_or(default.cabinet_overlay),
+        colour_filter: settings_data["colour-filter"]
+            .as_str()
+            .and_then(ColourFilter::from_name)
+            .unwrap_or(default.colour_filter),
+    })
+}
+
+pub fn write_post_processing_settings(
+    settings: &PostProcessingSettings,
+) -> Result<(), Box<dyn Error>> {
+    let settings_data = serde_json::json!({
+        "scanlines": settings.scanlines,
+        "curvature": settings.curvature,
+        "bloom": settings.bloom,
+        "cabinet-overlay": settings.cabinet_overlay,
+        "colour-filter": settings.colour_filter.name(),
+    });
+
+    let saves_directory = super::saves_directory();
+    fs::create_dir_all(&saves_directory)?;
+    fs::write(
+        saves_directory.join(POST_PROCESSING_FILE_NAME),
+        serde_json::to_string_pretty(&settings_data)?,
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,54 @@
+#[cfg(feature = "profiling")]
+mod recorder;
+
+const PROFILE_FLAG: &str = "--profile";
+
+#[cfg(feature = "profiling")]
+pub use self::recorder::{
+    begin_frame, capture_from_arguments, frame_summary, handle_event, ProfileScope,
+};
+
+#[cfg(not(feature = "profiling"))]
+pub use self::disabled::{begin_frame, capture_from_arguments, frame_summary, handle_event};
+
+#[cfg(feature = "profiling")]
+macro_rules! profile_scope {
+    ($name:expr) => {
+        let _profile_scope = $crate::game::profiler::ProfileScope::new($name);
+    };
+}
+
+#[cfg(not(feature = "profiling"))]
+macro_rules! profile_scope {
+    ($name:expr) => {};
+}
+
+pub(crate) use profile_scope;
+
+#[cfg(not(feature = "profiling"))]
+mod disabled {
+    use std::env;
+
+    use sdl2::event::Event;
+
+    use crate::game::logging::log_warn;
+
+    #[inline(always)]
+    pub fn begin_frame() {}
+
+    #[inline(always)]
+    pub fn handle_event(_event: &Event) -> bool {
+        false
+    }
+
+    #[inline(always)]
+    pub fn frame_summary() -> Vec<String> {
+        vec![]
+    }
+
+    pub fn capture_from_arguments() {
+        if env::args().any(|argument| argument == super::PROFILE_FLAG) {
+            log_warn!("Ignoring --profile, this build does not have the profiling feature");
+        }
+    }
+}
@@ -0,0 +1,292 @@
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::io;
+use std::mem;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, MutexGuard, OnceLock};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use sdl2::{event::Event, keyboard::Keycode};
+use serde_json::json;
+
+use super::PROFILE_FLAG;
+use crate::game::logging::{self, log_info, log_warn};
+
+const PROFILES_DIRECTORY: &str = "profiles";
+const DEFAULT_CAPTURE_FRAME_COUNT: u32 = 300;
+const SUMMARY_SCOPE_COUNT: usize = 8;
+const FRAME_SCOPE_NAME: &str = "frame";
+
+struct ProfileEvent {
+    name: &'static str,
+    thread_id: u64,
+    start: Duration,
+    duration: Duration,
+}
+
+struct Capture {
+    remaining_frames: u32,
+    frame_count: u32,
+    events: Vec<ProfileEvent>,
+}
+
+struct Profiler {
+    start: Instant,
+    frame_start: Option<Duration>,
+    frame_events: Vec<ProfileEvent>,
+    frame_summary: Vec<(&'static str, Duration, u32)>,
+    thread_names: HashMap<u64, String>,
+    capture: Option<Capture>,
+}
+
+impl Profiler {
+    fn end_frame(&mut self, frame_event: ProfileEvent) -> Option<Capture> {
+        let mut events = mem::take(&mut self.frame_events);
+        let mut scope_totals = HashMap::<&'static str, (Duration, u32)>::new();
+
+        for event in &events {
+            let (total, count) = scope_totals.entry(event.name).or_default();
+            *total += event.duration;
+            *count += 1;
+        }
+
+        self.frame_summary = scope_totals
+            .into_iter()
+            .map(|(name, (total, count))| (name, total, count))
+            .collect();
+        self.frame_summary
+            .sort_by_key(|(_, total, _)| Reverse(*total));
+
+        events.push(frame_event);
+
+        let capture = self.capture.as_mut()?;
+        capture.events.extend(events);
+        capture.frame_count += 1;
+        capture.remaining_frames -= 1;
+
+        if capture.remaining_frames == 0 {
+            self.capture.take()
+        } else {
+            None
+        }
+    }
+}
+
+pub struct ProfileScope {
+    name: &'static str,
+    start: Instant,
+}
+
+impl ProfileScope {
+    pub fn new(name: &'static str) -> ProfileScope {
+        ProfileScope {
+            name,
+            start: Instant::now(),
+        }
+    }
+}
+
+impl Drop for ProfileScope {
+    fn drop(&mut self) {
+        let duration = self.start.elapsed();
+        let thread_id = current_thread_id();
+        let mut profiler = profiler();
+        let start = self.start.saturating_duration_since(profiler.start);
+
+        profiler.frame_events.push(ProfileEvent {
+            name: self.name,
+            thread_id,
+            start,
+            duration,
+        });
+    }
+}
+
+static PROFILER: OnceLock<Mutex<Profiler>> = OnceLock::new();
+static NEXT_THREAD_ID: AtomicU64 = AtomicU64::new(1);
+
+thread_local! {
+    static THREAD_ID: u64 = {
+        let thread_id = NEXT_THREAD_ID.fetch_add(1, Ordering::Relaxed);
+        let thread_name = thread::current().name().unwrap_or("unnamed").to_string();
+        profiler().thread_names.insert(thread_id, thread_name);
+
+        thread_id
+    };
+}
+
+fn profiler() -> MutexGuard<'static, Profiler> {
+    PROFILER
+        .get_or_init(|| {
+            Mutex::new(Profiler {
+                start: Instant::now(),
+                frame_start: None,
+                frame_events: vec![],
+                frame_summary: vec![],
+                thread_names: HashMap::new(),
+                capture: None,
+            })
+        })
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+fn current_thread_id() -> u64 {
+    THREAD_ID.with(|thread_id| *thread_id)
+}
+
+pub fn begin_frame() {
+    let thread_id = current_thread_id();
+    let finished_capture = {
+        let mut profiler = profiler();
+        let now = profiler.start.elapsed();
+
+        match profiler.frame_start.replace(now) {
+            Some(frame_start) => profiler.end_frame(ProfileEvent {
+                name: FRAME_SCOPE_NAME,
+                thread_id,
+                start: frame_start,
+                duration: now - frame_start,
+            }),
+            None => None,
+        }
+    };
+
+    if let Some(capture) = finished_capture {
+        write_capture(capture);
+    }
+}
+
+pub fn handle_event(event: &Event) -> bool {
+    match event {
+        Event::KeyDown {
+            keycode: Some(Keycode::F5),
+            repeat: false,
+            ..
+        } => {
+            let active_capture = profiler().capture.take();
+
+            match active_capture {
+                Some(capture) => write_capture(capture),
+                None => start_capture(DEFAULT_CAPTURE_FRAME_COUNT),
+            }
+
+            true
+        }
+        _ => false,
+    }
+}
+
+pub fn capture_from_arguments() {
+    let mut arguments = env::args().skip_while(|argument| argument != PROFILE_FLAG);
+
+    if arguments.next().is_some() {
+        let frame_count = arguments
+            .next()
+            .and_then(|frame_count| frame_count.parse().ok())
+            .filter(|frame_count| *frame_count > 0)
+            .unwrap_or(DEFAULT_CAPTURE_FRAME_COUNT);
+
+        start_capture(frame_count);
+    }
+}
+
+pub fn frame_summary() -> Vec<String> {
+    let profiler = profiler();
+
+    let mut lines = vec![match &profiler.capture {
+        Some(capture) => format!(
+            "Profiling: {} frames captured, {} left (F5 to stop)",
+            capture.frame_count, capture.remaining_frames
+        ),
+        None => "Profiling: F5 to capture a trace".to_string(),
+    }];
+
+    lines.extend(profiler.frame_summary.iter().take(SUMMARY_SCOPE_COUNT).map(
+        |(name, total, count)| {
+            format!(
+                "  {}: {:.2} ms ({}x)",
+                name,
+                total.as_secs_f32() * 1000.0,
+                count
+            )
+        },
+    ));
+
+    lines
+}
+
+fn start_capture(frame_count: u32) {
+    profiler().capture = Some(Capture {
+        remaining_frames: frame_count,
+        frame_count: 0,
+        events: vec![],
+    });
+
+    log_info!("Capturing a profile of {} frames", frame_count);
+}
+
+fn write_capture(capture: Capture) {
+    let thread_names = profiler().thread_names.clone();
+
+    match write_chrome_trace(&capture, &thread_names) {
+        Ok(trace_filepath) => log_info!(
+            "Wrote a profile of {} frames to {}",
+            capture.frame_count,
+            trace_filepath.display()
+        ),
+        Err(error) => log_warn!("Could not write profile: {}", error),
+    }
+}
+
+fn write_chrome_trace(
+    capture: &Capture,
+    thread_names: &HashMap<u64, String>,
+) -> io::Result<PathBuf> {
+    let microseconds = |duration: Duration| duration.as_secs_f64() * 1e6;
+
+    let mut trace_events = thread_names
+        .iter()
+        .map(|(thread_id, thread_name)| {
+            json!({
+                "name": "thread_name",
+                "ph": "M",
+                "pid": 1,
+                "tid": thread_id,
+                "args": { "name": thread_name },
+            })
+        })
+        .collect::<Vec<_>>();
+
+    trace_events.extend(capture.events.iter().map(|event| {
+        json!({
+            "name": event.name,
+            "cat": if event.name == FRAME_SCOPE_NAME { "frame" } else { "scope" },
+            "ph": "X",
+            "ts": microseconds(event.start),
+            "dur": microseconds(event.duration),
+            "pid": 1,
+            "tid": event.thread_id,
+        })
+    }));
+
+    let trace = json!({
+        "traceEvents": trace_events,
+        "displayTimeUnit": "ms",
+        "otherData": { "frames": capture.frame_count },
+    });
+
+    let profiles_directory = crate::game::user_data_directory()
+        .unwrap_or_default()
+        .join(PROFILES_DIRECTORY);
+    fs::create_dir_all(&profiles_directory)?;
+
+    let trace_filepath = profiles_directory.join(format!("trace-{}.json", logging::unix_time()));
+    fs::write(&trace_filepath, serde_json::to_string(&trace)?)?;
+
+    Ok(trace_filepath)
+}
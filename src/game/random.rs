@@ -0,0 +1,30 @@
+use std::env;
+use std::sync::{Mutex, MutexGuard, OnceLock};
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+const SEED_FLAG: &str = "--seed";
+
+// Keeps cosmetic effects from replaying the gameplay sequence when both use the same seed.
+const COSMETIC_SEED_SALT: u64 = 0x9e37_79b9_7f4a_7c15;
+
+static COSMETIC_RNG: OnceLock<Mutex<StdRng>> = OnceLock::new();
+
+pub fn seed_from_arguments() -> Option<u64> {
+    let mut arguments = env::args().skip_while(|argument| argument != SEED_FLAG);
+    arguments.next()?;
+
+    arguments.next().and_then(|seed| seed.parse().ok())
+}
+
+pub fn initial_seed() -> u64 {
+    seed_from_arguments().unwrap_or_else(|| rand::thread_rng().gen())
+}
+
+pub fn cosmetic_rng() -> MutexGuard<'static, StdRng> {
+    COSMETIC_RNG
+        .get_or_init(|| Mutex::new(StdRng::seed_from_u64(initial_seed() ^ COSMETIC_SEED_SALT)))
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
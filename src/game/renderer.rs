@@ -0,0 +1,612 @@
+use sdl2::{
+    pixels::Color as Colour,
+    rect::{Point, Rect},
+    render::{BlendMode, Texture, TextureCreator, WindowCanvas},
+    ttf::Font,
+};
+
+use super::profiler::profile_scope;
+
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub enum Layer {
+    Background,
+    Playfield,
+    Foreground,
+    Effects,
+    Interface,
+    Debug,
+}
+
+impl Layer {
+    fn is_world_space(self) -> bool {
+        self != Layer::Interface
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CameraTransform {
+    pub offset: (f32, f32),
+    pub zoom: f32,
+}
+
+impl Default for CameraTransform {
+    fn default() -> CameraTransform {
+        CameraTransform {
+            offset: (0.0, 0.0),
+            zoom: 1.0,
+        }
+    }
+}
+
+impl CameraTransform {
+    fn apply_to_point(&self, point: Point, viewport: Rect) -> Point {
+        let center = viewport.center();
+
+        Point::new(
+            center.x() + ((point.x() - center.x()) as f32 * self.zoom + self.offset.0) as i32,
+            center.y() + ((point.y() - center.y()) as f32 * self.zoom + self.offset.1) as i32,
+        )
+    }
+
+    fn apply_to_rect(&self, rect: Rect, viewport: Rect) -> Rect {
+        let top_left = self.apply_to_point(rect.top_left(), viewport);
+
+        Rect::new(
+            top_left.x(),
+            top_left.y(),
+            (rect.width() as f32 * self.zoom).round() as u32,
+            (rect.height() as f32 * self.zoom).round() as u32,
+        )
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Anchor {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    Center,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct SpriteCommand {
+    texture_index: usize,
+    source: Option<Rect>,
+    destination: Rect,
+
+    tint: Colour,
+    alpha: u8,
+    rotation: f64,
+    flip: (bool, bool),
+}
+
+impl SpriteCommand {
+    pub fn new<R: Into<Option<Rect>>>(
+        texture_index: usize,
+        source: R,
+        destination: Rect,
+    ) -> SpriteCommand {
+        SpriteCommand {
+            texture_index,
+            source: source.into(),
+            destination,
+            tint: Colour::WHITE,
+            alpha: 255,
+            rotation: 0.0,
+            flip: (false, false),
+        }
+    }
+
+    pub fn with_tint(mut self, tint: Colour) -> SpriteCommand {
+        self.tint = tint;
+
+        self
+    }
+
+    pub fn with_alpha(mut self, alpha: u8) -> SpriteCommand {
+        self.alpha = alpha;
+
+        self
+    }
+
+    pub fn with_rotation(mut self, degrees: f64) -> SpriteCommand {
+        self.rotation = degrees;
+
+        self
+    }
+
+    pub fn with_flip(mut self, is_horizontal: bool, is_vertical: bool) -> SpriteCommand {
+        self.flip = (is_horizontal, is_vertical);
+
+        self
+    }
+
+    fn is_transformed(&self) -> bool {
+        self.rotation != 0.0 || self.flip.0 || self.flip.1
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct TextCommand {
+    text: String,
+    font_index: usize,
+    colour: Colour,
+    position: Point,
+    anchor: Anchor,
+    scale: f32,
+}
+
+impl TextCommand {
+    pub fn new<T: Into<String>>(
+        text: T,
+        font_index: usize,
+        colour: Colour,
+        position: Point,
+    ) -> TextCommand {
+        TextCommand {
+            text: text.into(),
+            font_index,
+            colour,
+            position,
+            anchor: Anchor::Center,
+            scale: 1.0,
+        }
+    }
+
+    pub fn with_anchor(mut self, anchor: Anchor) -> TextCommand {
+        self.anchor = anchor;
+
+        self
+    }
+
+    pub fn with_scale(mut self, scale: f32) -> TextCommand {
+        self.scale = scale;
+
+        self
+    }
+
+    fn rect(&self, width: u32, height: u32) -> Rect {
+        let (width, height) = (
+            (width as f32 * self.scale) as u32,
+            (height as f32 * self.scale) as u32,
+        );
+        let (x, y) = (self.position.x(), self.position.y());
+
+        match self.anchor {
+            Anchor::TopLeft => Rect::new(x, y, width, height),
+            Anchor::TopRight => Rect::new(x - width as i32, y, width, height),
+            Anchor::BottomLeft => Rect::new(x, y - height as i32, width, height),
+            Anchor::Center => Rect::from_center(self.position, width, height),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+enum Shape {
+    Fill(Rect),
+    Outline(Rect),
+    Lines(Vec<Point>),
+}
+
+#[derive(Clone, Debug)]
+enum DrawCommand {
+    Sprite(SpriteCommand),
+    Shape {
+        shape: Shape,
+        colour: Colour,
+        blend_mode: BlendMode,
+    },
+    Text(TextCommand),
+}
+
+impl DrawCommand {
+    fn batch_key(&self) -> (bool, usize) {
+        match self {
+            DrawCommand::Sprite(sprite) => (false, sprite.texture_index),
+            _ => (true, 0),
+        }
+    }
+
+    fn is_batched_with(&self, other: &DrawCommand) -> bool {
+        match (self, other) {
+            (DrawCommand::Sprite(sprite), DrawCommand::Sprite(other_sprite)) => {
+                sprite.texture_index == other_sprite.texture_index
+            }
+            (
+                DrawCommand::Shape {
+                    shape,
+                    colour,
+                    blend_mode,
+                },
+                DrawCommand::Shape {
+                    shape: other_shape,
+                    colour: other_colour,
+                    blend_mode: other_blend_mode,
+                },
+            ) => {
+                colour == other_colour
+                    && blend_mode == other_blend_mode
+                    && matches!(
+                        (shape, other_shape),
+                        (Shape::Fill(_), Shape::Fill(_)) | (Shape::Outline(_), Shape::Outline(_))
+                    )
+            }
+            _ => false,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RenderStats {
+    pub commands: usize,
+    pub batches: usize,
+    pub draw_calls: usize,
+}
+
+pub struct Renderer {
+    viewport: Rect,
+    camera: CameraTransform,
+    clear_colour: Option<Colour>,
+    commands: Vec<(Layer, DrawCommand)>,
+
+    stats: RenderStats,
+}
+
+impl Renderer {
+    pub fn new() -> Renderer {
+        Renderer {
+            viewport: Rect::new(0, 0, 1, 1),
+            camera: CameraTransform::default(),
+            clear_colour: None,
+            commands: vec![],
+            stats: RenderStats::default(),
+        }
+    }
+
+    pub fn viewport(&self) -> Rect {
+        self.viewport
+    }
+
+    pub fn stats(&self) -> RenderStats {
+        self.stats
+    }
+
+    pub fn begin(&mut self, viewport: Rect) {
+        self.viewport = viewport;
+        self.camera = CameraTransform::default();
+        self.clear_colour = None;
+        self.commands.clear();
+    }
+
+    pub fn set_camera(&mut self, camera: CameraTransform) {
+        self.camera = camera;
+    }
+
+    pub fn clear(&mut self, colour: Colour) {
+        self.clear_colour = Some(colour);
+    }
+
+    pub fn draw_sprite(&mut self, layer: Layer, sprite: SpriteCommand) {
+        self.commands.push((layer, DrawCommand::Sprite(sprite)));
+    }
+
+    pub fn draw_text(&mut self, layer: Layer, text: TextCommand) {
+        self.commands.push((layer, DrawCommand::Text(text)));
+    }
+
+    pub fn fill_rect(&mut self, layer: Layer, rect: Rect, colour: Colour) {
+        self.push_shape(layer, Shape::Fill(rect), colour, BlendMode::Blend);
+    }
+
+    pub fn fill_rect_additive(&mut self, layer: Layer, rect: Rect, colour: Colour) {
+        self.push_shape(layer, Shape::Fill(rect), colour, BlendMode::Add);
+    }
+
+    pub fn draw_rect(&mut self, layer: Layer, rect: Rect, colour: Colour) {
+        self.push_shape(layer, Shape::Outline(rect), colour, BlendMode::Blend);
+    }
+
+    pub fn draw_lines(&mut self, layer: Layer, points: Vec<Point>, colour: Colour) {
+        self.push_shape(layer, Shape::Lines(points), colour, BlendMode::Blend);
+    }
+
+    fn push_shape(&mut self, layer: Layer, shape: Shape, colour: Colour, blend_mode: BlendMode) {
+        self.commands.push((
+            layer,
+            DrawCommand::Shape {
+                shape,
+                colour,
+                blend_mode,
+            },
+        ));
+    }
+
+    pub fn flush(
+        &mut self,
+        canvas: &mut WindowCanvas,
+        texture_creator: &TextureCreator<sdl2::video::WindowContext>,
+        textures: &mut [Texture],
+        fonts: &[Font],
+    ) {
+        profile_scope!("renderer::flush");
+
+        let mut stats = RenderStats {
+            commands: self.commands.len(),
+            ..RenderStats::default()
+        };
+
+        if let Some(clear_colour) = self.clear_colour {
+            canvas.set_draw_color(clear_colour);
+            canvas.clear();
+        }
+
+        self.commands
+            .sort_by_key(|(layer, command)| (*layer, command.batch_key()));
+
+        let previous_blend_mode = canvas.blend_mode();
+        let mut batch_start = 0;
+
+        while batch_start < self.commands.len() {
+            let layer = self.commands[batch_start].0;
+            let batch_end = batch_end(&self.commands, batch_start);
+
+            let camera = if layer.is_world_space() {
+                Some((self.camera, self.viewport))
+            } else {
+                None
+            };
+
+            stats.batches += 1;
+            stats.draw_calls += draw_batch(
+                canvas,
+                (texture_creator, textures, fonts),
+                &self.commands[batch_start..batch_end],
+                camera,
+            );
+
+            batch_start = batch_end;
+        }
+
+        canvas.set_blend_mode(previous_blend_mode);
+
+        self.commands.clear();
+        self.stats = stats;
+    }
+}
+
+fn batch_end(commands: &[(Layer, DrawCommand)], batch_start: usize) -> usize {
+    let (layer, first_command) = &commands[batch_start];
+
+    commands[batch_start + 1..]
+        .iter()
+        .position(|(other_layer, command)| {
+            other_layer != layer || !first_command.is_batched_with(command)
+        })
+        .map_or(commands.len(), |length| batch_start + 1 + length)
+}
+
+fn draw_batch(
+    canvas: &mut WindowCanvas,
+    resources: (
+        &TextureCreator<sdl2::video::WindowContext>,
+        &mut [Texture],
+        &[Font],
+    ),
+    batch: &[(Layer, DrawCommand)],
+    camera: Option<(CameraTransform, Rect)>,
+) -> usize {
+    let (texture_creator, textures, fonts) = resources;
+    let transform = |rect: Rect| {
+        camera.map_or(rect, |(camera, viewport)| {
+            camera.apply_to_rect(rect, viewport)
+        })
+    };
+
+    match &batch[0].1 {
+        DrawCommand::Sprite(first_sprite) => {
+            profile_scope!("renderer::sprites");
+
+            let texture = &mut textures[first_sprite.texture_index];
+            let mut modulation = None;
+
+            for (_, command) in batch {
+                if let DrawCommand::Sprite(sprite) = command {
+                    if modulation != Some((sprite.tint, sprite.alpha)) {
+                        texture.set_color_mod(sprite.tint.r, sprite.tint.g, sprite.tint.b);
+                        texture.set_alpha_mod(sprite.alpha);
+                        modulation = Some((sprite.tint, sprite.alpha));
+                    }
+
+                    if sprite.is_transformed() {
+                        canvas
+                            .copy_ex(
+                                texture,
+                                sprite.source,
+                                transform(sprite.destination),
+                                sprite.rotation,
+                                None,
+                                sprite.flip.0,
+                                sprite.flip.1,
+                            )
+                            .unwrap();
+                    } else {
+                        canvas
+                            .copy(texture, sprite.source, transform(sprite.destination))
+                            .unwrap();
+                    }
+                }
+            }
+
+            texture.set_color_mod(255, 255, 255);
+            texture.set_alpha_mod(255);
+
+            batch.len()
+        }
+        DrawCommand::Shape {
+            shape,
+            colour,
+            blend_mode,
+        } => {
+            profile_scope!("renderer::shapes");
+
+            canvas.set_blend_mode(*blend_mode);
+            canvas.set_draw_color(*colour);
+
+            let rects: Vec<Rect> = batch
+                .iter()
+                .filter_map(|(_, command)| match command {
+                    DrawCommand::Shape {
+                        shape: Shape::Fill(rect),
+                        ..
+                    }
+                    | DrawCommand::Shape {
+                        shape: Shape::Outline(rect),
+                        ..
+                    } => Some(transform(*rect)),
+                    _ => None,
+                })
+                .collect();
+
+            match shape {
+                Shape::Fill(_) => canvas.fill_rects(&rects[..]).unwrap(),
+                Shape::Outline(_) => canvas.draw_rects(&rects[..]).unwrap(),
+                Shape::Lines(points) => {
+                    let points: Vec<Point> = points
+                        .iter()
+                        .map(|point| {
+                            camera.map_or(*point, |(camera, viewport)| {
+                                camera.apply_to_point(*point, viewport)
+                            })
+                        })
+                        .collect();
+
+                    canvas.draw_lines(&points[..]).unwrap()
+                }
+            }
+
+            1
+        }
+        DrawCommand::Text(text) => {
+            profile_scope!("renderer::text");
+
+            let text_surface = fonts[text.font_index]
+                .render(&text.text)
+                .blended(text.colour)
+                .unwrap();
+            let text_texture = texture_creator
+                .create_texture_from_surface(text_surface)
+                .unwrap();
+            let text_texture_data = text_texture.query();
+
+            canvas
+                .copy(
+                    &text_texture,
+                    None,
+                    transform(text.rect(text_texture_data.width, text_texture_data.height)),
+                )
+                .unwrap();
+
+            1
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn batches(commands: &[(Layer, DrawCommand)]) -> Vec<(usize, usize)> {
+        let mut batches = vec![];
+        let mut batch_start = 0;
+
+        while batch_start < commands.len() {
+            let end = batch_end(commands, batch_start);
+            batches.push((batch_start, end));
+            batch_start = end;
+        }
+
+        batches
+    }
+
+    fn sprite(texture_index: usize) -> DrawCommand {
+        DrawCommand::Sprite(SpriteCommand::new(
+            texture_index,
+            None,
+            Rect::new(0, 0, 8, 8),
+        ))
+    }
+
+    fn fill(colour: Colour) -> DrawCommand {
+        DrawCommand::Shape {
+            shape: Shape::Fill(Rect::new(0, 0, 8, 8)),
+            colour,
+            blend_mode: BlendMode::Blend,
+        }
+    }
+
+    fn lines() -> DrawCommand {
+        DrawCommand::Shape {
+            shape: Shape::Lines(vec![Point::new(0, 0), Point::new(8, 8)]),
+            colour: Colour::WHITE,
+            blend_mode: BlendMode::Blend,
+        }
+    }
+
+    fn text() -> DrawCommand {
+        DrawCommand::Text(TextCommand::new(
+            "Score",
+            0,
+            Colour::WHITE,
+            Point::new(0, 0),
+        ))
+    }
+
+    #[test]
+    fn unbatchable_commands_are_drawn_alone() {
+        let commands = vec![
+            (Layer::Interface, text()),
+            (Layer::Interface, text()),
+            (Layer::Interface, lines()),
+            (Layer::Interface, lines()),
+        ];
+
+        assert_eq!(batches(&commands), vec![(0, 1), (1, 2), (2, 3), (3, 4)]);
+    }
+
+    #[test]
+    fn matching_commands_are_batched() {
+        let commands = vec![
+            (Layer::Playfield, sprite(0)),
+            (Layer::Playfield, sprite(0)),
+            (Layer::Playfield, sprite(1)),
+            (Layer::Playfield, fill(Colour::RED)),
+            (Layer::Playfield, fill(Colour::RED)),
+            (Layer::Playfield, fill(Colour::BLUE)),
+            (Layer::Playfield, text()),
+            (Layer::Playfield, lines()),
+            (Layer::Interface, sprite(1)),
+            (Layer::Interface, text()),
+        ];
+
+        assert_eq!(
+            batches(&commands),
+            vec![
+                (0, 2),
+                (2, 3),
+                (3, 5),
+                (5, 6),
+                (6, 7),
+                (7, 8),
+                (8, 9),
+                (9, 10)
+            ]
+        );
+    }
+
+    #[test]
+    fn layer_change_ends_a_batch() {
+        let commands = vec![(Layer::Playfield, sprite(0)), (Layer::Effects, sprite(0))];
+
+        assert_eq!(batches(&commands), vec![(0, 1), (1, 2)]);
+    }
+}
@@ -0,0 +1,344 @@
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+
+use sdl2::{
+    image::ImageRWops,
+    mixer::{Chunk, LoaderRWops},
+    pixels::PixelFormatEnum,
+    render::{BlendMode, Texture, TextureCreator},
+    rwops::RWops,
+    ttf::{Font, Sdl2TtfContext},
+    video::WindowContext,
+};
+
+use super::error::EngineError;
+use super::logging::log_debug;
+use super::profiler::profile_scope;
+use super::vfs;
+
+const FONT_POINT_SIZE: u16 = 128;
+
+#[derive(Clone, Debug, Default)]
+pub struct ResourcePaths {
+    pub textures: Vec<String>,
+    pub fonts: Vec<String>,
+    pub sounds: Vec<String>,
+}
+
+impl ResourcePaths {
+    fn decoded_paths(&self) -> impl Iterator<Item = (&String, ResourceKind)> {
+        self.textures
+            .iter()
+            .map(|path| (path, ResourceKind::Image))
+            .chain(self.sounds.iter().map(|path| (path, ResourceKind::Sound)))
+    }
+}
+
+pub struct LoadedResources<'a> {
+    pub textures: Vec<Texture<'a>>,
+    pub fonts: Vec<Font<'a, 'a>>,
+    pub sounds: HashMap<String, Chunk>,
+}
+
+pub enum ReloadedResource {
+    Texture,
+    Font,
+    Sounds(HashMap<String, Chunk>),
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum ResourceKind {
+    Image,
+    Sound,
+}
+
+struct DecodedImage {
+    width: u32,
+    height: u32,
+    pitch: usize,
+    pixels: Vec<u8>,
+}
+
+enum DecodedResource {
+    Image(DecodedImage),
+    Sound(Vec<u8>),
+}
+
+type DecodeResult = Result<DecodedResource, String>;
+
+pub struct ResourceManager<'a> {
+    texture_creator: &'a TextureCreator<WindowContext>,
+    ttf_context: &'a Sdl2TtfContext,
+
+    request_sender: Sender<(String, ResourceKind)>,
+    result_receiver: Receiver<(String, DecodeResult)>,
+
+    decoded: HashMap<String, DecodeResult>,
+    pending: HashSet<String>,
+    current_load: Option<ResourcePaths>,
+    loaded_paths: ResourcePaths,
+}
+
+impl<'a> ResourceManager<'a> {
+    pub fn new(
+        texture_creator: &'a TextureCreator<WindowContext>,
+        ttf_context: &'a Sdl2TtfContext,
+    ) -> ResourceManager<'a> {
+        let (request_sender, request_receiver) = mpsc::channel::<(String, ResourceKind)>();
+        let (result_sender, result_receiver) = mpsc::channel();
+
+        thread::Builder::new()
+            .name("resource-loader".to_string())
+            .spawn(move || {
+                for (path, kind) in request_receiver {
+                    let result = decode_resource(&path, kind);
+
+                    if result_sender.send((path, result)).is_err() {
+                        break;
+                    }
+                }
+            })
+            .unwrap();
+
+        ResourceManager {
+            texture_creator,
+            ttf_context,
+            request_sender,
+            result_receiver,
+            decoded: HashMap::new(),
+            pending: HashSet::new(),
+            current_load: None,
+            loaded_paths: ResourcePaths::default(),
+        }
+    }
+
+    pub fn preload(&mut self, paths: &ResourcePaths) {
+        for (path, kind) in paths.decoded_paths() {
+            if self.decoded.contains_key(path) || self.pending.contains(path) {
+                continue;
+            }
+
+            self.pending.insert(path.clone());
+            self.request_sender.send((path.clone(), kind)).unwrap();
+        }
+    }
+
+    pub fn begin_load(&mut self, paths: ResourcePaths) {
+        self.preload(&paths);
+        self.current_load = Some(paths);
+    }
+
+    pub fn is_loading(&self) -> bool {
+        self.current_load.is_some()
+    }
+
+    pub fn progress(&self) -> f32 {
+        let current_load = match &self.current_load {
+            Some(current_load) => current_load,
+            None => return 1.0,
+        };
+
+        let total_count = current_load.decoded_paths().count();
+        let decoded_count = current_load
+            .decoded_paths()
+            .filter(|(path, _)| self.decoded.contains_key(*path))
+            .count();
+
+        if total_count == 0 {
+            1.0
+        } else {
+            decoded_count as f32 / total_count as f32
+        }
+    }
+
+    pub fn poll(&mut self) {
+        while let Ok((path, result)) = self.result_receiver.try_recv() {
+            self.pending.remove(&path);
+            self.decoded.insert(path, result);
+        }
+    }
+
+    pub fn finish_load(&mut self) -> Result<Option<LoadedResources<'a>>, EngineError> {
+        if self.progress() < 1.0 {
+            return Ok(None);
+        }
+
+        let paths = match self.current_load.take() {
+            Some(paths) => paths,
+            None => return Ok(None),
+        };
+
+        let textures = paths
+            .textures
+            .iter()
+            .map(|path| match self.take_decoded(path)? {
+                DecodedResource::Image(image) => self
+                    .upload_texture(&image)
+                    .map_err(|error| EngineError::asset(path, error)),
+                DecodedResource::Sound(_) => Err(EngineError::asset(path, "not an image")),
+            })
+            .collect::<Result<_, _>>()?;
+
+        let fonts = paths
+            .fonts
+            .iter()
+            .map(|path| {
+                let font_data = vfs::read_static(path)
+                    .map_err(|error| error.to_string())
+                    .and_then(RWops::from_bytes)
+                    .map_err(|error| EngineError::asset(path, error))?;
+
+                self.ttf_context
+                    .load_font_from_rwops(font_data, FONT_POINT_SIZE)
+                    .map_err(|error| EngineError::asset(path, error))
+            })
+            .collect::<Result<_, _>>()?;
+
+        let sounds = paths
+            .sounds
+            .iter()
+            .map(|path| {
+                let sound_bytes = match self.take_decoded(path)? {
+                    DecodedResource::Sound(sound_bytes) => sound_bytes,
+                    DecodedResource::Image(_) => {
+                        return Err(EngineError::asset(path, "not a sound"))
+                    }
+                };
+                let sound_chunk = RWops::from_bytes(&sound_bytes)
+                    .and_then(|sound_data| sound_data.load_wav())
+                    .map_err(|error| EngineError::asset(path, error))?;
+
+                Ok((file_name(path), sound_chunk))
+            })
+            .collect::<Result<_, _>>()?;
+
+        log_debug!(
+            "Loaded {} textures, {} fonts and {} sounds",
+            paths.textures.len(),
+            paths.fonts.len(),
+            paths.sounds.len()
+        );
+
+        self.loaded_paths = paths;
+
+        Ok(Some(LoadedResources {
+            textures,
+            fonts,
+            sounds,
+        }))
+    }
+
+    pub fn reload(
+        &self,
+        path: &Path,
+        textures: &mut [Texture<'a>],
+        fonts: &mut [Font<'a, 'a>],
+    ) -> Result<Option<ReloadedResource>, String> {
+        let position = |paths: &[String]| paths.iter().position(|loaded| Path::new(loaded) == path);
+
+        if let Some(texture_index) = position(&self.loaded_paths.textures) {
+            let path = &self.loaded_paths.textures[texture_index];
+
+            textures[texture_index] = match decode_resource(path, ResourceKind::Image)? {
+                DecodedResource::Image(image) => self.upload_texture(&image)?,
+                DecodedResource::Sound(_) => unreachable!(),
+            };
+
+            Ok(Some(ReloadedResource::Texture))
+        } else if let Some(font_index) = position(&self.loaded_paths.fonts) {
+            fonts[font_index] = self
+                .ttf_context
+                .load_font(&self.loaded_paths.fonts[font_index], FONT_POINT_SIZE)?;
+
+            Ok(Some(ReloadedResource::Font))
+        } else if position(&self.loaded_paths.sounds).is_some() {
+            let mut sounds = HashMap::new();
+
+            for sound_path in &self.loaded_paths.sounds {
+                let sound_bytes = vfs::read(sound_path).map_err(|error| error.to_string())?;
+                let sound_chunk = RWops::from_bytes(&sound_bytes)?.load_wav()?;
+
+                sounds.insert(file_name(sound_path), sound_chunk);
+            }
+
+            Ok(Some(ReloadedResource::Sounds(sounds)))
+        } else {
+            Ok(None)
+        }
+    }
+
+    pub fn load_now(&mut self, paths: ResourcePaths) -> Result<LoadedResources<'a>, EngineError> {
+        self.begin_load(paths);
+
+        loop {
+            if let Some(resources) = self.finish_load()? {
+                return Ok(resources);
+            }
+
+            let (path, result) = self
+                .result_receiver
+                .recv()
+                .map_err(|error| EngineError::asset("resources", error))?;
+            self.pending.remove(&path);
+            self.decoded.insert(path, result);
+        }
+    }
+
+    fn take_decoded(&mut self, path: &str) -> Result<DecodedResource, EngineError> {
+        match self.decoded.remove(path) {
+            Some(result) => result.map_err(|error| EngineError::asset(path, error)),
+            None => Err(EngineError::asset(path, "it was never requested")),
+        }
+    }
+
+    fn upload_texture(&self, image: &DecodedImage) -> Result<Texture<'a>, String> {
+        let mut texture = self
+            .texture_creator
+            .create_texture_static(PixelFormatEnum::ARGB8888, image.width, image.height)
+            .map_err(|error| error.to_string())?;
+        texture
+            .update(None, &image.pixels, image.pitch)
+            .map_err(|error| error.to_string())?;
+        texture.set_blend_mode(BlendMode::Blend);
+
+        Ok(texture)
+    }
+}
+
+fn decode_resource(path: &str, kind: ResourceKind) -> DecodeResult {
+    profile_scope!("resources::decode");
+
+    match kind {
+        ResourceKind::Image => {
+            let image_bytes = vfs::read(path).map_err(|error| error.to_string())?;
+            let surface = RWops::from_bytes(&image_bytes)?
+                .load()?
+                .convert_format(PixelFormatEnum::ARGB8888)?;
+            let pixels = surface.with_lock(|pixels| pixels.to_vec());
+
+            Ok(DecodedResource::Image(DecodedImage {
+                width: surface.width(),
+                height: surface.height(),
+                pitch: surface.pitch() as usize,
+                pixels,
+            }))
+        }
+        ResourceKind::Sound => vfs::read(path)
+            .map(DecodedResource::Sound)
+            .map_err(|error| error.to_string()),
+    }
+}
+
+pub fn file_name(path: &str) -> String {
+    Path::new(path)
+        .file_name()
+        .and_then(|file_name| file_name.to_str())
+        .unwrap_or(path)
+        .to_string()
+}
+
+pub fn directory_paths(directory: &str) -> Vec<String> {
+    vfs::read_dir(directory)
+}
@@ -1,11 +1,25 @@
-use std::collections::VecDeque;
+use std::collections::{HashMap, VecDeque};
+use std::error::Error;
+use std::path::Path;
 
-use sdl2::render::{Texture, TextureCreator, WindowCanvas};
+use sdl2::mixer::Chunk;
+use sdl2::render::{Texture, WindowCanvas};
 use sdl2::ttf::Font;
 
+use super::console::{ConsoleCommand, ConsoleVariable};
 use super::input::InputState;
+use super::renderer::Renderer;
+use super::resources::ResourcePaths;
+use super::transition::Transition;
 
 pub trait Scene {
+    fn name(&self) -> &'static str {
+        std::any::type_name::<Self>()
+            .rsplit("::")
+            .next()
+            .unwrap_or("Scene")
+    }
+
     fn is_done(&self) -> bool;
 
     fn on_load(
@@ -13,13 +27,52 @@ pub trait Scene {
         sdl_context: &sdl2::Sdl,
         canvas: &WindowCanvas,
         previous_scene_payload: Option<i32>,
-    ) -> (Vec<String>, Vec<String>);
+    ) -> ResourcePaths;
+    fn on_sounds_loaded(&mut self, _sounds: HashMap<String, Chunk>) {}
     fn on_late_load(&mut self, _canvas: &WindowCanvas, _textures: &[Texture], _fonts: &[Font]) {}
 
     fn on_unload(&mut self, _sdl_context: &sdl2::Sdl) -> Option<i32> {
         None
     }
 
+    fn transition(&self) -> Transition {
+        Transition::default()
+    }
+
+    fn preload(&self) -> ResourcePaths {
+        ResourcePaths::default()
+    }
+
+    fn on_loading_progress(&mut self, _progress: f32) {}
+
+    fn on_textures_reloaded(&mut self, _textures: &[Texture]) {}
+    fn on_file_changed(
+        &mut self,
+        _path: &Path,
+        _textures: &[Texture],
+    ) -> Result<(), Box<dyn Error>> {
+        Ok(())
+    }
+
+    fn console_commands(&self) -> Vec<ConsoleCommand> {
+        vec![]
+    }
+
+    fn console_variables(&self) -> Vec<ConsoleVariable> {
+        vec![]
+    }
+
+    fn run_console_command(
+        &mut self,
+        name: &str,
+        _arguments: &[&str],
+        _canvas: &WindowCanvas,
+    ) -> Result<String, String> {
+        Err(format!("Unknown command \"{}\".", name))
+    }
+
+    fn on_console_variable_changed(&mut self, _name: &str, _value: f32) {}
+
     fn poll_event(&mut self, _event: sdl2::event::Event) {}
     fn process_input(&mut self, input_state: &InputState);
 
@@ -40,11 +93,11 @@ pub trait Scene {
     ) {
     }
 
-    fn draw(
-        &mut self,
-        canvas: &mut WindowCanvas,
-        texture_creator: &TextureCreator<sdl2::video::WindowContext>,
-        textures: &[Texture],
-        fonts: &[Font],
-    );
+    fn draw(&mut self, renderer: &mut Renderer, fonts: &[Font]);
+
+    fn draw_hitboxes(&self, _renderer: &mut Renderer) {}
+
+    fn debug_lines(&self) -> Vec<String> {
+        vec![]
+    }
 }
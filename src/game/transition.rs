@@ -0,0 +1,219 @@
+use rand::seq::SliceRandom;
+use sdl2::{
+    mixer::{Music, MAX_VOLUME},
+    pixels::{Color as Colour, PixelFormatEnum},
+    rect::Rect,
+    render::{BlendMode, Texture, TextureCreator, WindowCanvas},
+    video::WindowContext,
+};
+
+use super::random;
+
+const DEFAULT_DURATION: f32 = 0.6;
+const DISSOLVE_CELL_SIZE: u32 = 16;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TransitionEffect {
+    Cut,
+    Fade,
+    Wipe,
+    Dissolve,
+}
+
+impl TransitionEffect {
+    fn out_fraction(self) -> f32 {
+        match self {
+            TransitionEffect::Fade => 0.5,
+            _ => 0.0,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Transition {
+    pub effect: TransitionEffect,
+    pub duration: f32,
+}
+
+impl Default for Transition {
+    fn default() -> Transition {
+        Transition::new(TransitionEffect::Fade, DEFAULT_DURATION)
+    }
+}
+
+impl Transition {
+    pub fn new(effect: TransitionEffect, duration: f32) -> Transition {
+        Transition {
+            effect,
+            duration: f32::max(duration, 0.0),
+        }
+    }
+
+    pub fn is_instant(&self) -> bool {
+        self.effect == TransitionEffect::Cut || self.duration <= 0.0
+    }
+}
+
+pub struct SceneTransition<'a> {
+    transition: Transition,
+    snapshot: Texture<'a>,
+    size: (u32, u32),
+
+    dissolve_thresholds: Vec<f32>,
+
+    timer: f32,
+    has_switched: bool,
+}
+
+impl<'a> SceneTransition<'a> {
+    pub fn new(
+        texture_creator: &'a TextureCreator<WindowContext>,
+        size: (u32, u32),
+        transition: Transition,
+    ) -> SceneTransition<'a> {
+        let (width, height) = size;
+        let mut snapshot = texture_creator
+            .create_texture_target(PixelFormatEnum::ARGB8888, width, height)
+            .unwrap();
+        snapshot.set_blend_mode(BlendMode::None);
+
+        let cell_count = (cell_columns(width) * cell_rows(height)) as usize;
+        let mut dissolve_thresholds: Vec<f32> = (0..cell_count)
+            .map(|cell_index| (cell_index + 1) as f32 / cell_count as f32)
+            .collect();
+        dissolve_thresholds.shuffle(&mut *random::cosmetic_rng());
+
+        SceneTransition {
+            transition,
+            snapshot,
+            size,
+            dissolve_thresholds,
+            timer: 0.0,
+            has_switched: false,
+        }
+    }
+
+    pub fn snapshot_mut(&mut self) -> &mut Texture<'a> {
+        &mut self.snapshot
+    }
+
+    fn out_time(&self) -> f32 {
+        self.transition.duration * self.transition.effect.out_fraction()
+    }
+
+    fn out_progress(&self) -> f32 {
+        if self.out_time() <= 0.0 {
+            1.0
+        } else {
+            f32::min(self.timer / self.out_time(), 1.0)
+        }
+    }
+
+    fn in_progress(&self) -> f32 {
+        let in_time = self.transition.duration - self.out_time();
+
+        if in_time <= 0.0 {
+            1.0
+        } else {
+            f32::min((self.timer - self.out_time()) / in_time, 1.0)
+        }
+    }
+
+    pub fn update(&mut self, delta_time: f32) {
+        self.timer = f32::min(self.timer + delta_time, self.transition.duration);
+
+        let music_volume = if self.has_switched {
+            self.in_progress()
+        } else {
+            1.0 - self.out_progress()
+        };
+
+        Music::set_volume((MAX_VOLUME as f32 * music_volume.clamp(0.0, 1.0)) as i32);
+    }
+
+    pub fn is_switch_due(&self) -> bool {
+        !self.has_switched && self.timer >= self.out_time()
+    }
+
+    pub fn on_switched(&mut self) {
+        self.timer = self.out_time();
+        self.has_switched = true;
+
+        Music::set_volume(0);
+    }
+
+    pub fn is_outgoing_frozen(&self) -> bool {
+        !self.has_switched
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.has_switched && self.timer >= self.transition.duration
+    }
+
+    pub fn draw(&self, canvas: &mut WindowCanvas) {
+        let (width, height) = self.size;
+        let previous_blend_mode = canvas.blend_mode();
+
+        if !self.has_switched {
+            canvas.copy(&self.snapshot, None, None).unwrap();
+        }
+
+        match self.transition.effect {
+            TransitionEffect::Cut => (),
+            TransitionEffect::Fade => {
+                let darkness = if self.has_switched {
+                    1.0 - self.in_progress()
+                } else {
+                    self.out_progress()
+                };
+
+                canvas.set_blend_mode(BlendMode::Blend);
+                canvas.set_draw_color(Colour::RGBA(
+                    0,
+                    0,
+                    0,
+                    (255.0 * darkness.clamp(0.0, 1.0)) as u8,
+                ));
+                canvas.fill_rect(None).unwrap();
+            }
+            TransitionEffect::Wipe => {
+                let edge = (width as f32 * self.in_progress()) as u32;
+
+                if edge < width {
+                    let remaining = Rect::new(edge as i32, 0, width - edge, height);
+
+                    canvas.copy(&self.snapshot, remaining, remaining).unwrap();
+                }
+            }
+            TransitionEffect::Dissolve => {
+                let progress = self.in_progress();
+                let columns = cell_columns(width);
+
+                for (cell_index, threshold) in self.dissolve_thresholds.iter().enumerate() {
+                    if *threshold <= progress {
+                        continue;
+                    }
+
+                    let cell = Rect::new(
+                        (cell_index as u32 % columns * DISSOLVE_CELL_SIZE) as i32,
+                        (cell_index as u32 / columns * DISSOLVE_CELL_SIZE) as i32,
+                        DISSOLVE_CELL_SIZE,
+                        DISSOLVE_CELL_SIZE,
+                    );
+
+                    canvas.copy(&self.snapshot, cell, cell).unwrap();
+                }
+            }
+        }
+
+        canvas.set_blend_mode(previous_blend_mode);
+    }
+}
+
+fn cell_columns(width: u32) -> u32 {
+    width.div_ceil(DISSOLVE_CELL_SIZE)
+}
+
+fn cell_rows(height: u32) -> u32 {
+    height.div_ceil(DISSOLVE_CELL_SIZE)
+}
@@ -0,0 +1,151 @@
+// Shared with the pack tool, which only uses the writing half.
+#[allow(dead_code)]
+mod archive;
+
+use std::collections::{BTreeSet, HashMap};
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+use self::archive::Archive;
+use super::logging::log_warn;
+
+pub const ARCHIVE_FILE_NAME: &str = "assets.pak";
+
+const MAX_BASE_DIRECTORY_DEPTH: usize = 4;
+
+static VFS: OnceLock<Vfs> = OnceLock::new();
+
+struct Vfs {
+    base_directory: PathBuf,
+    archive: Option<Archive>,
+    static_files: Mutex<HashMap<String, &'static [u8]>>,
+}
+
+pub fn mount() {
+    let base_directory = find_base_directory();
+    let archive_filepath = base_directory.join(ARCHIVE_FILE_NAME);
+
+    let archive = if archive_filepath.is_file() {
+        match Archive::open(&archive_filepath) {
+            Ok(archive) => Some(archive),
+            Err(error) => {
+                log_warn!("Could not open {}: {}", ARCHIVE_FILE_NAME, error);
+
+                None
+            }
+        }
+    } else {
+        None
+    };
+
+    VFS.get_or_init(|| Vfs {
+        base_directory,
+        archive,
+        static_files: Mutex::new(HashMap::new()),
+    });
+}
+
+fn find_base_directory() -> PathBuf {
+    let current_directory = env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+    let executable_path = match env::current_exe() {
+        Ok(executable_path) => executable_path,
+        Err(_) => return current_directory,
+    };
+
+    executable_path
+        .ancestors()
+        .skip(1)
+        .take(MAX_BASE_DIRECTORY_DEPTH)
+        .find(|directory| {
+            directory.join(super::CONFIG_FILE_NAME).is_file()
+                || directory.join(ARCHIVE_FILE_NAME).is_file()
+        })
+        .map_or(current_directory, Path::to_path_buf)
+}
+
+pub fn base_directory() -> &'static Path {
+    VFS.get()
+        .map_or(Path::new(""), |vfs| vfs.base_directory.as_path())
+}
+
+pub fn resolve(path: impl AsRef<Path>) -> PathBuf {
+    base_directory().join(path)
+}
+
+fn archive() -> Option<&'static Archive> {
+    VFS.get().and_then(|vfs| vfs.archive.as_ref())
+}
+
+fn archive_path(path: &str) -> String {
+    path.replace('\\', "/")
+}
+
+pub fn read(path: &str) -> io::Result<Vec<u8>> {
+    if let Some(archive) = archive() {
+        let archive_path = archive_path(path);
+
+        if archive.contains(&archive_path) {
+            return archive.read(&archive_path);
+        }
+    }
+
+    fs::read(resolve(path))
+}
+
+pub fn read_to_string(path: &str) -> io::Result<String> {
+    String::from_utf8(read(path)?)
+        .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+}
+
+pub fn read_static(path: &str) -> io::Result<&'static [u8]> {
+    let vfs = VFS
+        .get()
+        .expect("The virtual filesystem has not been mounted");
+    let mut static_files = vfs.static_files.lock().unwrap();
+
+    if let Some(data) = static_files.get(path) {
+        return Ok(data);
+    }
+
+    let data: &'static [u8] = Box::leak(read(path)?.into_boxed_slice());
+    static_files.insert(path.to_string(), data);
+
+    Ok(data)
+}
+
+pub fn read_dir(directory: &str) -> Vec<String> {
+    let directory = archive_path(directory).trim_end_matches('/').to_string();
+    let mut paths = BTreeSet::new();
+
+    if let Some(archive) = archive() {
+        paths.extend(
+            archive
+                .paths()
+                .filter(|path| {
+                    Path::new(path)
+                        .parent()
+                        .is_some_and(|parent| parent == Path::new(&directory))
+                })
+                .map(str::to_string),
+        );
+    }
+
+    if let Ok(entries) = fs::read_dir(resolve(&directory)) {
+        paths.extend(
+            entries
+                .flatten()
+                .filter(|entry| entry.path().is_file())
+                .filter_map(|entry| {
+                    entry
+                        .file_name()
+                        .to_str()
+                        .map(|file_name| format!("{}/{}", directory, file_name))
+                }),
+        );
+    }
+
+    paths.into_iter().collect()
+}
@@ -0,0 +1,436 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::Path;
+use std::sync::Mutex;
+
+pub const ARCHIVE_MAGIC: &[u8; 4] = b"SIPK";
+pub const ARCHIVE_VERSION: u32 = 1;
+
+const HEADER_SIZE: u64 = 16;
+
+const WINDOW_SIZE: usize = 4096;
+const MIN_MATCH_LENGTH: usize = 3;
+const MAX_MATCH_LENGTH: usize = 18;
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Compression {
+    None,
+    Lzss,
+}
+
+impl Compression {
+    fn from_byte(byte: u8) -> io::Result<Compression> {
+        match byte {
+            0 => Ok(Compression::None),
+            1 => Ok(Compression::Lzss),
+            _ => Err(invalid_data(format!("unknown compression {}", byte))),
+        }
+    }
+
+    fn to_byte(self) -> u8 {
+        match self {
+            Compression::None => 0,
+            Compression::Lzss => 1,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ArchiveEntry {
+    pub path: String,
+    pub offset: u64,
+    pub stored_size: u64,
+    pub size: u64,
+    pub compression: Compression,
+    pub hash: u64,
+}
+
+pub struct Archive {
+    file: Mutex<File>,
+    entries: HashMap<String, ArchiveEntry>,
+}
+
+impl Archive {
+    pub fn open(archive_path: &Path) -> io::Result<Archive> {
+        let mut file = File::open(archive_path)?;
+
+        let mut magic = [0; 4];
+        file.read_exact(&mut magic)?;
+
+        if &magic != ARCHIVE_MAGIC {
+            return Err(invalid_data("not an asset archive".to_string()));
+        }
+
+        let version = read_u32(&mut file)?;
+
+        if version != ARCHIVE_VERSION {
+            return Err(invalid_data(format!(
+                "unsupported archive version {}",
+                version
+            )));
+        }
+
+        let index_offset = read_u64(&mut file)?;
+        file.seek(SeekFrom::Start(index_offset))?;
+
+        let entry_count = read_u32(&mut file)?;
+        let mut entries = HashMap::new();
+
+        for _ in 0..entry_count {
+            let path_length = read_u16(&mut file)? as usize;
+            let mut path = vec![0; path_length];
+            file.read_exact(&mut path)?;
+
+            let entry = ArchiveEntry {
+                path: String::from_utf8(path).map_err(|error| invalid_data(error.to_string()))?,
+                offset: read_u64(&mut file)?,
+                stored_size: read_u64(&mut file)?,
+                size: read_u64(&mut file)?,
+                compression: Compression::from_byte(read_u8(&mut file)?)?,
+                hash: read_u64(&mut file)?,
+            };
+
+            entries.insert(entry.path.clone(), entry);
+        }
+
+        Ok(Archive {
+            file: Mutex::new(file),
+            entries,
+        })
+    }
+
+    pub fn contains(&self, path: &str) -> bool {
+        self.entries.contains_key(path)
+    }
+
+    pub fn paths(&self) -> impl Iterator<Item = &str> {
+        self.entries.keys().map(String::as_str)
+    }
+
+    pub fn read(&self, path: &str) -> io::Result<Vec<u8>> {
+        let entry = self
+            .entries
+            .get(path)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, path.to_string()))?;
+
+        let mut stored_data = vec![0; entry.stored_size as usize];
+        {
+            let mut file = self.file.lock().unwrap();
+            file.seek(SeekFrom::Start(entry.offset))?;
+            file.read_exact(&mut stored_data)?;
+        }
+
+        let data = match entry.compression {
+            Compression::None => stored_data,
+            Compression::Lzss => decompress(&stored_data, entry.size as usize)?,
+        };
+
+        if data.len() as u64 != entry.size || fnv1a_hash(&data) != entry.hash {
+            return Err(invalid_data(format!("{} is corrupt", path)));
+        }
+
+        Ok(data)
+    }
+}
+
+pub fn write_archive(
+    writer: &mut (impl Write + Seek),
+    files: &[(String, Vec<u8>)],
+    allow_compression: bool,
+) -> io::Result<Vec<ArchiveEntry>> {
+    writer.write_all(ARCHIVE_MAGIC)?;
+    writer.write_all(&ARCHIVE_VERSION.to_le_bytes())?;
+    writer.write_all(&0u64.to_le_bytes())?;
+
+    let mut entries = vec![];
+    let mut offset = HEADER_SIZE;
+
+    for (path, data) in files {
+        let compressed_data = if allow_compression {
+            Some(compress(data)).filter(|compressed_data| compressed_data.len() < data.len())
+        } else {
+            None
+        };
+        let (compression, stored_data) = match &compressed_data {
+            Some(compressed_data) => (Compression::Lzss, compressed_data),
+            None => (Compression::None, data),
+        };
+
+        writer.write_all(stored_data)?;
+
+        entries.push(ArchiveEntry {
+            path: path.clone(),
+            offset,
+            stored_size: stored_data.len() as u64,
+            size: data.len() as u64,
+            compression,
+            hash: fnv1a_hash(data),
+        });
+        offset += stored_data.len() as u64;
+    }
+
+    writer.write_all(&(entries.len() as u32).to_le_bytes())?;
+
+    for entry in &entries {
+        writer.write_all(&(entry.path.len() as u16).to_le_bytes())?;
+        writer.write_all(entry.path.as_bytes())?;
+        writer.write_all(&entry.offset.to_le_bytes())?;
+        writer.write_all(&entry.stored_size.to_le_bytes())?;
+        writer.write_all(&entry.size.to_le_bytes())?;
+        writer.write_all(&[entry.compression.to_byte()])?;
+        writer.write_all(&entry.hash.to_le_bytes())?;
+    }
+
+    writer.seek(SeekFrom::Start(8))?;
+    writer.write_all(&offset.to_le_bytes())?;
+
+    Ok(entries)
+}
+
+pub fn fnv1a_hash(data: &[u8]) -> u64 {
+    data.iter().fold(FNV_OFFSET_BASIS, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(FNV_PRIME)
+    })
+}
+
+fn compress(data: &[u8]) -> Vec<u8> {
+    let mut output = vec![];
+    let mut last_positions: HashMap<[u8; 3], usize> = HashMap::new();
+
+    let mut position = 0;
+    let mut flags_index = 0;
+    let mut item_count = 8;
+
+    while position < data.len() {
+        if item_count == 8 {
+            flags_index = output.len();
+            output.push(0);
+            item_count = 0;
+        }
+
+        let mut match_length = 0;
+        let mut match_distance = 0;
+
+        if position + MIN_MATCH_LENGTH <= data.len() {
+            let key = [data[position], data[position + 1], data[position + 2]];
+
+            if let Some(&candidate) = last_positions.get(&key) {
+                if position - candidate <= WINDOW_SIZE {
+                    let max_length = usize::min(MAX_MATCH_LENGTH, data.len() - position);
+
+                    while match_length < max_length
+                        && data[candidate + match_length] == data[position + match_length]
+                    {
+                        match_length += 1;
+                    }
+
+                    match_distance = position - candidate;
+                }
+            }
+        }
+
+        let step = if match_length >= MIN_MATCH_LENGTH {
+            let token = ((match_distance - 1) << 4) | (match_length - MIN_MATCH_LENGTH);
+            output.extend_from_slice(&(token as u16).to_le_bytes());
+
+            match_length
+        } else {
+            output[flags_index] |= 1 << item_count;
+            output.push(data[position]);
+
+            1
+        };
+
+        for skipped_position in position..position + step {
+            if skipped_position + MIN_MATCH_LENGTH <= data.len() {
+                last_positions.insert(
+                    [
+                        data[skipped_position],
+                        data[skipped_position + 1],
+                        data[skipped_position + 2],
+                    ],
+                    skipped_position,
+                );
+            }
+        }
+
+        position += step;
+        item_count += 1;
+    }
+
+    output
+}
+
+fn decompress(data: &[u8], size: usize) -> io::Result<Vec<u8>> {
+    let mut output = Vec::with_capacity(size);
+    let mut position = 0;
+
+    while position < data.len() && output.len() < size {
+        let flags = data[position];
+        position += 1;
+
+        for item_index in 0..8 {
+            if position >= data.len() || output.len() >= size {
+                break;
+            }
+
+            if flags & (1 << item_index) != 0 {
+                output.push(data[position]);
+                position += 1;
+
+                continue;
+            }
+
+            if position + 1 >= data.len() {
+                return Err(invalid_data("truncated match".to_string()));
+            }
+
+            let token = u16::from_le_bytes([data[position], data[position + 1]]) as usize;
+            let distance = (token >> 4) + 1;
+            let length = (token & 0xf) + MIN_MATCH_LENGTH;
+            position += 2;
+
+            if distance > output.len() {
+                return Err(invalid_data("match outside of window".to_string()));
+            }
+
+            let start = output.len() - distance;
+
+            for copy_index in 0..length {
+                output.push(output[start + copy_index]);
+            }
+        }
+    }
+
+    Ok(output)
+}
+
+fn invalid_data(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+fn read_u8(reader: &mut impl Read) -> io::Result<u8> {
+    let mut bytes = [0; 1];
+    reader.read_exact(&mut bytes)?;
+
+    Ok(bytes[0])
+}
+
+fn read_u16(reader: &mut impl Read) -> io::Result<u16> {
+    let mut bytes = [0; 2];
+    reader.read_exact(&mut bytes)?;
+
+    Ok(u16::from_le_bytes(bytes))
+}
+
+fn read_u32(reader: &mut impl Read) -> io::Result<u32> {
+    let mut bytes = [0; 4];
+    reader.read_exact(&mut bytes)?;
+
+    Ok(u32::from_le_bytes(bytes))
+}
+
+fn read_u64(reader: &mut impl Read) -> io::Result<u64> {
+    let mut bytes = [0; 8];
+    reader.read_exact(&mut bytes)?;
+
+    Ok(u64::from_le_bytes(bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::fs;
+    use std::io::Cursor;
+    use std::path::PathBuf;
+    use std::process;
+
+    use super::*;
+
+    fn assert_round_trip(data: &[u8]) -> Vec<u8> {
+        let compressed_data = compress(data);
+        assert_eq!(decompress(&compressed_data, data.len()).unwrap(), data);
+
+        compressed_data
+    }
+
+    fn write_test_archive(
+        name: &str,
+        files: &[(String, Vec<u8>)],
+        allow_compression: bool,
+    ) -> (PathBuf, Vec<u8>) {
+        let mut archive_data = Cursor::new(vec![]);
+        write_archive(&mut archive_data, files, allow_compression).unwrap();
+
+        let archive_path =
+            env::temp_dir().join(format!("archive-test-{}-{}.pak", process::id(), name));
+
+        (archive_path, archive_data.into_inner())
+    }
+
+    #[test]
+    fn empty_input_round_trips() {
+        assert!(assert_round_trip(&[]).is_empty());
+    }
+
+    #[test]
+    fn overlapping_matches_round_trip() {
+        let repeated_byte = vec![b'a'; 100];
+        let repeated_pair = b"ab".repeat(50);
+
+        assert!(assert_round_trip(&repeated_byte).len() < repeated_byte.len());
+        assert!(assert_round_trip(&repeated_pair).len() < repeated_pair.len());
+    }
+
+    #[test]
+    fn input_larger_than_window_round_trips() {
+        let block = (0..WINDOW_SIZE + 500)
+            .map(|index| (index * 31 % 251) as u8)
+            .collect::<Vec<_>>();
+        let data = [&block[..], b"a short repeat", &block[..], b"a short repeat"].concat();
+
+        assert!(data.len() > WINDOW_SIZE);
+        assert_round_trip(&data);
+    }
+
+    #[test]
+    fn archive_round_trips() {
+        let files = vec![
+            ("empty.txt".to_string(), vec![]),
+            ("text.txt".to_string(), b"space invaders ".repeat(64)),
+            ("bytes.bin".to_string(), (0..=255).collect()),
+        ];
+        let (archive_path, archive_data) = write_test_archive("round-trip", &files, true);
+        fs::write(&archive_path, archive_data).unwrap();
+
+        let archive = Archive::open(&archive_path).unwrap();
+
+        for (path, data) in &files {
+            assert!(archive.contains(path));
+            assert_eq!(&archive.read(path).unwrap(), data);
+        }
+
+        assert!(!archive.contains("missing.txt"));
+        fs::remove_file(archive_path).unwrap();
+    }
+
+    #[test]
+    fn corrupt_entry_is_detected() {
+        let files = vec![("text.txt".to_string(), b"space invaders".to_vec())];
+        let (archive_path, mut archive_data) = write_test_archive("corrupt", &files, false);
+        archive_data[HEADER_SIZE as usize] ^= 0xff;
+        fs::write(&archive_path, archive_data).unwrap();
+
+        let error = Archive::open(&archive_path)
+            .unwrap()
+            .read("text.txt")
+            .unwrap_err();
+
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        fs::remove_file(archive_path).unwrap();
+    }
+}
@@ -1,8 +1,42 @@
 mod game;
 mod scenes;
 
+use std::env;
+use std::process;
+
+use sdl2::messagebox::{self, MessageBoxFlag};
+
+use crate::game::logging::log_error;
+use crate::scenes::level_editor_scene::LevelEditorScene;
+use crate::scenes::loading_scene::LoadingScene;
 use crate::scenes::main_menu_scene::MainMenuScene;
+use crate::scenes::space_scene::difficulty::Difficulty;
+use crate::scenes::space_scene::SpaceScene;
 
 fn main() {
-    game::play(Box::new(MainMenuScene::new()));
+    let loading_scene = Box::new(LoadingScene::new());
+
+    let result = if env::args().any(|argument| argument == "--editor") {
+        game::play(Box::new(LevelEditorScene::new()), loading_scene)
+    } else if env::args().any(|argument| argument == "--play") {
+        game::play(
+            Box::new(SpaceScene::new(Difficulty::default())),
+            loading_scene,
+        )
+    } else {
+        game::play(Box::new(MainMenuScene::new()), loading_scene)
+    };
+
+    if let Err(error) = result {
+        log_error!("{}", error);
+
+        let _ = messagebox::show_simple_message_box(
+            MessageBoxFlag::ERROR,
+            "Space Invaders",
+            &error.to_string(),
+            None,
+        );
+
+        process::exit(1);
+    }
 }
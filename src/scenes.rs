@@ -1,6 +1,10 @@
 pub mod common;
+pub mod difficulty_select_scene;
 pub mod game_over_scene;
+pub mod level_editor_scene;
+pub mod loading_scene;
 pub mod main_menu_scene;
+pub mod settings_scene;
 pub mod space_scene;
 
 pub use common::*;
@@ -1,13 +1,16 @@
+use std::borrow::Cow;
+
 use sdl2::pixels::Color as Colour;
 use sdl2::rect::{Point, Rect};
 
 use crate::game::input::InputState;
+use crate::game::renderer::{Layer, Renderer, TextCommand};
 
 pub struct Button<'a> {
     rect: Rect,
     midpoint: (u32, u32),
 
-    pub text: &'a str,
+    pub text: Cow<'a, str>,
     pub text_scale: f32,
 
     pub is_hovered: bool,
@@ -23,12 +26,19 @@ pub struct Button<'a> {
     clicked_background_colour: Colour,
 }
 
-impl Button<'_> {
-    pub fn new(x: u32, y: u32, width: u32, height: u32, text: &str, text_scale: f32) -> Button {
+impl<'a> Button<'a> {
+    pub fn new<T: Into<Cow<'a, str>>>(
+        x: u32,
+        y: u32,
+        width: u32,
+        height: u32,
+        text: T,
+        text_scale: f32,
+    ) -> Button<'a> {
         Button {
             rect: Rect::from_center(Point::new(x as i32, y as i32), width, height),
             midpoint: (x, y),
-            text,
+            text: text.into(),
             text_scale,
             is_hovered: false,
             is_clicked: false,
@@ -82,12 +92,7 @@ impl Button<'_> {
             .contains_point(Point::new(input_state.mouse_x, input_state.mouse_y))
     }
 
-    pub fn draw(
-        &self,
-        canvas: &mut sdl2::render::WindowCanvas,
-        texture_creator: &sdl2::render::TextureCreator<sdl2::video::WindowContext>,
-        font: &sdl2::ttf::Font,
-    ) {
+    pub fn draw(&self, renderer: &mut Renderer, font_index: usize) {
         let (text_colour, background_colour) = if self.is_clicked {
             (self.clicked_text_colour, self.clicked_background_colour)
         } else if self.is_hovered {
@@ -96,23 +101,16 @@ impl Button<'_> {
             (self.text_colour, self.background_colour)
         };
 
-        canvas.set_draw_color(background_colour);
-        canvas.fill_rect(self.rect).unwrap();
-
-        let text = font.render(self.text).solid(text_colour).unwrap();
-        let text_texture = texture_creator.create_texture_from_surface(text).unwrap();
-        let text_texture_data = text_texture.query();
-
-        canvas
-            .copy(
-                &text_texture,
-                None,
-                Rect::from_center(
-                    Point::new(self.midpoint.0 as i32, self.midpoint.1 as i32),
-                    (text_texture_data.width as f32 * self.text_scale) as u32,
-                    (text_texture_data.height as f32 * self.text_scale) as u32,
-                ),
+        renderer.fill_rect(Layer::Interface, self.rect, background_colour);
+        renderer.draw_text(
+            Layer::Interface,
+            TextCommand::new(
+                self.text.as_ref(),
+                font_index,
+                text_colour,
+                Point::new(self.midpoint.0 as i32, self.midpoint.1 as i32),
             )
-            .unwrap();
+            .with_scale(self.text_scale),
+        );
     }
 }
@@ -0,0 +1,136 @@
+use sdl2::mouse::MouseButton;
+use sdl2::pixels::Color as Colour;
+use sdl2::rect::Point;
+
+use super::button::Button;
+use crate::game::input::InputState;
+use crate::game::renderer::{Layer, Renderer, TextCommand};
+
+const SPINNER_BUTTON_SIZE: u32 = 40;
+const SPINNER_LABEL_WIDTH: i32 = 110;
+const SPINNER_VALUE_WIDTH: i32 = 70;
+const SPINNER_TEXT_SCALE: f32 = 0.2;
+
+pub struct Spinner<'a> {
+    position: (i32, i32),
+
+    pub label: &'a str,
+    pub value: f32,
+    pub step: f32,
+    pub range: (f32, f32),
+    pub zero_text: Option<&'a str>,
+    pub decimal_places: usize,
+
+    decrease_button: Button<'a>,
+    increase_button: Button<'a>,
+}
+
+impl<'a> Spinner<'a> {
+    pub fn new(x: i32, y: i32, label: &'a str, step: f32, range: (f32, f32)) -> Spinner<'a> {
+        let button_x = x + SPINNER_LABEL_WIDTH + SPINNER_BUTTON_SIZE as i32 / 2;
+
+        let mut spinner = Spinner {
+            position: (x, y),
+            label,
+            value: range.0,
+            step,
+            range,
+            zero_text: None,
+            decimal_places: if step.fract() == 0.0 { 0 } else { 2 },
+            decrease_button: Button::new(
+                button_x as u32,
+                y as u32,
+                SPINNER_BUTTON_SIZE,
+                SPINNER_BUTTON_SIZE,
+                "-",
+                0.3,
+            ),
+            increase_button: Button::new(
+                (button_x + SPINNER_BUTTON_SIZE as i32 + SPINNER_VALUE_WIDTH) as u32,
+                y as u32,
+                SPINNER_BUTTON_SIZE,
+                SPINNER_BUTTON_SIZE,
+                "+",
+                0.3,
+            ),
+        };
+
+        for button in &mut [&mut spinner.decrease_button, &mut spinner.increase_button] {
+            button.set_colours(
+                Colour::BLACK,
+                Colour::BLACK,
+                Colour::BLACK,
+                Colour::GREY,
+                Colour::YELLOW,
+                Colour::GREEN,
+            );
+        }
+
+        spinner
+    }
+
+    pub fn process_input(&mut self, input_state: &InputState) -> bool {
+        let mut step_direction = 0.0;
+
+        for (button, direction) in &mut [
+            (&mut self.decrease_button, -1.0),
+            (&mut self.increase_button, 1.0),
+        ] {
+            button.is_hovered = button.is_mouse_over(input_state);
+            button.is_clicked =
+                button.is_hovered && input_state.is_mouse_button_down(MouseButton::Left);
+
+            if button.is_clicked {
+                step_direction = *direction;
+            }
+        }
+
+        if step_direction == 0.0 {
+            return false;
+        }
+
+        let value = f32::min(
+            f32::max(self.value + self.step * step_direction, self.range.0),
+            self.range.1,
+        );
+        let has_changed = value != self.value;
+        self.value = value;
+
+        has_changed
+    }
+
+    pub fn draw(&self, renderer: &mut Renderer, font_index: usize) {
+        let value_string = match self.zero_text {
+            Some(zero_text) if self.value == 0.0 => zero_text.to_string(),
+            _ => format!("{:.*}", self.decimal_places, self.value),
+        };
+
+        for (text, center_x) in [
+            (
+                self.label.to_string(),
+                self.position.0 + SPINNER_LABEL_WIDTH / 2,
+            ),
+            (
+                value_string,
+                self.position.0
+                    + SPINNER_LABEL_WIDTH
+                    + SPINNER_BUTTON_SIZE as i32
+                    + SPINNER_VALUE_WIDTH / 2,
+            ),
+        ] {
+            renderer.draw_text(
+                Layer::Interface,
+                TextCommand::new(
+                    text,
+                    font_index,
+                    Colour::WHITE,
+                    Point::new(center_x, self.position.1),
+                )
+                .with_scale(SPINNER_TEXT_SCALE),
+            );
+        }
+
+        self.decrease_button.draw(renderer, font_index);
+        self.increase_button.draw(renderer, font_index);
+    }
+}
@@ -0,0 +1,391 @@
+use std::collections::{HashMap, VecDeque};
+
+use sdl2::{
+    keyboard::Scancode,
+    mixer::{Channel, Chunk, Music},
+    mouse::MouseButton,
+    pixels::Color as Colour,
+    rect::Point,
+    render::{Texture, WindowCanvas},
+    ttf::Font,
+};
+
+use super::button::Button;
+use super::spinner::Spinner;
+use crate::game::input::InputState;
+use crate::game::logging::log_warn;
+use crate::game::renderer::{Layer, Renderer, TextCommand};
+use crate::game::resources::{self, ResourcePaths};
+use crate::game::scene::Scene;
+use crate::game::vfs;
+use crate::scenes::main_menu_scene::MainMenuScene;
+use crate::scenes::space_scene::difficulty::{self, Difficulty};
+use crate::scenes::space_scene::SpaceScene;
+
+const BACKGROUND_COLOUR: Colour = Colour::RGB(10, 10, 10);
+
+const DIFFICULTY_BUTTON_Y: u32 = 260;
+const CUSTOM_SPINNER_Y: i32 = 440;
+const CUSTOM_SPINNER_SPACING: i32 = 60;
+
+pub struct DifficultySelectScene<'a> {
+    font_index: usize,
+    buttons: Vec<Button<'a>>,
+    spinners: Vec<Spinner<'a>>,
+
+    difficulties: Vec<Difficulty>,
+    custom_difficulty: Difficulty,
+
+    is_done: bool,
+
+    button_hover_sound: Option<Chunk>,
+    button_select_sound: Option<Chunk>,
+
+    music: Option<Music<'a>>,
+}
+
+impl<'a> DifficultySelectScene<'a> {
+    pub fn new() -> DifficultySelectScene<'a> {
+        DifficultySelectScene {
+            font_index: 0,
+            buttons: vec![],
+            spinners: vec![],
+            difficulties: vec![],
+            custom_difficulty: Difficulty::default(),
+            is_done: false,
+            button_hover_sound: None,
+            button_select_sound: None,
+            music: None,
+        }
+    }
+
+    fn apply_spinners(&mut self) {
+        let custom_difficulty = &mut self.custom_difficulty;
+
+        custom_difficulty.player_lives = self.spinners[0].value as u32;
+        custom_difficulty.alien_velocity_multiplier = self.spinners[1].value;
+        custom_difficulty.alien_fire_interval_multiplier = self.spinners[2].value;
+        custom_difficulty.alien_bullet_velocity_multiplier = self.spinners[3].value;
+        custom_difficulty.power_up_drop_multiplier = self.spinners[4].value;
+        custom_difficulty.score_multiplier = self.spinners[5].value;
+    }
+}
+
+impl Scene for DifficultySelectScene<'_> {
+    fn is_done(&self) -> bool {
+        self.is_done
+    }
+
+    fn on_load(
+        &mut self,
+        _sdl_context: &sdl2::Sdl,
+        _canvas: &WindowCanvas,
+        _previous_scene_payload: Option<i32>,
+    ) -> ResourcePaths {
+        let mut fonts = vec![];
+
+        for (current_index, font_filepath) in resources::directory_paths("assets/fonts")
+            .into_iter()
+            .enumerate()
+        {
+            let font_filepath_string = resources::file_name(&font_filepath);
+
+            if font_filepath_string == "Recursive.ttf" {
+                self.font_index = current_index;
+            }
+
+            fonts.push(font_filepath);
+        }
+
+        let sounds = resources::directory_paths("assets/sounds/effects/menu");
+
+        let difficulty_settings = difficulty::read_difficulty_file().unwrap();
+        self.difficulties = difficulty_settings.presets;
+        self.custom_difficulty = difficulty_settings.custom;
+
+        self.music = Some(
+            Music::from_static_bytes(
+                vfs::read_static("assets/sounds/music/Chill Wave.mp3").unwrap(),
+            )
+            .unwrap(),
+        );
+
+        ResourcePaths {
+            textures: vec![],
+            fonts,
+            sounds,
+        }
+    }
+
+    fn on_sounds_loaded(&mut self, mut sounds: HashMap<String, Chunk>) {
+        self.button_hover_sound = sounds.remove("button_hover.wav");
+        self.button_select_sound = sounds.remove("button_select.wav");
+    }
+
+    fn on_late_load(&mut self, canvas: &WindowCanvas, _textures: &[Texture], _fonts: &[Font]) {
+        let button_spacing = canvas.viewport().width() / (self.difficulties.len() as u32 + 1);
+
+        for (difficulty_index, difficulty) in self
+            .difficulties
+            .iter()
+            .chain(std::iter::once(&self.custom_difficulty))
+            .enumerate()
+        {
+            self.buttons.push(Button::new(
+                button_spacing / 2 + button_spacing * difficulty_index as u32,
+                DIFFICULTY_BUTTON_Y,
+                button_spacing - 40,
+                100,
+                difficulty.name.clone(),
+                0.4,
+            ));
+            self.buttons.last_mut().unwrap().set_colours(
+                Colour::BLACK,
+                Colour::BLACK,
+                Colour::BLACK,
+                Colour::YELLOW,
+                Colour::GREEN,
+                Colour::WHITE,
+            );
+        }
+
+        self.buttons.push(Button::new(
+            canvas.viewport().width() / 2,
+            canvas.viewport().height() - 80,
+            300,
+            90,
+            "Back",
+            0.4,
+        ));
+        self.buttons.last_mut().unwrap().set_colours(
+            Colour::BLACK,
+            Colour::BLACK,
+            Colour::BLACK,
+            Colour::YELLOW,
+            Colour::GREEN,
+            Colour::RED,
+        );
+
+        let custom_difficulty = &self.custom_difficulty;
+
+        for (spinner_index, (label, step, range, value)) in [
+            (
+                "Lives",
+                1.0,
+                (1.0, 9.0),
+                custom_difficulty.player_lives as f32,
+            ),
+            (
+                "Alien speed",
+                0.05,
+                (0.5, 2.0),
+                custom_difficulty.alien_velocity_multiplier,
+            ),
+            (
+                "Fire interval",
+                0.05,
+                (0.25, 3.0),
+                custom_difficulty.alien_fire_interval_multiplier,
+            ),
+            (
+                "Bullet speed",
+                0.05,
+                (0.5, 2.0),
+                custom_difficulty.alien_bullet_velocity_multiplier,
+            ),
+            (
+                "Drop chance",
+                0.25,
+                (0.0, 3.0),
+                custom_difficulty.power_up_drop_multiplier,
+            ),
+            (
+                "Score",
+                0.05,
+                (0.1, 3.0),
+                custom_difficulty.score_multiplier,
+            ),
+        ]
+        .iter()
+        .enumerate()
+        {
+            let mut spinner = Spinner::new(
+                canvas.viewport().width() as i32 / 2 - 300 + (spinner_index as i32 % 2) * 320,
+                CUSTOM_SPINNER_Y + (spinner_index as i32 / 2) * CUSTOM_SPINNER_SPACING,
+                label,
+                *step,
+                *range,
+            );
+            spinner.value = *value;
+
+            self.spinners.push(spinner);
+        }
+
+        self.music.as_ref().unwrap().play(-1).unwrap();
+    }
+
+    fn preload(&self) -> ResourcePaths {
+        SpaceScene::resource_paths()
+    }
+
+    fn on_unload(&mut self, _sdl_context: &sdl2::Sdl) -> Option<i32> {
+        Music::halt();
+
+        None
+    }
+
+    fn process_input(&mut self, input_state: &InputState) {
+        if input_state.is_key_down(Scancode::Escape) {
+            self.buttons.last_mut().unwrap().is_clicked = true;
+
+            return;
+        }
+
+        for button in &mut self.buttons {
+            button.is_hovered = false;
+            button.is_clicked = false;
+
+            if button.is_mouse_over(input_state) {
+                button.is_hovered = true;
+
+                if input_state.is_mouse_button_down(MouseButton::Left) {
+                    button.is_clicked = true;
+                }
+            } else if button.played_enter_sound {
+                button.played_enter_sound = false;
+            }
+        }
+
+        let mut has_spinner_changed = false;
+
+        for spinner in &mut self.spinners {
+            has_spinner_changed |= spinner.process_input(input_state);
+        }
+
+        if has_spinner_changed {
+            self.apply_spinners();
+        }
+    }
+
+    fn update(
+        &mut self,
+        _delta_time: f32,
+        scene_queue: &mut VecDeque<Box<dyn Scene>>,
+        _canvas: &WindowCanvas,
+        sound_channel: &Channel,
+    ) {
+        for button in &mut self.buttons {
+            if button.is_hovered && !button.played_enter_sound {
+                sound_channel
+                    .play(self.button_hover_sound.as_ref().unwrap(), 0)
+                    .unwrap();
+                button.played_enter_sound = true;
+            }
+        }
+
+        let clicked_button_index = match self.buttons.iter().position(|button| button.is_clicked) {
+            Some(button_index) => button_index,
+            None => return,
+        };
+
+        self.is_done = true;
+
+        sound_channel
+            .play(self.button_select_sound.as_ref().unwrap(), 0)
+            .unwrap();
+
+        if clicked_button_index == self.buttons.len() - 1 {
+            scene_queue.push_back(Box::new(MainMenuScene::new()));
+
+            return;
+        }
+
+        let difficulty = match self.difficulties.get(clicked_button_index) {
+            Some(difficulty) => difficulty.clone(),
+            None => {
+                if let Err(error) = difficulty::write_custom_difficulty(&self.custom_difficulty) {
+                    log_warn!("Could not save custom difficulty: {}", error);
+                }
+
+                self.custom_difficulty.clone()
+            }
+        };
+
+        scene_queue.push_back(Box::new(SpaceScene::new(difficulty)));
+    }
+
+    fn draw(&mut self, renderer: &mut Renderer, _fonts: &[Font]) {
+        renderer.clear(BACKGROUND_COLOUR);
+
+        let viewport_width = renderer.viewport().width();
+
+        draw_text(
+            renderer,
+            self.font_index,
+            "Select Difficulty",
+            Colour::WHITE,
+            Point::new(viewport_width as i32 / 2, 110),
+            0.6,
+        );
+
+        let button_spacing = viewport_width / (self.difficulties.len() as u32 + 1);
+
+        for (difficulty_index, difficulty) in self
+            .difficulties
+            .iter()
+            .chain(std::iter::once(&self.custom_difficulty))
+            .enumerate()
+        {
+            let summary = format!(
+                "{} lives, x{:.2} score",
+                difficulty.player_lives, difficulty.score_multiplier
+            );
+
+            draw_text(
+                renderer,
+                self.font_index,
+                &summary,
+                Colour::GREY,
+                Point::new(
+                    (button_spacing / 2 + button_spacing * difficulty_index as u32) as i32,
+                    DIFFICULTY_BUTTON_Y as i32 + 75,
+                ),
+                0.18,
+            );
+        }
+
+        draw_text(
+            renderer,
+            self.font_index,
+            "Custom settings",
+            Colour::CYAN,
+            Point::new(
+                viewport_width as i32 / 2,
+                CUSTOM_SPINNER_Y - CUSTOM_SPINNER_SPACING,
+            ),
+            0.25,
+        );
+
+        for spinner in &self.spinners {
+            spinner.draw(renderer, self.font_index);
+        }
+
+        for button in &self.buttons {
+            button.draw(renderer, self.font_index);
+        }
+    }
+}
+
+fn draw_text(
+    renderer: &mut Renderer,
+    font_index: usize,
+    text: &str,
+    colour: Colour,
+    center: Point,
+    text_scale: f32,
+) {
+    renderer.draw_text(
+        Layer::Interface,
+        TextCommand::new(text, font_index, colour, center).with_scale(text_scale),
+    );
+}
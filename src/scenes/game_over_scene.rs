@@ -1,26 +1,38 @@
-use std::collections::VecDeque;
-use std::fs;
+mod high_score;
+
+use std::collections::{HashMap, VecDeque};
 
 use sdl2::{
     keyboard::Scancode,
     mixer::{Channel, Chunk},
     mouse::MouseButton,
     pixels::Color as Colour,
-    rect::{Point, Rect},
-    render::{Texture, TextureCreator, WindowCanvas},
+    rect::Point,
+    render::{Texture, WindowCanvas},
     ttf::Font,
 };
 
+use self::high_score::{HighScore, HighScoreTable};
 use super::button::Button;
 use crate::game::input::InputState;
+use crate::game::logging::log_warn;
+use crate::game::renderer::{Layer, Renderer, TextCommand};
+use crate::game::resources::{self, ResourcePaths};
 use crate::game::scene::Scene;
+use crate::game::transition::{Transition, TransitionEffect};
 use crate::scenes::main_menu_scene::MainMenuScene;
+use crate::scenes::space_scene::difficulty::Difficulty;
 use crate::scenes::space_scene::SpaceScene;
 
 const BACKGROUND_COLOUR: Colour = Colour::RGB(10, 10, 10);
+const TRANSITION_TIME: f32 = 1.0;
 
 pub struct GameOverScene<'a> {
     player_score: u32,
+    final_level: u32,
+    difficulty: Difficulty,
+    high_scores: HighScoreTable,
+    high_score_rank: Option<usize>,
 
     buttons: Vec<Button<'a>>,
     button_hover_sound: Option<Chunk>,
@@ -31,9 +43,13 @@ pub struct GameOverScene<'a> {
 }
 
 impl<'a> GameOverScene<'a> {
-    pub fn new() -> GameOverScene<'a> {
+    pub fn new(difficulty: Difficulty, final_level: u32) -> GameOverScene<'a> {
         GameOverScene {
             player_score: 0,
+            final_level,
+            difficulty,
+            high_scores: HighScoreTable::default(),
+            high_score_rank: None,
             buttons: vec![],
             button_hover_sound: None,
             button_select_sound: None,
@@ -42,63 +58,82 @@ impl<'a> GameOverScene<'a> {
         }
     }
 
-    fn draw_header(
-        &self,
-        canvas: &mut WindowCanvas,
-        texture_creator: &TextureCreator<sdl2::video::WindowContext>,
-        font: &Font,
-    ) {
+    fn draw_header(&self, renderer: &mut Renderer) {
         const TEXT_SCALE: f32 = 0.75;
 
-        let title_text = font.render("Game Over!").blended(Colour::WHITE).unwrap();
-        let title_texture = texture_creator
-            .create_texture_from_surface(title_text)
-            .unwrap();
-
-        let title_texture_data = title_texture.query();
-
-        canvas
-            .copy(
-                &title_texture,
-                None,
-                Rect::from_center(
-                    Point::new(canvas.viewport().width() as i32 / 2, 128),
-                    (title_texture_data.width as f32 * TEXT_SCALE) as u32,
-                    (title_texture_data.height as f32 * TEXT_SCALE) as u32,
-                ),
+        renderer.draw_text(
+            Layer::Interface,
+            TextCommand::new(
+                "Game Over!",
+                self.font_index,
+                Colour::WHITE,
+                Point::new(renderer.viewport().width() as i32 / 2, 128),
             )
-            .unwrap();
+            .with_scale(TEXT_SCALE),
+        );
     }
 
-    fn draw_score_text(
-        &self,
-        canvas: &mut WindowCanvas,
-        texture_creator: &TextureCreator<sdl2::video::WindowContext>,
-        font: &Font,
-    ) {
+    fn draw_score_text(&self, renderer: &mut Renderer) {
         const TEXT_SCALE: f32 = 0.25;
 
-        let title_text = font
-            .render(format!("Your final score was {}.", self.player_score).as_str())
-            .blended(Colour::YELLOW)
-            .unwrap();
-        let title_texture = texture_creator
-            .create_texture_from_surface(title_text)
-            .unwrap();
-
-        let title_texture_data = title_texture.query();
-
-        canvas
-            .copy(
-                &title_texture,
-                None,
-                Rect::from_center(
-                    Point::new(canvas.viewport().width() as i32 / 2, 256),
-                    (title_texture_data.width as f32 * TEXT_SCALE) as u32,
-                    (title_texture_data.height as f32 * TEXT_SCALE) as u32,
-                ),
+        renderer.draw_text(
+            Layer::Interface,
+            TextCommand::new(
+                format!("Your final score was {}.", self.player_score),
+                self.font_index,
+                Colour::YELLOW,
+                Point::new(renderer.viewport().width() as i32 / 2, 256),
             )
-            .unwrap();
+            .with_scale(TEXT_SCALE),
+        );
+    }
+
+    fn draw_high_scores(&self, renderer: &mut Renderer) {
+        const TEXT_SCALE: f32 = 0.2;
+        const FIRST_LINE_Y: i32 = 320;
+        const LINE_HEIGHT: i32 = 30;
+
+        let column_x = renderer.viewport().width() as i32 / 6 * 5;
+        let mut lines = vec![(
+            format!("{} High Scores", self.difficulty.name),
+            Colour::WHITE,
+        )];
+
+        for (rank, high_score) in self
+            .high_scores
+            .scores(&self.difficulty.name)
+            .iter()
+            .enumerate()
+        {
+            let colour = if Some(rank) == self.high_score_rank {
+                Colour::YELLOW
+            } else {
+                Colour::GREY
+            };
+
+            lines.push((
+                format!(
+                    "{}. {} (level {})",
+                    rank + 1,
+                    high_score.score,
+                    high_score.level
+                ),
+                colour,
+            ));
+        }
+
+        for (line_index, (line, colour)) in lines.into_iter().enumerate() {
+            renderer.draw_text(
+                Layer::Interface,
+                TextCommand::new(
+                    line,
+                    self.font_index,
+                    colour,
+                    Point::new(column_x, FIRST_LINE_Y + line_index as i32 * LINE_HEIGHT),
+                )
+                .with_scale(TEXT_SCALE),
+            );
+        }
     }
 }
 
@@ -107,56 +142,71 @@ impl Scene for GameOverScene<'_> {
         self.is_done
     }
 
+    fn transition(&self) -> Transition {
+        Transition::new(TransitionEffect::Dissolve, TRANSITION_TIME)
+    }
+
     fn on_load(
         &mut self,
         _sdl_context: &sdl2::Sdl,
         _canvas: &WindowCanvas,
         previous_scene_payload: Option<i32>,
-    ) -> (Vec<String>, Vec<String>) {
+    ) -> ResourcePaths {
         self.player_score = previous_scene_payload.unwrap_or(0) as u32;
+
+        self.high_scores = high_score::read_high_scores_file().unwrap_or_else(|error| {
+            log_warn!("Could not read high scores: {}", error);
+
+            HighScoreTable::default()
+        });
+
+        if self.player_score > 0 {
+            self.high_score_rank = self.high_scores.insert(
+                &self.difficulty.name,
+                HighScore {
+                    score: self.player_score,
+                    level: self.final_level,
+                },
+            );
+
+            if self.high_score_rank.is_some() {
+                if let Err(error) = high_score::write_high_scores_file(&self.high_scores) {
+                    log_warn!("Could not save high scores: {}", error);
+                }
+            }
+        }
+
         let mut fonts = vec![];
 
-        for (current_index, font_file) in fs::read_dir("assets/fonts").unwrap().enumerate() {
-            let font_file = font_file.unwrap();
-            let font_filepath = font_file.path();
-            let font_filepath_string = font_filepath
-                .file_name()
-                .unwrap()
-                .to_str()
-                .unwrap()
-                .to_owned();
+        for (current_index, font_filepath) in resources::directory_paths("assets/fonts")
+            .into_iter()
+            .enumerate()
+        {
+            let font_filepath_string = resources::file_name(&font_filepath);
 
             if font_filepath_string == "Recursive.ttf" {
                 self.font_index = current_index;
             }
 
-            fonts.push(font_filepath.to_str().unwrap().to_owned());
+            fonts.push(font_filepath);
         }
 
-        for sound_file in fs::read_dir("assets/sounds/effects/menu").unwrap() {
-            let sound_file = sound_file.unwrap();
-            let sound_filepath = sound_file.path();
-            let sound_filepath_string = sound_filepath
-                .file_name()
-                .unwrap()
-                .to_str()
-                .unwrap()
-                .to_owned();
-
-            let loaded_sound_chunk = Some(Chunk::from_file(sound_filepath).unwrap());
-
-            match sound_filepath_string.as_ref() {
-                "button_hover.wav" => {
-                    self.button_hover_sound = loaded_sound_chunk;
-                }
-                "button_select.wav" => {
-                    self.button_select_sound = loaded_sound_chunk;
-                }
-                _ => (),
-            }
+        let sounds = resources::directory_paths("assets/sounds/effects/menu");
+
+        ResourcePaths {
+            textures: vec![],
+            fonts,
+            sounds,
         }
+    }
 
-        (vec![], fonts)
+    fn on_sounds_loaded(&mut self, mut sounds: HashMap<String, Chunk>) {
+        self.button_hover_sound = sounds.remove("button_hover.wav");
+        self.button_select_sound = sounds.remove("button_select.wav");
+    }
+
+    fn preload(&self) -> ResourcePaths {
+        SpaceScene::resource_paths()
     }
 
     fn on_late_load(&mut self, canvas: &WindowCanvas, _textures: &[Texture], _fonts: &[Font]) {
@@ -240,7 +290,7 @@ impl Scene for GameOverScene<'_> {
 
         if self.buttons.first().unwrap().is_clicked {
             self.is_done = true;
-            scene_queue.push_back(Box::new(SpaceScene::new()));
+            scene_queue.push_back(Box::new(SpaceScene::new(self.difficulty.clone())));
 
             sound_channel
                 .play(self.button_select_sound.as_ref().unwrap(), 0)
@@ -257,21 +307,15 @@ impl Scene for GameOverScene<'_> {
         }
     }
 
-    fn draw(
-        &mut self,
-        canvas: &mut WindowCanvas,
-        texture_creator: &TextureCreator<sdl2::video::WindowContext>,
-        _textures: &[Texture],
-        fonts: &[Font],
-    ) {
-        canvas.set_draw_color(BACKGROUND_COLOUR);
-        canvas.clear();
+    fn draw(&mut self, renderer: &mut Renderer, _fonts: &[Font]) {
+        renderer.clear(BACKGROUND_COLOUR);
 
-        self.draw_header(canvas, texture_creator, &fonts[self.font_index]);
-        self.draw_score_text(canvas, texture_creator, &fonts[self.font_index]);
+        self.draw_header(renderer);
+        self.draw_score_text(renderer);
+        self.draw_high_scores(renderer);
 
         for button in &self.buttons {
-            button.draw(canvas, texture_creator, &fonts[self.font_index]);
+            button.draw(renderer, self.font_index);
         }
     }
 }
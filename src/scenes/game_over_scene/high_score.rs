@@ -0,0 +1,104 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs;
+
+use crate::game;
+
+const HIGH_SCORES_FILE_NAME: &str = "high_scores.json";
+const HIGH_SCORES_PER_DIFFICULTY: usize = 10;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct HighScore {
+    pub score: u32,
+    pub level: u32,
+}
+
+#[derive(Debug, Default)]
+pub struct HighScoreTable {
+    scores: HashMap<String, Vec<HighScore>>,
+}
+
+impl HighScoreTable {
+    pub fn scores(&self, difficulty_name: &str) -> &[HighScore] {
+        self.scores
+            .get(difficulty_name)
+            .map_or(&[], |scores| &scores[..])
+    }
+
+    pub fn insert(&mut self, difficulty_name: &str, high_score: HighScore) -> Option<usize> {
+        let scores = self.scores.entry(difficulty_name.to_string()).or_default();
+        let rank = scores
+            .iter()
+            .position(|existing_score| high_score.score > existing_score.score)
+            .unwrap_or(scores.len());
+
+        if rank >= HIGH_SCORES_PER_DIFFICULTY {
+            return None;
+        }
+
+        scores.insert(rank, high_score);
+        scores.truncate(HIGH_SCORES_PER_DIFFICULTY);
+
+        Some(rank)
+    }
+}
+
+pub fn read_high_scores_file() -> Result<HighScoreTable, Box<dyn Error>> {
+    let high_scores_filepath = game::saves_directory().join(HIGH_SCORES_FILE_NAME);
+
+    if !high_scores_filepath.exists() {
+        return Ok(HighScoreTable::default());
+    }
+
+    let json_string = fs::read_to_string(high_scores_filepath)?;
+    let high_score_data: serde_json::Value = serde_json::from_str(&json_string[..])?;
+
+    let mut table = HighScoreTable::default();
+
+    for (difficulty_name, scores_data) in high_score_data
+        .as_object()
+        .ok_or("High scores file must contain an object.")?
+    {
+        let scores = scores_data
+            .as_array()
+            .unwrap_or(&vec![])
+            .iter()
+            .map(|score_data| HighScore {
+                score: score_data["score"].as_u64().unwrap_or(0) as u32,
+                level: score_data["level"].as_u64().unwrap_or(1) as u32,
+            })
+            .collect();
+
+        table.scores.insert(difficulty_name.clone(), scores);
+    }
+
+    Ok(table)
+}
+
+pub fn write_high_scores_file(table: &HighScoreTable) -> Result<(), Box<dyn Error>> {
+    let mut high_score_data = serde_json::Map::new();
+
+    for (difficulty_name, scores) in &table.scores {
+        high_score_data.insert(
+            difficulty_name.clone(),
+            scores
+                .iter()
+                .map(|high_score| {
+                    serde_json::json!({
+                        "score": high_score.score,
+                        "level": high_score.level,
+                    })
+                })
+                .collect(),
+        );
+    }
+
+    let saves_directory = game::saves_directory();
+    fs::create_dir_all(&saves_directory)?;
+    fs::write(
+        saves_directory.join(HIGH_SCORES_FILE_NAME),
+        serde_json::to_string_pretty(&serde_json::Value::Object(high_score_data))?,
+    )?;
+
+    Ok(())
+}
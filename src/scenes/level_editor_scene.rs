@@ -0,0 +1,790 @@
+mod history;
+
+use std::collections::{HashMap, VecDeque};
+
+use sdl2::{
+    keyboard::Scancode,
+    mixer::{Channel, Chunk},
+    mouse::MouseButton,
+    pixels::Color as Colour,
+    rect::{Point, Rect},
+    render::{Texture, WindowCanvas},
+    ttf::Font,
+};
+
+use self::history::EditHistory;
+use super::button::Button;
+use super::spinner::Spinner;
+use crate::game::animation::{self, SpriteSheet, SpriteSheetDefinition};
+use crate::game::input::InputState;
+use crate::game::renderer::{Layer, Renderer, SpriteCommand, TextCommand};
+use crate::game::resources::{self, ResourcePaths};
+use crate::game::scene::Scene;
+use crate::game::vfs;
+use crate::scenes::main_menu_scene::MainMenuScene;
+use crate::scenes::space_scene::alien::alien_type::{self, AlienTypeTable};
+use crate::scenes::space_scene::bunker::{Bunker, BUNKER_BOTTOM_OFFSET};
+use crate::scenes::space_scene::level::{self, Level, LEVELS_DIRECTORY, LEVEL_FILE_EXTENSION};
+use crate::scenes::space_scene::SpaceScene;
+
+const BACKGROUND_COLOUR: Colour = Colour::RGB(10, 10, 10);
+const GRID_COLOUR: Colour = Colour::RGB(60, 60, 60);
+const BUNKER_LANE_COLOUR: Colour = Colour::RGB(20, 40, 20);
+
+const ANIMATIONS_FILE_NAME: &str = "config/animations.json";
+
+const GRID_ROW_COUNT: usize = 6;
+const HISTORY_LENGTH: usize = 100;
+
+const STATUS_Y: i32 = 570;
+const SPINNER_Y: i32 = 705;
+const TOOLBAR_Y: i32 = 765;
+const PALETTE_SWATCH_SIZE: u32 = 48;
+
+const BUTTON_LABELS: [&str; 8] = [
+    "New", "Load", "Save", "Undo", "Redo", "Boss", "Play", "Back",
+];
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum EditorAction {
+    New,
+    Load,
+    Save,
+    Undo,
+    Redo,
+    ToggleBoss,
+    Play,
+    Back,
+}
+
+const BUTTON_ACTIONS: [EditorAction; 8] = [
+    EditorAction::New,
+    EditorAction::Load,
+    EditorAction::Save,
+    EditorAction::Undo,
+    EditorAction::Redo,
+    EditorAction::ToggleBoss,
+    EditorAction::Play,
+    EditorAction::Back,
+];
+
+pub struct LevelEditorScene<'a> {
+    is_done: bool,
+    font_index: usize,
+
+    level: Level,
+    level_filepath: String,
+    next_load_index: usize,
+    history: EditHistory<Level>,
+    status_message: String,
+    pending_action: Option<EditorAction>,
+
+    alien_types: AlienTypeTable,
+    selected_type_index: usize,
+    grid_size: (usize, usize),
+    viewport_size: (u32, u32),
+    hovered_cell: Option<(usize, usize)>,
+    is_painting: bool,
+
+    texture_names: Vec<String>,
+    animation_definitions: HashMap<String, SpriteSheetDefinition>,
+    sprite_sheets: Vec<SpriteSheet>,
+
+    spinners: Vec<Spinner<'a>>,
+    buttons: Vec<Button<'a>>,
+    button_hover_sound: Option<Chunk>,
+    button_select_sound: Option<Chunk>,
+}
+
+impl<'a> LevelEditorScene<'a> {
+    pub fn new() -> LevelEditorScene<'a> {
+        LevelEditorScene::with_level(Level::new("Custom Level"), &new_level_filepath())
+    }
+
+    pub fn with_level(level: Level, level_filepath: &str) -> LevelEditorScene<'a> {
+        LevelEditorScene {
+            is_done: false,
+            font_index: 0,
+            level,
+            level_filepath: level_filepath.to_string(),
+            next_load_index: 0,
+            history: EditHistory::new(HISTORY_LENGTH),
+            status_message: String::from(
+                "Left click places the selected alien or a bunker, right click removes it.",
+            ),
+            pending_action: None,
+            alien_types: AlienTypeTable {
+                types: vec![],
+                row_types: vec![],
+            },
+            selected_type_index: 0,
+            grid_size: (0, 0),
+            viewport_size: (0, 0),
+            hovered_cell: None,
+            is_painting: false,
+            texture_names: vec![],
+            animation_definitions: HashMap::new(),
+            sprite_sheets: vec![],
+            spinners: vec![],
+            buttons: vec![],
+            button_hover_sound: None,
+            button_select_sound: None,
+        }
+    }
+
+    fn set_level(&mut self, level: Level, level_filepath: &str) {
+        let (cell_width, _) = self.alien_types.cell_size();
+
+        self.level = level;
+        self.level_filepath = level_filepath.to_string();
+        self.grid_size = (
+            usize::max(
+                level::default_column_count(cell_width, self.viewport_size.0),
+                self.level.column_count(),
+            ),
+            usize::max(GRID_ROW_COUNT, self.level.formation.len()),
+        );
+
+        let (column_count, row_count) = self.grid_size;
+        let column_offset = (column_count - self.level.column_count()) / 2;
+        let mut formation = vec![vec![None; column_count]; row_count];
+
+        for (row, cells) in self.level.formation.iter().enumerate() {
+            for (column, cell) in cells.iter().enumerate() {
+                formation[row][column + column_offset] = *cell;
+            }
+        }
+
+        self.level.formation = formation;
+        self.history.clear();
+        self.sync_widgets();
+    }
+
+    fn saved_level(&self) -> Level {
+        let mut level = self.level.clone();
+
+        while level
+            .formation
+            .last()
+            .is_some_and(|row| row.iter().all(Option::is_none))
+        {
+            level.formation.pop();
+        }
+
+        level
+    }
+
+    fn validate_level(&mut self) -> bool {
+        let is_valid =
+            self.level.is_boss_level || self.level.formation.iter().flatten().any(Option::is_some);
+
+        if !is_valid {
+            self.status_message =
+                String::from("Place at least one alien or make this a boss level.");
+        }
+
+        is_valid
+    }
+
+    fn sync_widgets(&mut self) {
+        let values = [
+            self.level.velocity,
+            self.level.velocity_increment,
+            self.level.dropdown_distance,
+            self.level.fire_interval.unwrap_or(0.0),
+        ];
+
+        for (spinner, value) in self.spinners.iter_mut().zip(values.iter()) {
+            spinner.value = *value;
+        }
+
+        let boss_button_index = BUTTON_ACTIONS
+            .iter()
+            .position(|action| *action == EditorAction::ToggleBoss)
+            .unwrap();
+
+        if let Some(boss_button) = self.buttons.get_mut(boss_button_index) {
+            let background_colour = if self.level.is_boss_level {
+                Colour::RED
+            } else {
+                Colour::YELLOW
+            };
+
+            boss_button.set_colours(
+                Colour::BLACK,
+                Colour::BLACK,
+                Colour::BLACK,
+                background_colour,
+                Colour::GREEN,
+                Colour::WHITE,
+            );
+        }
+    }
+
+    fn apply_spinners(&mut self) {
+        self.level.velocity = self.spinners[0].value;
+        self.level.velocity_increment = self.spinners[1].value;
+        self.level.dropdown_distance = self.spinners[2].value;
+        self.level.fire_interval = if self.spinners[3].value > 0.0 {
+            Some(self.spinners[3].value)
+        } else {
+            None
+        };
+    }
+
+    fn cell_rect(&self, row: usize, column: usize) -> Rect {
+        let cell_size = self.alien_types.cell_size();
+        let (cell_x, cell_y) =
+            self.level
+                .alien_position(row, column, cell_size, self.viewport_size.0);
+
+        Rect::from_center(
+            Point::new(cell_x as i32, cell_y as i32),
+            cell_size.0,
+            cell_size.1,
+        )
+    }
+
+    fn bunker_lane_rect(&self) -> Rect {
+        let (_, bunker_height) = Bunker::size();
+
+        Rect::from_center(
+            Point::new(
+                self.viewport_size.0 as i32 / 2,
+                (self.viewport_size.1 - BUNKER_BOTTOM_OFFSET) as i32,
+            ),
+            self.viewport_size.0,
+            bunker_height + 16,
+        )
+    }
+
+    fn palette_rect(&self, type_index: usize) -> Rect {
+        Rect::from_center(
+            Point::new(
+                20 + PALETTE_SWATCH_SIZE as i32 / 2
+                    + type_index as i32 * (PALETTE_SWATCH_SIZE as i32 + 8),
+                TOOLBAR_Y,
+            ),
+            PALETTE_SWATCH_SIZE,
+            PALETTE_SWATCH_SIZE,
+        )
+    }
+
+    fn process_grid_input(&mut self, input_state: &InputState) {
+        let mouse_point = Point::new(input_state.mouse_x, input_state.mouse_y);
+        let (column_count, row_count) = self.grid_size;
+
+        self.hovered_cell = (0..row_count)
+            .flat_map(|row| (0..column_count).map(move |column| (row, column)))
+            .find(|(row, column)| self.cell_rect(*row, *column).contains_point(mouse_point));
+
+        let is_left_pressed = input_state.is_mouse_button_pressed(MouseButton::Left);
+        let is_right_pressed = input_state.is_mouse_button_pressed(MouseButton::Right);
+
+        if !is_left_pressed && !is_right_pressed {
+            if self.is_painting {
+                self.is_painting = false;
+                self.history.commit(&self.level);
+            }
+
+            return;
+        }
+
+        if let Some((row, column)) = self.hovered_cell {
+            if input_state.is_any_mouse_button_down(&[MouseButton::Left, MouseButton::Right]) {
+                self.is_painting = true;
+                self.history.begin(&self.level);
+            }
+
+            if self.is_painting {
+                self.level.formation[row][column] = if is_left_pressed {
+                    Some(self.selected_type_index)
+                } else {
+                    None
+                };
+            }
+        }
+
+        let bunker_lane_rect = self.bunker_lane_rect();
+
+        if bunker_lane_rect.contains_point(mouse_point)
+            && input_state.is_any_mouse_button_down(&[MouseButton::Left, MouseButton::Right])
+        {
+            let (bunker_width, _) = Bunker::size();
+            let mouse_position = input_state.mouse_x as f32 / self.viewport_size.0 as f32;
+            let bunker_width = bunker_width as f32 / self.viewport_size.0 as f32;
+            let hovered_bunker = self
+                .level
+                .bunkers
+                .iter()
+                .position(|position| (position - mouse_position).abs() < bunker_width / 2.0);
+
+            self.history.begin(&self.level);
+
+            if is_left_pressed {
+                let is_overlapping = self
+                    .level
+                    .bunkers
+                    .iter()
+                    .any(|position| (position - mouse_position).abs() < bunker_width);
+
+                if !is_overlapping {
+                    self.level.bunkers.push(mouse_position);
+                    self.level
+                        .bunkers
+                        .sort_by(|first, second| first.partial_cmp(second).unwrap());
+                }
+            } else if let Some(bunker_index) = hovered_bunker {
+                self.level.bunkers.remove(bunker_index);
+            }
+
+            self.history.commit(&self.level);
+        }
+    }
+
+    fn run_action(&mut self, action: EditorAction, scene_queue: &mut VecDeque<Box<dyn Scene>>) {
+        match action {
+            EditorAction::New => {
+                self.set_level(Level::new("Custom Level"), &new_level_filepath());
+                self.status_message = format!("New level {}", self.level_filepath);
+            }
+            EditorAction::Load => {
+                let level_filepaths = level::level_filepaths();
+
+                if level_filepaths.is_empty() {
+                    self.status_message = format!("No level files in {}.", LEVELS_DIRECTORY);
+
+                    return;
+                }
+
+                let level_filepath = &level_filepaths[self.next_load_index % level_filepaths.len()];
+                self.next_load_index = (self.next_load_index + 1) % level_filepaths.len();
+
+                match level::read_level_file(level_filepath, &self.alien_types) {
+                    Ok(level) => {
+                        self.set_level(level, level_filepath);
+                        self.status_message = format!("Loaded {}", level_filepath);
+                    }
+                    Err(errors) => {
+                        self.status_message = if errors.len() > 1 {
+                            format!("{} (and {} more errors)", errors[0], errors.len() - 1)
+                        } else {
+                            errors[0].to_string()
+                        };
+                    }
+                }
+            }
+            EditorAction::Save => {
+                if !self.validate_level() {
+                    return;
+                }
+
+                self.status_message = match level::write_level_file(
+                    &self.level_filepath,
+                    &self.saved_level(),
+                    &self.alien_types,
+                ) {
+                    Ok(()) => format!("Saved {}", self.level_filepath),
+                    Err(error) => format!("Could not save {}: {}", self.level_filepath, error),
+                };
+            }
+            EditorAction::Undo => {
+                if let Some(level) = self.history.undo(&self.level) {
+                    self.level = level;
+                    self.sync_widgets();
+                }
+            }
+            EditorAction::Redo => {
+                if let Some(level) = self.history.redo(&self.level) {
+                    self.level = level;
+                    self.sync_widgets();
+                }
+            }
+            EditorAction::ToggleBoss => {
+                self.history.begin(&self.level);
+                self.level.is_boss_level = !self.level.is_boss_level;
+                self.history.commit(&self.level);
+                self.sync_widgets();
+            }
+            EditorAction::Play => {
+                if !self.validate_level() {
+                    return;
+                }
+
+                self.is_done = true;
+                scene_queue.push_back(Box::new(SpaceScene::with_test_level(
+                    self.saved_level(),
+                    &self.level_filepath,
+                )));
+            }
+            EditorAction::Back => {
+                self.is_done = true;
+                scene_queue.push_back(Box::new(MainMenuScene::new()));
+            }
+        }
+    }
+
+    fn draw_text(&self, renderer: &mut Renderer, text: &str, colour: Colour, center: Point) {
+        const TEXT_SCALE: f32 = 0.2;
+
+        renderer.draw_text(
+            Layer::Interface,
+            TextCommand::new(text, self.font_index, colour, center).with_scale(TEXT_SCALE),
+        );
+    }
+
+    fn draw_alien(&self, renderer: &mut Renderer, type_index: usize, rect: Rect) {
+        let texture_index = self.alien_types.types[type_index].texture_index;
+        let sprite_sheet = &self.sprite_sheets[texture_index];
+
+        renderer.draw_sprite(
+            Layer::Playfield,
+            SpriteCommand::new(
+                texture_index,
+                sprite_sheet.frame_rect(0),
+                Rect::from_center(
+                    rect.center(),
+                    u32::min(sprite_sheet.frame_width, rect.width()),
+                    u32::min(sprite_sheet.frame_height, rect.height()),
+                ),
+            ),
+        );
+    }
+
+    fn draw_playfield(&self, renderer: &mut Renderer) {
+        let (column_count, row_count) = self.grid_size;
+
+        for row in 0..row_count {
+            for column in 0..column_count {
+                let cell_rect = self.cell_rect(row, column);
+
+                renderer.draw_rect(Layer::Background, cell_rect, GRID_COLOUR);
+
+                if let Some(type_index) = self.level.formation[row][column] {
+                    self.draw_alien(renderer, type_index, cell_rect);
+                }
+            }
+        }
+
+        if let Some((row, column)) = self.hovered_cell {
+            renderer.draw_rect(
+                Layer::Foreground,
+                self.cell_rect(row, column),
+                Colour::YELLOW,
+            );
+        }
+
+        renderer.fill_rect(
+            Layer::Background,
+            self.bunker_lane_rect(),
+            BUNKER_LANE_COLOUR,
+        );
+
+        for position in &self.level.bunkers {
+            Bunker::at_position(*position, self.viewport_size).draw(renderer);
+        }
+    }
+
+    fn draw_toolbar(&self, renderer: &mut Renderer) {
+        for type_index in 0..self.alien_types.types.len() {
+            let swatch_rect = self.palette_rect(type_index);
+
+            renderer.fill_rect(Layer::Background, swatch_rect, GRID_COLOUR);
+            self.draw_alien(renderer, type_index, swatch_rect);
+
+            if type_index == self.selected_type_index {
+                renderer.draw_rect(Layer::Foreground, swatch_rect, Colour::YELLOW);
+            }
+        }
+
+        for spinner in &self.spinners {
+            spinner.draw(renderer, self.font_index);
+        }
+
+        for button in &self.buttons {
+            button.draw(renderer, self.font_index);
+        }
+
+        let title_string = format!(
+            "{} ({}){}",
+            self.level.name,
+            self.level_filepath,
+            if self.level.is_boss_level {
+                " - boss level"
+            } else {
+                ""
+            }
+        );
+
+        self.draw_text(
+            renderer,
+            &title_string,
+            Colour::WHITE,
+            Point::new(self.viewport_size.0 as i32 / 2, STATUS_Y),
+        );
+        self.draw_text(
+            renderer,
+            &self.status_message,
+            Colour::CYAN,
+            Point::new(self.viewport_size.0 as i32 / 2, STATUS_Y + 24),
+        );
+    }
+}
+
+impl Scene for LevelEditorScene<'_> {
+    fn is_done(&self) -> bool {
+        self.is_done
+    }
+
+    fn on_load(
+        &mut self,
+        _sdl_context: &sdl2::Sdl,
+        canvas: &WindowCanvas,
+        _previous_scene_payload: Option<i32>,
+    ) -> ResourcePaths {
+        let mut textures = vec![];
+        let mut texture_indices = HashMap::new();
+        let mut fonts = vec![];
+
+        for (current_index, texture_filepath) in resources::directory_paths("assets/textures")
+            .into_iter()
+            .enumerate()
+        {
+            let texture_filepath_string = resources::file_name(&texture_filepath);
+
+            texture_indices.insert(texture_filepath_string.clone(), current_index);
+            self.texture_names.push(texture_filepath_string);
+            textures.push(texture_filepath);
+        }
+
+        self.animation_definitions = animation::read_animations_file(ANIMATIONS_FILE_NAME).unwrap();
+        self.alien_types = alien_type::read_alien_types_file().unwrap();
+        self.alien_types
+            .resolve_texture_indices(&texture_indices)
+            .unwrap();
+
+        for (current_index, font_filepath) in resources::directory_paths("assets/fonts")
+            .into_iter()
+            .enumerate()
+        {
+            let font_filepath_string = resources::file_name(&font_filepath);
+
+            if font_filepath_string == "Recursive.ttf" {
+                self.font_index = current_index;
+            }
+
+            fonts.push(font_filepath);
+        }
+
+        let sounds = resources::directory_paths("assets/sounds/effects/menu");
+
+        self.viewport_size = (canvas.viewport().width(), canvas.viewport().height());
+
+        ResourcePaths {
+            textures,
+            fonts,
+            sounds,
+        }
+    }
+
+    fn on_sounds_loaded(&mut self, mut sounds: HashMap<String, Chunk>) {
+        self.button_hover_sound = sounds.remove("button_hover.wav");
+        self.button_select_sound = sounds.remove("button_select.wav");
+    }
+
+    fn preload(&self) -> ResourcePaths {
+        SpaceScene::resource_paths()
+    }
+
+    fn on_late_load(&mut self, _canvas: &WindowCanvas, textures: &[Texture], _fonts: &[Font]) {
+        self.sprite_sheets = animation::create_sprite_sheets(
+            &self.texture_names,
+            textures,
+            &self.animation_definitions,
+        );
+        self.alien_types.resolve_sizes(&self.sprite_sheets);
+
+        for (spinner_index, (label, step, range)) in [
+            ("Velocity", 10.0, (10.0, 400.0)),
+            ("Step", 1.0, (0.0, 50.0)),
+            ("Dropdown", 5.0, (5.0, 120.0)),
+            ("Fire interval", 1.0, (0.0, 30.0)),
+        ]
+        .iter()
+        .enumerate()
+        {
+            self.spinners.push(Spinner::new(
+                20 + spinner_index as i32 * 295,
+                SPINNER_Y,
+                label,
+                *step,
+                *range,
+            ));
+        }
+
+        self.spinners.last_mut().unwrap().zero_text = Some("Default");
+
+        for (button_index, label) in BUTTON_LABELS.iter().enumerate() {
+            self.buttons.push(Button::new(
+                240 + button_index as u32 * 118 + 55,
+                TOOLBAR_Y as u32,
+                110,
+                44,
+                *label,
+                0.25,
+            ));
+            self.buttons.last_mut().unwrap().set_colours(
+                Colour::BLACK,
+                Colour::BLACK,
+                Colour::BLACK,
+                Colour::YELLOW,
+                Colour::GREEN,
+                Colour::WHITE,
+            );
+        }
+
+        let level = self.level.clone();
+        let level_filepath = self.level_filepath.clone();
+        self.set_level(level, &level_filepath);
+    }
+
+    fn on_unload(&mut self, _sdl_context: &sdl2::Sdl) -> Option<i32> {
+        None
+    }
+
+    fn poll_event(&mut self, _event: sdl2::event::Event) {}
+
+    fn process_input(&mut self, input_state: &InputState) {
+        let is_control_pressed =
+            input_state.is_any_key_pressed(&[Scancode::LCtrl, Scancode::RCtrl]);
+        let is_shift_pressed =
+            input_state.is_any_key_pressed(&[Scancode::LShift, Scancode::RShift]);
+
+        if input_state.is_key_down(Scancode::Escape) {
+            self.pending_action = Some(EditorAction::Back);
+        } else if input_state.is_key_down(Scancode::F5) {
+            self.pending_action = Some(EditorAction::Play);
+        } else if is_control_pressed && input_state.is_key_down(Scancode::S) {
+            self.pending_action = Some(EditorAction::Save);
+        } else if is_control_pressed && input_state.is_key_down(Scancode::O) {
+            self.pending_action = Some(EditorAction::Load);
+        } else if is_control_pressed && input_state.is_key_down(Scancode::Y)
+            || is_control_pressed && is_shift_pressed && input_state.is_key_down(Scancode::Z)
+        {
+            self.pending_action = Some(EditorAction::Redo);
+        } else if is_control_pressed && input_state.is_key_down(Scancode::Z) {
+            self.pending_action = Some(EditorAction::Undo);
+        }
+
+        let number_keys = [
+            Scancode::Num1,
+            Scancode::Num2,
+            Scancode::Num3,
+            Scancode::Num4,
+            Scancode::Num5,
+            Scancode::Num6,
+            Scancode::Num7,
+            Scancode::Num8,
+            Scancode::Num9,
+        ];
+
+        for (type_index, scancode) in number_keys
+            .iter()
+            .enumerate()
+            .take(self.alien_types.types.len())
+        {
+            if input_state.is_key_down(*scancode) {
+                self.selected_type_index = type_index;
+            }
+        }
+
+        if input_state.is_mouse_button_down(MouseButton::Left) {
+            let mouse_point = Point::new(input_state.mouse_x, input_state.mouse_y);
+
+            if let Some(type_index) = (0..self.alien_types.types.len())
+                .find(|type_index| self.palette_rect(*type_index).contains_point(mouse_point))
+            {
+                self.selected_type_index = type_index;
+            }
+        }
+
+        let mut has_spinner_changed = false;
+
+        for spinner in &mut self.spinners {
+            has_spinner_changed |= spinner.process_input(input_state);
+        }
+
+        if has_spinner_changed {
+            self.history.begin(&self.level);
+            self.apply_spinners();
+            self.history.commit(&self.level);
+        }
+
+        for (button, action) in self.buttons.iter_mut().zip(BUTTON_ACTIONS.iter()) {
+            button.is_hovered = false;
+            button.is_clicked = false;
+
+            if button.is_mouse_over(input_state) {
+                button.is_hovered = true;
+
+                if input_state.is_mouse_button_down(MouseButton::Left) {
+                    button.is_clicked = true;
+                    self.pending_action = Some(*action);
+                }
+            } else if button.played_enter_sound {
+                button.played_enter_sound = false;
+            }
+        }
+
+        self.process_grid_input(input_state);
+    }
+
+    fn update(
+        &mut self,
+        _delta_time: f32,
+        scene_queue: &mut VecDeque<Box<dyn Scene>>,
+        _canvas: &WindowCanvas,
+        sound_channel: &Channel,
+    ) {
+        for button in &mut self.buttons {
+            if button.is_hovered && !button.played_enter_sound {
+                sound_channel
+                    .play(self.button_hover_sound.as_ref().unwrap(), 0)
+                    .unwrap();
+                button.played_enter_sound = true;
+            }
+        }
+
+        if let Some(action) = self.pending_action.take() {
+            sound_channel
+                .play(self.button_select_sound.as_ref().unwrap(), 0)
+                .unwrap();
+
+            self.run_action(action, scene_queue);
+        }
+    }
+
+    fn draw(&mut self, renderer: &mut Renderer, _fonts: &[Font]) {
+        renderer.clear(BACKGROUND_COLOUR);
+
+        self.draw_playfield(renderer);
+        self.draw_toolbar(renderer);
+    }
+}
+
+fn new_level_filepath() -> String {
+    let mut level_number = level::level_filepaths().len() + 1;
+
+    loop {
+        let level_filepath = format!(
+            "{}/{:02}_custom.{}",
+            LEVELS_DIRECTORY, level_number, LEVEL_FILE_EXTENSION
+        );
+
+        if !vfs::resolve(&level_filepath).exists() {
+            return level_filepath;
+        }
+
+        level_number += 1;
+    }
+}
@@ -0,0 +1,62 @@
+pub struct EditHistory<T> {
+    undo_stack: Vec<T>,
+    redo_stack: Vec<T>,
+    pending_snapshot: Option<T>,
+
+    max_length: usize,
+}
+
+impl<T: Clone + PartialEq> EditHistory<T> {
+    pub fn new(max_length: usize) -> EditHistory<T> {
+        EditHistory {
+            undo_stack: vec![],
+            redo_stack: vec![],
+            pending_snapshot: None,
+            max_length,
+        }
+    }
+
+    pub fn begin(&mut self, state: &T) {
+        if self.pending_snapshot.is_none() {
+            self.pending_snapshot = Some(state.clone());
+        }
+    }
+
+    pub fn commit(&mut self, state: &T) {
+        let snapshot = match self.pending_snapshot.take() {
+            Some(snapshot) => snapshot,
+            None => return,
+        };
+
+        if snapshot == *state {
+            return;
+        }
+
+        if self.undo_stack.len() >= self.max_length {
+            self.undo_stack.remove(0);
+        }
+
+        self.undo_stack.push(snapshot);
+        self.redo_stack.clear();
+    }
+
+    pub fn undo(&mut self, state: &T) -> Option<T> {
+        let snapshot = self.undo_stack.pop()?;
+        self.redo_stack.push(state.clone());
+
+        Some(snapshot)
+    }
+
+    pub fn redo(&mut self, state: &T) -> Option<T> {
+        let snapshot = self.redo_stack.pop()?;
+        self.undo_stack.push(state.clone());
+
+        Some(snapshot)
+    }
+
+    pub fn clear(&mut self) {
+        self.undo_stack.clear();
+        self.redo_stack.clear();
+        self.pending_snapshot = None;
+    }
+}
@@ -0,0 +1,135 @@
+use std::collections::VecDeque;
+
+use sdl2::{
+    mixer::Channel,
+    pixels::Color as Colour,
+    rect::{Point, Rect},
+    render::WindowCanvas,
+    ttf::Font,
+};
+
+use crate::game::input::InputState;
+use crate::game::renderer::{Layer, Renderer, TextCommand};
+use crate::game::resources::{self, ResourcePaths};
+use crate::game::scene::Scene;
+
+const BACKGROUND_COLOUR: Colour = Colour::RGB(10, 10, 10);
+const PROGRESS_BAR_COLOUR: Colour = Colour::RGB(80, 220, 120);
+
+const PROGRESS_BAR_SIZE: (u32, u32) = (600, 24);
+const PROGRESS_BAR_SPEED: f32 = 4.0;
+
+pub struct LoadingScene {
+    font_index: usize,
+
+    progress: f32,
+    displayed_progress: f32,
+}
+
+impl LoadingScene {
+    pub fn new() -> LoadingScene {
+        LoadingScene {
+            font_index: 0,
+            progress: 0.0,
+            displayed_progress: 0.0,
+        }
+    }
+}
+
+impl Scene for LoadingScene {
+    fn is_done(&self) -> bool {
+        false
+    }
+
+    fn on_load(
+        &mut self,
+        _sdl_context: &sdl2::Sdl,
+        _canvas: &WindowCanvas,
+        _previous_scene_payload: Option<i32>,
+    ) -> ResourcePaths {
+        let mut fonts = vec![];
+
+        for (current_index, font_filepath) in resources::directory_paths("assets/fonts")
+            .into_iter()
+            .enumerate()
+        {
+            let font_filepath_string = resources::file_name(&font_filepath);
+
+            if font_filepath_string == "Recursive.ttf" {
+                self.font_index = current_index;
+            }
+
+            fonts.push(font_filepath);
+        }
+
+        ResourcePaths {
+            fonts,
+            ..ResourcePaths::default()
+        }
+    }
+
+    fn on_loading_progress(&mut self, progress: f32) {
+        if progress < self.progress {
+            self.displayed_progress = 0.0;
+        }
+
+        self.progress = progress;
+    }
+
+    fn process_input(&mut self, _input_state: &InputState) {}
+
+    fn update(
+        &mut self,
+        delta_time: f32,
+        _scene_queue: &mut VecDeque<Box<dyn Scene>>,
+        _canvas: &WindowCanvas,
+        _sound_channel: &Channel,
+    ) {
+        self.displayed_progress = f32::min(
+            self.displayed_progress
+                + (self.progress - self.displayed_progress) * PROGRESS_BAR_SPEED * delta_time,
+            self.progress,
+        );
+    }
+
+    fn draw(&mut self, renderer: &mut Renderer, _fonts: &[Font]) {
+        renderer.clear(BACKGROUND_COLOUR);
+
+        let viewport = renderer.viewport();
+        let (bar_width, bar_height) = PROGRESS_BAR_SIZE;
+        let bar_outline = Rect::from_center(viewport.center(), bar_width, bar_height);
+        let filled_width = (bar_width as f32 * self.displayed_progress) as u32;
+
+        renderer.draw_text(
+            Layer::Interface,
+            TextCommand::new(
+                "Loading",
+                self.font_index,
+                Colour::WHITE,
+                Point::new(viewport.center().x(), bar_outline.top() - 50),
+            )
+            .with_scale(0.3),
+        );
+
+        if filled_width > 0 {
+            renderer.fill_rect(
+                Layer::Interface,
+                Rect::new(bar_outline.x(), bar_outline.y(), filled_width, bar_height),
+                PROGRESS_BAR_COLOUR,
+            );
+        }
+
+        renderer.draw_rect(Layer::Interface, bar_outline, Colour::WHITE);
+
+        renderer.draw_text(
+            Layer::Interface,
+            TextCommand::new(
+                format!("{:.0}%", self.displayed_progress * 100.0),
+                self.font_index,
+                Colour::GREY,
+                Point::new(viewport.center().x(), bar_outline.bottom() + 30),
+            )
+            .with_scale(0.18),
+        );
+    }
+}
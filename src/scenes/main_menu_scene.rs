@@ -1,23 +1,30 @@
-use std::collections::VecDeque;
-use std::fs;
+use std::collections::{HashMap, VecDeque};
 
 use sdl2::{
     keyboard::Scancode,
     mixer::{Channel, Chunk, Music},
     mouse::MouseButton,
     pixels::Color as Colour,
-    rect::{Point, Rect},
-    render::{Texture, TextureCreator, WindowCanvas},
+    rect::Point,
+    render::{Texture, WindowCanvas},
     ttf::Font,
 };
 
 use super::button::Button;
 use crate::game::input::InputState;
+use crate::game::renderer::{Layer, Renderer, TextCommand};
+use crate::game::resources::{self, ResourcePaths};
 use crate::game::scene::Scene;
-use crate::scenes::space_scene::SpaceScene;
+use crate::game::vfs;
+use crate::scenes::difficulty_select_scene::DifficultySelectScene;
+use crate::scenes::level_editor_scene::LevelEditorScene;
+use crate::scenes::settings_scene::SettingsScene;
 
 const BACKGROUND_COLOUR: Colour = Colour::RGB(10, 10, 10);
 
+const FIRST_BUTTON_Y: u32 = 290;
+const BUTTON_SPACING: u32 = 135;
+
 pub struct MainMenuScene<'a> {
     font_index: usize,
     buttons: Vec<Button<'a>>,
@@ -42,33 +49,16 @@ impl<'a> MainMenuScene<'a> {
         }
     }
 
-    fn draw_title(
-        &self,
-        canvas: &mut WindowCanvas,
-        texture_creator: &TextureCreator<sdl2::video::WindowContext>,
-        font: &Font,
-    ) {
-        let title_text = font
-            .render("Space Invaders!")
-            .blended(Colour::WHITE)
-            .unwrap();
-        let title_texture = texture_creator
-            .create_texture_from_surface(title_text)
-            .unwrap();
-
-        let title_texture_data = title_texture.query();
-
-        canvas
-            .copy(
-                &title_texture,
-                None,
-                Rect::from_center(
-                    Point::new(canvas.viewport().width() as i32 / 2, 128),
-                    title_texture_data.width,
-                    title_texture_data.height,
-                ),
-            )
-            .unwrap();
+    fn draw_title(&self, renderer: &mut Renderer) {
+        renderer.draw_text(
+            Layer::Interface,
+            TextCommand::new(
+                "Space Invaders!",
+                self.font_index,
+                Colour::WHITE,
+                Point::new(renderer.viewport().width() as i32 / 2, 128),
+            ),
+        );
     }
 }
 
@@ -82,88 +72,70 @@ impl Scene for MainMenuScene<'_> {
         _sdl_context: &sdl2::Sdl,
         _canvas: &WindowCanvas,
         _previous_scene_payload: Option<i32>,
-    ) -> (Vec<String>, Vec<String>) {
+    ) -> ResourcePaths {
         let mut fonts = vec![];
 
-        for (current_index, font_file) in fs::read_dir("assets/fonts").unwrap().enumerate() {
-            let font_file = font_file.unwrap();
-            let font_filepath = font_file.path();
-            let font_filepath_string = font_filepath
-                .file_name()
-                .unwrap()
-                .to_str()
-                .unwrap()
-                .to_owned();
+        for (current_index, font_filepath) in resources::directory_paths("assets/fonts")
+            .into_iter()
+            .enumerate()
+        {
+            let font_filepath_string = resources::file_name(&font_filepath);
 
             if font_filepath_string == "Recursive.ttf" {
                 self.font_index = current_index;
             }
 
-            fonts.push(font_filepath.to_str().unwrap().to_owned());
+            fonts.push(font_filepath);
         }
 
-        for sound_file in fs::read_dir("assets/sounds/effects/menu").unwrap() {
-            let sound_file = sound_file.unwrap();
-            let sound_filepath = sound_file.path();
-            let sound_filepath_string = sound_filepath
-                .file_name()
-                .unwrap()
-                .to_str()
-                .unwrap()
-                .to_owned();
-
-            let loaded_sound_chunk = Some(Chunk::from_file(sound_filepath).unwrap());
-
-            match sound_filepath_string.as_ref() {
-                "button_hover.wav" => {
-                    self.button_hover_sound = loaded_sound_chunk;
-                }
-                "button_select.wav" => {
-                    self.button_select_sound = loaded_sound_chunk;
-                }
-                _ => (),
-            }
-        }
+        let sounds = resources::directory_paths("assets/sounds/effects/menu");
 
-        self.music = Some(Music::from_file("assets/sounds/music/Chill Wave.mp3").unwrap());
+        self.music = Some(
+            Music::from_static_bytes(
+                vfs::read_static("assets/sounds/music/Chill Wave.mp3").unwrap(),
+            )
+            .unwrap(),
+        );
 
-        (vec![], fonts)
+        ResourcePaths {
+            textures: vec![],
+            fonts,
+            sounds,
+        }
     }
 
-    fn on_late_load(&mut self, canvas: &WindowCanvas, _textures: &[Texture], _fonts: &[Font]) {
-        self.buttons.push(Button::new(
-            canvas.viewport().width() / 2,
-            canvas.viewport().height() / 2,
-            400,
-            150,
-            "Play",
-            0.75,
-        ));
-        self.buttons.last_mut().unwrap().set_colours(
-            Colour::BLACK,
-            Colour::BLACK,
-            Colour::BLACK,
-            Colour::YELLOW,
-            Colour::GREEN,
-            Colour::WHITE,
-        );
+    fn on_sounds_loaded(&mut self, mut sounds: HashMap<String, Chunk>) {
+        self.button_hover_sound = sounds.remove("button_hover.wav");
+        self.button_select_sound = sounds.remove("button_select.wav");
+    }
 
-        self.buttons.push(Button::new(
-            canvas.viewport().width() / 2,
-            canvas.viewport().height() / 4 * 3,
-            400,
-            150,
-            "Quit",
-            0.75,
-        ));
-        self.buttons.last_mut().unwrap().set_colours(
-            Colour::BLACK,
-            Colour::BLACK,
-            Colour::BLACK,
-            Colour::YELLOW,
-            Colour::GREEN,
-            Colour::RED,
-        );
+    fn on_late_load(&mut self, canvas: &WindowCanvas, _textures: &[Texture], _fonts: &[Font]) {
+        for (button_index, (text, text_scale, clicked_background_colour)) in [
+            ("Play", 0.6, Colour::WHITE),
+            ("Level Editor", 0.45, Colour::CYAN),
+            ("Settings", 0.45, Colour::MAGENTA),
+            ("Quit", 0.6, Colour::RED),
+        ]
+        .iter()
+        .enumerate()
+        {
+            self.buttons.push(Button::new(
+                canvas.viewport().width() / 2,
+                FIRST_BUTTON_Y + BUTTON_SPACING * button_index as u32,
+                400,
+                110,
+                *text,
+                *text_scale,
+            ));
+            self.buttons.last_mut().unwrap().set_colours(
+                Colour::BLACK,
+                Colour::BLACK,
+                Colour::BLACK,
+                Colour::YELLOW,
+                Colour::GREEN,
+                *clicked_background_colour,
+            );
+        }
 
         self.music.as_ref().unwrap().play(-1).unwrap();
     }
@@ -238,7 +210,25 @@ impl Scene for MainMenuScene<'_> {
 
         if self.buttons.first().unwrap().is_clicked {
             self.is_done = true;
-            scene_queue.push_back(Box::new(SpaceScene::new()));
+            scene_queue.push_back(Box::new(DifficultySelectScene::new()));
+
+            sound_channel
+                .play(self.button_select_sound.as_ref().unwrap(), 0)
+                .unwrap();
+        }
+
+        if self.buttons[1].is_clicked {
+            self.is_done = true;
+            scene_queue.push_back(Box::new(LevelEditorScene::new()));
+
+            sound_channel
+                .play(self.button_select_sound.as_ref().unwrap(), 0)
+                .unwrap();
+        }
+
+        if self.buttons[2].is_clicked {
+            self.is_done = true;
+            scene_queue.push_back(Box::new(SettingsScene::new()));
 
             sound_channel
                 .play(self.button_select_sound.as_ref().unwrap(), 0)
@@ -254,20 +244,13 @@ impl Scene for MainMenuScene<'_> {
         }
     }
 
-    fn draw(
-        &mut self,
-        canvas: &mut WindowCanvas,
-        texture_creator: &TextureCreator<sdl2::video::WindowContext>,
-        _textures: &[Texture],
-        fonts: &[Font],
-    ) {
-        canvas.set_draw_color(BACKGROUND_COLOUR);
-        canvas.clear();
+    fn draw(&mut self, renderer: &mut Renderer, _fonts: &[Font]) {
+        renderer.clear(BACKGROUND_COLOUR);
 
-        self.draw_title(canvas, texture_creator, &fonts[self.font_index]);
+        self.draw_title(renderer);
 
         for button in &self.buttons {
-            button.draw(canvas, texture_creator, &fonts[self.font_index]);
+            button.draw(renderer, self.font_index);
         }
     }
 }
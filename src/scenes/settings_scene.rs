@@ -0,0 +1,265 @@
+use std::collections::{HashMap, VecDeque};
+
+use sdl2::{
+    keyboard::Scancode,
+    mixer::{Channel, Chunk, Music},
+    mouse::MouseButton,
+    pixels::Color as Colour,
+    rect::Point,
+    render::{Texture, WindowCanvas},
+    ttf::Font,
+};
+
+use super::button::Button;
+use crate::game::input::InputState;
+use crate::game::logging::log_warn;
+use crate::game::post_processing::{self, PostProcessingSettings};
+use crate::game::renderer::{Layer, Renderer, TextCommand};
+use crate::game::resources::{self, ResourcePaths};
+use crate::game::scene::Scene;
+use crate::game::vfs;
+use crate::scenes::main_menu_scene::MainMenuScene;
+
+const BACKGROUND_COLOUR: Colour = Colour::RGB(10, 10, 10);
+
+const FIRST_SETTING_Y: u32 = 240;
+const SETTING_SPACING: u32 = 85;
+
+pub struct SettingsScene<'a> {
+    font_index: usize,
+    buttons: Vec<Button<'a>>,
+
+    settings: PostProcessingSettings,
+
+    is_done: bool,
+
+    button_hover_sound: Option<Chunk>,
+    button_select_sound: Option<Chunk>,
+
+    music: Option<Music<'a>>,
+}
+
+impl<'a> SettingsScene<'a> {
+    pub fn new() -> SettingsScene<'a> {
+        SettingsScene {
+            font_index: 0,
+            buttons: vec![],
+            settings: PostProcessingSettings::default(),
+            is_done: false,
+            button_hover_sound: None,
+            button_select_sound: None,
+            music: None,
+        }
+    }
+
+    fn setting_labels(&self) -> [String; 5] {
+        let on_off = |is_on: bool| if is_on { "on" } else { "off" };
+
+        [
+            format!("CRT scanlines: {}", on_off(self.settings.scanlines)),
+            format!("CRT curvature: {}", on_off(self.settings.curvature)),
+            format!("Phosphor glow: {}", on_off(self.settings.bloom)),
+            format!("Cabinet overlay: {}", on_off(self.settings.cabinet_overlay)),
+            format!("Colour filter: {}", self.settings.colour_filter.name()),
+        ]
+    }
+
+    fn toggle_setting(&mut self, setting_index: usize) {
+        let settings = &mut self.settings;
+
+        match setting_index {
+            0 => settings.scanlines = !settings.scanlines,
+            1 => settings.curvature = !settings.curvature,
+            2 => settings.bloom = !settings.bloom,
+            3 => settings.cabinet_overlay = !settings.cabinet_overlay,
+            _ => settings.colour_filter = settings.colour_filter.next(),
+        }
+
+        let label = self.setting_labels()[setting_index].clone();
+        self.buttons[setting_index].text = label.into();
+    }
+}
+
+impl Scene for SettingsScene<'_> {
+    fn is_done(&self) -> bool {
+        self.is_done
+    }
+
+    fn on_load(
+        &mut self,
+        _sdl_context: &sdl2::Sdl,
+        _canvas: &WindowCanvas,
+        _previous_scene_payload: Option<i32>,
+    ) -> ResourcePaths {
+        let mut fonts = vec![];
+
+        for (current_index, font_filepath) in resources::directory_paths("assets/fonts")
+            .into_iter()
+            .enumerate()
+        {
+            let font_filepath_string = resources::file_name(&font_filepath);
+
+            if font_filepath_string == "Recursive.ttf" {
+                self.font_index = current_index;
+            }
+
+            fonts.push(font_filepath);
+        }
+
+        let sounds = resources::directory_paths("assets/sounds/effects/menu");
+
+        self.settings = post_processing::read_post_processing_settings().unwrap();
+        self.music = Some(
+            Music::from_static_bytes(
+                vfs::read_static("assets/sounds/music/Chill Wave.mp3").unwrap(),
+            )
+            .unwrap(),
+        );
+
+        ResourcePaths {
+            textures: vec![],
+            fonts,
+            sounds,
+        }
+    }
+
+    fn on_sounds_loaded(&mut self, mut sounds: HashMap<String, Chunk>) {
+        self.button_hover_sound = sounds.remove("button_hover.wav");
+        self.button_select_sound = sounds.remove("button_select.wav");
+    }
+
+    fn on_late_load(&mut self, canvas: &WindowCanvas, _textures: &[Texture], _fonts: &[Font]) {
+        for (setting_index, label) in self.setting_labels().iter().enumerate() {
+            self.buttons.push(Button::new(
+                canvas.viewport().width() / 2,
+                FIRST_SETTING_Y + SETTING_SPACING * setting_index as u32,
+                500,
+                70,
+                label.clone(),
+                0.3,
+            ));
+            self.buttons.last_mut().unwrap().set_colours(
+                Colour::BLACK,
+                Colour::BLACK,
+                Colour::BLACK,
+                Colour::WHITE,
+                Colour::GREEN,
+                Colour::YELLOW,
+            );
+        }
+
+        self.buttons.push(Button::new(
+            canvas.viewport().width() / 2,
+            canvas.viewport().height() - 80,
+            300,
+            90,
+            "Back",
+            0.4,
+        ));
+        self.buttons.last_mut().unwrap().set_colours(
+            Colour::BLACK,
+            Colour::BLACK,
+            Colour::BLACK,
+            Colour::YELLOW,
+            Colour::GREEN,
+            Colour::RED,
+        );
+
+        self.music.as_ref().unwrap().play(-1).unwrap();
+    }
+
+    fn on_unload(&mut self, _sdl_context: &sdl2::Sdl) -> Option<i32> {
+        Music::halt();
+
+        if let Err(error) = post_processing::write_post_processing_settings(&self.settings) {
+            log_warn!("Could not save post-processing settings: {}", error);
+        }
+
+        None
+    }
+
+    fn process_input(&mut self, input_state: &InputState) {
+        if input_state.is_key_down(Scancode::Escape) {
+            self.buttons.last_mut().unwrap().is_clicked = true;
+
+            return;
+        }
+
+        for button in &mut self.buttons {
+            button.is_hovered = false;
+            button.is_clicked = false;
+
+            if button.is_mouse_over(input_state) {
+                button.is_hovered = true;
+
+                if input_state.is_mouse_button_down(MouseButton::Left) {
+                    button.is_clicked = true;
+                }
+            } else if button.played_enter_sound {
+                button.played_enter_sound = false;
+            }
+        }
+    }
+
+    fn update(
+        &mut self,
+        _delta_time: f32,
+        scene_queue: &mut VecDeque<Box<dyn Scene>>,
+        _canvas: &WindowCanvas,
+        sound_channel: &Channel,
+    ) {
+        for button in &mut self.buttons {
+            if button.is_hovered && !button.played_enter_sound {
+                sound_channel
+                    .play(self.button_hover_sound.as_ref().unwrap(), 0)
+                    .unwrap();
+                button.played_enter_sound = true;
+            }
+        }
+
+        let clicked_button_index = match self.buttons.iter().position(|button| button.is_clicked) {
+            Some(button_index) => button_index,
+            None => return,
+        };
+
+        sound_channel
+            .play(self.button_select_sound.as_ref().unwrap(), 0)
+            .unwrap();
+
+        if clicked_button_index == self.buttons.len() - 1 {
+            self.is_done = true;
+            scene_queue.push_back(Box::new(MainMenuScene::new()));
+        } else {
+            self.toggle_setting(clicked_button_index);
+        }
+    }
+
+    fn draw(&mut self, renderer: &mut Renderer, _fonts: &[Font]) {
+        renderer.clear(BACKGROUND_COLOUR);
+
+        renderer.draw_text(
+            Layer::Interface,
+            TextCommand::new(
+                "Settings",
+                self.font_index,
+                Colour::WHITE,
+                Point::new(renderer.viewport().width() as i32 / 2, 110),
+            )
+            .with_scale(0.6),
+        );
+        renderer.draw_text(
+            Layer::Interface,
+            TextCommand::new(
+                "Display effects are applied when you leave this screen.",
+                self.font_index,
+                Colour::GREY,
+                Point::new(renderer.viewport().width() as i32 / 2, 170),
+            )
+            .with_scale(0.18),
+        );
+
+        for button in &self.buttons {
+            button.draw(renderer, self.font_index);
+        }
+    }
+}
@@ -1,98 +1,250 @@
-mod alien;
+pub mod alien;
+mod boss;
+pub mod bunker;
+mod components;
+pub mod difficulty;
+mod director;
+pub mod level;
+mod power_up;
 mod spaceship;
+mod systems;
 
-use std::collections::VecDeque;
-use std::fs;
+use std::collections::{HashMap, VecDeque};
+use std::error::Error;
+use std::path::Path;
+use std::rc::Rc;
 
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 use sdl2::{
     keyboard::Scancode,
     mixer::{Channel, Chunk, Music},
     pixels::Color as Colour,
     rect::{Point, Rect},
-    render::{Texture, TextureCreator, WindowCanvas},
+    render::{Texture, WindowCanvas},
     ttf::Font,
 };
 
+use self::alien::alien_type::{self, AlienTypeTable};
 use self::alien::*;
+use self::boss::{Boss, BossAction, BossDefinition, BossState};
+use self::bunker::Bunker;
+use self::components::*;
+use self::difficulty::Difficulty;
+use self::director::Director;
+use self::level::{Level, LevelEvent, LevelEventKind};
+use self::power_up::*;
 use self::spaceship::bullet::*;
 use self::spaceship::*;
+use self::systems::{Arena, EnemyTimeScale, SpaceEvent, SpaceRng};
+use crate::game::animation::{self, SpriteSheet, SpriteSheetDefinition};
+use crate::game::camera::{self, Camera, CameraSettings};
+use crate::game::collision::{
+    self, Collider, ColliderShape, CollisionSettings, CollisionWorld, PixelMask,
+};
+use crate::game::console::{ConsoleCommand, ConsoleVariable};
+use crate::game::crash_report;
+use crate::game::ecs::components::{Lifetime, Sprite, Transform, Velocity};
+use crate::game::ecs::{Entity, Schedule, World};
 use crate::game::input::InputState;
+use crate::game::logging::log_warn;
+use crate::game::particles::{self, Emitter, ParticleSystem, Starfield, StarfieldMode};
+use crate::game::profiler::profile_scope;
+use crate::game::random;
+use crate::game::renderer::{Anchor, Layer, Renderer, SpriteCommand, TextCommand};
+use crate::game::resources::{self, ResourcePaths};
 use crate::game::scene::Scene;
+use crate::game::transition::{Transition, TransitionEffect};
+use crate::game::vfs;
 use crate::scenes::game_over_scene::GameOverScene;
+use crate::scenes::level_editor_scene::LevelEditorScene;
 use crate::scenes::main_menu_scene::MainMenuScene;
 
 const BACKGROUND_COLOUR: Colour = Colour::RGB(10, 10, 10);
 const BACKGROUND_VELOCITY: f32 = 400.0;
+const TRANSITION_TIME: f32 = 0.8;
 
 const INITIAL_PLAYER_LIVES: u32 = 3;
 const LEVEL_RESET_TIME: f32 = 1.0;
 const LEVEL_COMPLETE_POINTS: u32 = 100;
+const LEVEL_MESSAGE_TIME: f32 = 3.0;
+const FLEET_INITIAL_FIRE_DELAY: f32 = 1.5;
+const BOSS_DAMAGE_TINT_MIN: u8 = 120;
+
+const PLAYER_DEATH_TRAUMA: f32 = 0.6;
+const FLEET_LANDED_TRAUMA: f32 = 0.8;
+const BOSS_HIT_TRAUMA: f32 = 0.1;
+const BOSS_PART_DESTROYED_TRAUMA: f32 = 0.35;
+const BOSS_EXPLOSION_TRAUMA: f32 = 0.2;
+const LEVEL_CLEAR_ZOOM: f32 = 0.08;
+
+const COLLISION_GRID_CELL_SIZE: f32 = 64.0;
+
+const TEXTURES_DIRECTORY: &str = "assets/textures";
+const FONTS_DIRECTORY: &str = "assets/fonts";
+const SOUNDS_DIRECTORY: &str = "assets/sounds/effects/game";
+const ANIMATIONS_FILE_NAME: &str = "config/animations.json";
+const PARTICLES_FILE_NAME: &str = "config/particles.json";
+
+const SPACESHIP_VELOCITY_VARIABLE: &str = "spaceship_velocity";
+const ALIEN_SHOOT_INTERVAL_VARIABLE: &str = "alien_shoot_interval";
+
+const BOSS_SPAWN_NAME: &str = "boss";
+// The boss is the game's take on the classic UFO, so "spawn ufo" works too.
+const BOSS_SPAWN_ALIAS: &str = "ufo";
+
+const CONSOLE_COMMANDS: [ConsoleCommand; 7] = [
+    ConsoleCommand {
+        name: "god",
+        usage: "god - Toggle invulnerability.",
+    },
+    ConsoleCommand {
+        name: "level",
+        usage: "level <number> - Jump to a level.",
+    },
+    ConsoleCommand {
+        name: "spawn",
+        usage: "spawn <alien type> [count] | spawn boss - Spawn aliens at the top of the screen, or the boss (alias: spawn ufo).",
+    },
+    ConsoleCommand {
+        name: "lives",
+        usage: "lives <count> - Set the number of lives.",
+    },
+    ConsoleCommand {
+        name: "kill_all",
+        usage: "kill_all - Destroy every alien and the boss.",
+    },
+    ConsoleCommand {
+        name: "give",
+        usage: "give <power-up> - Apply a power-up, e.g. rapid-fire.",
+    },
+    ConsoleCommand {
+        name: "seed",
+        usage: "seed <number> - Reseed the game's random numbers and restart the level.",
+    },
+];
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum CollisionTag {
+    Spaceship,
+    PlayerBullet(Entity),
+    AlienBullet(Entity),
+    Alien(Entity),
+    Bunker(usize),
+    BossPart(usize),
+    PowerUp(Entity),
+}
+
+impl CollisionTag {
+    fn for_entity(entity: Entity, layer: u32) -> CollisionTag {
+        match layer {
+            PLAYER_LAYER => CollisionTag::Spaceship,
+            PLAYER_BULLET_LAYER => CollisionTag::PlayerBullet(entity),
+            ALIEN_BULLET_LAYER => CollisionTag::AlienBullet(entity),
+            POWER_UP_LAYER => CollisionTag::PowerUp(entity),
+            _ => CollisionTag::Alien(entity),
+        }
+    }
+}
 
 pub struct SpaceScene<'a> {
     has_window_focus: bool,
     is_done: bool,
 
+    difficulty: Difficulty,
+    director: Director,
+    rng_seed: u64,
+    is_god_mode: bool,
+    spaceship_velocity: f32,
+    alien_shoot_interval: f32,
     current_level: u32,
+    levels: Vec<Level>,
+    test_level: Option<(Level, String)>,
+    level_events: Vec<LevelEvent>,
+    next_level_event_index: usize,
+    level_time: f32,
+    level_message: Option<(String, f32)>,
     player_lives: u32,
     level_reset_timeout: f32,
     score: u32,
 
+    world: World,
+    systems: Schedule,
+
     spaceship: Spaceship,
-    spaceship_size: (u32, u32),
     alien_data: AlienData,
-    aliens: Vec<Alien>,
+    bunkers: Vec<Bunker>,
+    collision_world: CollisionWorld<CollisionTag>,
+    collision_settings: CollisionSettings,
+    sprite_masks: HashMap<usize, Vec<Rc<PixelMask>>>,
+
+    boss_definition: Option<BossDefinition>,
+    boss: Option<Boss>,
+
+    power_up_table: PowerUpTable,
+    active_power_ups: ActivePowerUps,
 
     background_offset: f32,
     background_texture_index: usize,
+    background_size: (u32, u32),
+    explosion_texture_index: usize,
     font_index: usize,
 
+    texture_names: Vec<String>,
+    animation_definitions: HashMap<String, SpriteSheetDefinition>,
+
+    particle_system: ParticleSystem,
+    thruster_emitter: Emitter,
+    starfield: Option<Starfield>,
+    camera: Camera,
+    starfield_mode: StarfieldMode,
+
     level_win_sound: Option<Chunk>,
     music: Option<Music<'a>>,
 }
 
 impl<'a> SpaceScene<'a> {
-    pub fn new() -> SpaceScene<'a> {
+    pub fn new(difficulty: Difficulty) -> SpaceScene<'a> {
+        let rng_seed = random::initial_seed();
+        let mut world = systems::create_world(rng_seed);
+        let spaceship_entity = world.spawn();
+
         SpaceScene {
             has_window_focus: true,
             is_done: false,
+            director: Director::new(Default::default()),
+            rng_seed,
+            is_god_mode: false,
+            spaceship_velocity: SPACESHIP_VELOCITY,
+            alien_shoot_interval: ALIEN_SHOOT_INTERVAL,
             current_level: 1,
-            player_lives: INITIAL_PLAYER_LIVES,
+            levels: vec![],
+            test_level: None,
+            level_events: vec![],
+            next_level_event_index: 0,
+            level_time: 0.0,
+            level_message: None,
+            player_lives: difficulty.player_lives,
             level_reset_timeout: 0.0,
             score: 0,
 
+            world,
+            systems: systems::create_schedule(),
+
             spaceship: Spaceship {
-                rect: Rect::new(0, 0, 0, 0),
-                x_velocity: 0.0,
-                is_firing: false,
-                shoot_delay: 0.0,
-                is_hit: false,
+                entity: spaceship_entity,
+                size: (0, 0),
                 texture_index: 0,
-                bullet_data: BulletData {
-                    width: 0,
-                    height: 0,
-                    texture_index: 0,
-                },
-                bullets: vec![],
                 shoot_sound: None,
                 death_sound: None,
             },
-            spaceship_size: (0, 0),
 
             alien_data: AlienData {
-                width: 0,
-                height: 0,
-                velocity: INITIAL_ALIEN_VELOCITY,
-                direction: AlienDirection::Right,
-                next_direction: None,
-                dropdown_distance: 0.0,
-                has_hit_bottom: false,
-                texture_index: 0,
-                bullet_data: BulletData {
-                    width: 0,
-                    height: 0,
-                    texture_index: 0,
+                alien_types: AlienTypeTable {
+                    types: vec![],
+                    row_types: vec![],
                 },
-                bullets: vec![],
+                fire_interval: None,
                 shoot_sound: None,
                 death_sound: None,
                 pass_sound: None,
@@ -101,246 +253,916 @@ impl<'a> SpaceScene<'a> {
 
             background_offset: 0.0,
             background_texture_index: 0,
+            background_size: (0, 0),
+            explosion_texture_index: 0,
             font_index: 0,
-            aliens: vec![],
+            texture_names: vec![],
+            animation_definitions: HashMap::new(),
+            particle_system: ParticleSystem::new(0, HashMap::new()),
+            thruster_emitter: Emitter::new("thruster"),
+            starfield: None,
+            camera: Camera::new(CameraSettings::default()),
+            starfield_mode: StarfieldMode::Off,
+            bunkers: vec![],
+            collision_world: CollisionWorld::new(COLLISION_GRID_CELL_SIZE),
+            collision_settings: CollisionSettings {
+                use_pixel_masks: false,
+                alpha_threshold: 0,
+            },
+            sprite_masks: HashMap::new(),
+            boss_definition: None,
+            boss: None,
+            power_up_table: PowerUpTable::empty(),
+            active_power_ups: ActivePowerUps::default(),
             level_win_sound: None,
             music: None,
+            difficulty,
+        }
+    }
+
+    pub fn resource_paths() -> ResourcePaths {
+        ResourcePaths {
+            textures: resources::directory_paths(TEXTURES_DIRECTORY),
+            fonts: resources::directory_paths(FONTS_DIRECTORY),
+            sounds: resources::directory_paths(SOUNDS_DIRECTORY),
+        }
+    }
+
+    pub fn with_test_level(level: Level, level_filepath: &str) -> SpaceScene<'a> {
+        let mut space_scene = SpaceScene::new(Difficulty::default());
+        space_scene.test_level = Some((level, level_filepath.to_string()));
+
+        space_scene
+    }
+
+    fn exit_to_editor(&mut self, scene_queue: &mut VecDeque<Box<dyn Scene>>) -> bool {
+        match &self.test_level {
+            Some((level, level_filepath)) => {
+                self.is_done = true;
+                scene_queue.push_back(Box::new(LevelEditorScene::with_level(
+                    level.clone(),
+                    level_filepath,
+                )));
+
+                true
+            }
+            None => false,
         }
     }
 
     fn setup_objects(&mut self, canvas: &WindowCanvas) {
-        self.spaceship.rect = Rect::from_center(
-            Point::new(
-                (canvas.viewport().width() / 2) as i32,
-                (canvas.viewport().height() - self.spaceship_size.1) as i32,
+        crash_report::set_context("level", self.current_level);
+        crash_report::set_context("seed", self.rng_seed);
+
+        self.world.clear();
+        self.world.insert_resource(Arena {
+            width: canvas.viewport().width(),
+            height: canvas.viewport().height(),
+        });
+
+        self.spaceship.entity = systems::spawn_spaceship(
+            &mut self.world,
+            (
+                (canvas.viewport().width() / 2) as f32,
+                (canvas.viewport().height() - self.spaceship.size.1) as f32,
             ),
-            self.spaceship_size.0,
-            self.spaceship_size.1,
+            self.spaceship.texture_index,
+            self.spaceship.size,
         );
 
-        self.spaceship.x_velocity = 0.0;
-        self.spaceship.is_firing = false;
-        self.spaceship.shoot_delay = 0.0;
-        self.spaceship.is_hit = false;
-        self.spaceship.bullets.clear();
-
         self.create_alien_fleet(canvas);
     }
 
-    fn create_alien_fleet(&mut self, canvas: &WindowCanvas) {
-        self.alien_data.velocity = INITIAL_ALIEN_VELOCITY
+    fn restart_level(&mut self, canvas: &WindowCanvas) {
+        self.active_power_ups.clear();
+        self.level_reset_timeout = 0.0;
+        self.setup_objects(canvas);
+    }
+
+    fn is_spaceship_hit(&self) -> bool {
+        systems::is_dying(&self.world, self.spaceship.entity)
+    }
+
+    fn spaceship_position(&self) -> (f32, f32) {
+        self.world
+            .read::<Transform>()
+            .get(self.spaceship.entity)
+            .map_or((0.0, 0.0), Transform::position)
+    }
+
+    fn current_level_definition(&self, canvas: &WindowCanvas) -> Level {
+        if let Some(level) = self.levels.get(self.current_level as usize - 1) {
+            return level.clone();
+        }
+
+        let mut level = Level::new(&format!("Level {}", self.current_level));
+        level.velocity = INITIAL_ALIEN_VELOCITY
             + (self.current_level - 1) as f32 * PER_LEVEL_ALIEN_VELOCITY_INCREASE;
-        self.alien_data.direction = AlienDirection::Right;
-        self.alien_data.bullets.clear();
-        self.alien_data.next_direction = None;
-        self.alien_data.dropdown_distance = 0.0;
-        self.alien_data.has_hit_bottom = false;
-
-        self.aliens.clear();
-
-        for alien_y in 0..ALIEN_ROW_COUNT {
-            let mut alien_x = self.alien_data.width;
-
-            while alien_x < canvas.viewport().width() - self.alien_data.width {
-                self.aliens.push(Alien::new(
-                    alien_x as f32,
-                    self.alien_data.height as f32
-                        + (self.alien_data.height as f32 * 1.5 * alien_y as f32),
-                ));
+        level.is_boss_level = self
+            .boss_definition
+            .as_ref()
+            .is_some_and(|boss_definition| boss_definition.is_boss_level(self.current_level));
+
+        let (cell_width, _) = self.alien_data.alien_types.cell_size();
+        let column_count = level::default_column_count(cell_width, canvas.viewport().width());
+
+        for type_index in &self.alien_data.alien_types.row_types {
+            level.formation.push(vec![Some(*type_index); column_count]);
+        }
+
+        level
+    }
+
+    fn create_alien_fleet(&mut self, canvas: &WindowCanvas) {
+        let level = self.current_level_definition(canvas);
+        let cell_size = self.alien_data.alien_types.cell_size();
+
+        self.alien_data.fire_interval = level.fire_interval;
+        self.world.insert_resource(Fleet {
+            velocity: level.velocity * self.difficulty.alien_velocity_multiplier,
+            velocity_increment: level.velocity_increment
+                * self.difficulty.alien_velocity_multiplier
+                * self.director.velocity_increment_multiplier,
+            level_dropdown_distance: level.dropdown_distance,
+            fire_delay: FLEET_INITIAL_FIRE_DELAY,
+            fire_interval_scale: self.alien_shoot_interval / ALIEN_SHOOT_INTERVAL,
+            max_bullets: level.max_alien_bullets,
+            targeted_shot_chance: level.targeted_shot_chance,
+            bullet_velocity_multiplier: self.difficulty.alien_bullet_velocity_multiplier,
+            column_width: cell_size.0 as f32,
+            ..Fleet::default()
+        });
+
+        self.boss = None;
+
+        self.bunkers = level
+            .bunkers
+            .iter()
+            .map(|position| {
+                Bunker::at_position(
+                    *position,
+                    (canvas.viewport().width(), canvas.viewport().height()),
+                )
+            })
+            .collect();
+
+        self.level_events = level.events.clone();
+        self.next_level_event_index = 0;
+        self.level_time = 0.0;
+        self.level_message = None;
+
+        if level.is_boss_level {
+            self.spawn_boss(canvas);
+        }
+
+        for (row, cells) in level.formation.iter().enumerate() {
+            for (column, cell) in cells.iter().enumerate() {
+                if let Some(type_index) = cell {
+                    let alien_position =
+                        level.alien_position(row, column, cell_size, canvas.viewport().width());
 
-                alien_x += self.alien_data.width * 2;
+                    self.spawn_alien(alien_position, *type_index);
+                }
             }
         }
     }
 
+    fn spawn_alien(&mut self, position: (f32, f32), type_index: usize) {
+        let shoot_interval = self.alien_shoot_interval(type_index);
+
+        systems::spawn_alien(
+            &mut self.world,
+            position,
+            type_index,
+            &self.alien_data.alien_types.types[type_index],
+            shoot_interval,
+        );
+    }
+
+    fn alien_shoot_interval(&self, type_index: usize) -> f32 {
+        self.alien_data
+            .fire_interval
+            .unwrap_or(self.alien_data.alien_types.types[type_index].shoot_interval)
+            * self.fire_interval_multiplier()
+    }
+
+    fn fire_interval_multiplier(&self) -> f32 {
+        self.difficulty.alien_fire_interval_multiplier * self.director.fire_interval_multiplier
+    }
+
+    fn power_up_drop_multiplier(&self) -> f32 {
+        self.difficulty.power_up_drop_multiplier * self.director.power_up_drop_multiplier
+    }
+
+    fn spawn_boss(&mut self, canvas: &WindowCanvas) {
+        let boss_definition = match &self.boss_definition {
+            Some(boss_definition) => boss_definition,
+            None => return,
+        };
+        let appearance = self.current_level / u32::max(boss_definition.level_interval, 1);
+
+        self.boss = Some(Boss::new(
+            boss_definition,
+            u32::max(appearance, 1),
+            &self.world.resource::<Vec<SpriteSheet>>(),
+            canvas.viewport().width(),
+        ));
+    }
+
+    fn spawn_reinforcements(&mut self, type_index: usize, count: u32, canvas: &WindowCanvas) {
+        let (_, cell_height) = self.alien_data.alien_types.cell_size();
+        let spacing = canvas.viewport().width() as f32 / (count + 1) as f32;
+
+        for alien_index in 0..count {
+            self.spawn_alien(
+                (spacing * (alien_index + 1) as f32, cell_height as f32),
+                type_index,
+            );
+        }
+    }
+
+    fn is_level_clear(&self) -> bool {
+        systems::alien_count(&self.world, true) == 0 && self.boss.is_none()
+    }
+
     fn process_spaceship_input(&mut self, input_state: &InputState) {
-        self.spaceship.x_velocity = 0.0;
+        let mut x_direction = 0.0;
 
         if input_state.is_any_key_pressed(&[Scancode::A, Scancode::Left]) {
-            self.spaceship.x_velocity -= 1.0;
+            x_direction -= 1.0;
         }
 
         if input_state.is_any_key_pressed(&[Scancode::D, Scancode::Right]) {
-            self.spaceship.x_velocity += 1.0;
+            x_direction += 1.0;
         }
 
-        self.spaceship.is_firing = false;
+        if let Some(velocity) = self
+            .world
+            .write::<Velocity>()
+            .get_mut(self.spaceship.entity)
+        {
+            velocity.x = x_direction * self.spaceship_velocity;
+        }
 
-        if input_state.is_key_pressed(Scancode::Space) && self.spaceship.shoot_delay <= 0.0 {
-            self.spaceship.is_firing = true;
-            self.spaceship.shoot_delay = SPACESHIP_SHOOT_DELAY;
+        let mut weapons = self.world.write::<Weapon>();
+        let weapon = match weapons.get_mut(self.spaceship.entity) {
+            Some(weapon) => weapon,
+            None => return,
+        };
+
+        weapon.is_firing = false;
+
+        if input_state.is_key_pressed(Scancode::Space) && weapon.cooldown <= 0.0 {
+            weapon.is_firing = true;
+            weapon.cooldown = weapon.interval
+                * self
+                    .active_power_ups
+                    .shoot_delay_multiplier(&self.power_up_table);
+            weapon.spread = self.active_power_ups.spread_shot(&self.power_up_table);
+            weapon.is_piercing = self
+                .active_power_ups
+                .is_active(PowerUpKind::PiercingLaser, &self.power_up_table);
         }
     }
 
-    fn update_spaceship(
-        &mut self,
-        delta_time: f32,
-        canvas: &WindowCanvas,
-        sound_channel: &Channel,
-    ) {
-        self.spaceship.rect.set_x(
-            (self.spaceship.rect.x() as f32
-                + (self.spaceship.x_velocity * delta_time * SPACESHIP_VELOCITY)) as i32,
-        );
-        self.spaceship.rect.set_x(std::cmp::min(
-            std::cmp::max(0, self.spaceship.rect.x()),
-            (canvas.viewport().width() - self.spaceship.rect.width()) as i32,
-        ));
+    fn update_objects(&mut self, delta_time: f32, sound_channel: &Channel) {
+        profile_scope!("space_scene::systems");
 
-        if self.spaceship.is_firing {
-            self.spaceship.bullets.push(Bullet {
-                x: (self.spaceship.rect.x() + self.spaceship.rect.width() as i32 / 2) as f32,
-                y: (self.spaceship.rect.y()) as f32,
-                has_hit_something: false,
-            });
+        self.systems.run(&mut self.world, delta_time);
 
-            sound_channel
-                .play(self.spaceship.shoot_sound.as_ref().unwrap(), 0)
-                .unwrap();
+        let events = std::mem::take(&mut *self.world.resource_mut::<Vec<SpaceEvent>>());
+
+        for event in events {
+            let sound = match event {
+                SpaceEvent::PlayerFired { x, y, shots } => {
+                    self.director.record_shots(shots);
+                    self.particle_system.burst("muzzle-flash", x, y);
+
+                    &self.spaceship.shoot_sound
+                }
+                SpaceEvent::AlienFired => &self.alien_data.shoot_sound,
+                SpaceEvent::FleetShifted => &self.alien_data.shift_sound,
+                SpaceEvent::FleetLanded => {
+                    self.level_reset_timeout = LEVEL_RESET_TIME;
+                    self.camera.add_trauma(FLEET_LANDED_TRAUMA);
+
+                    &self.alien_data.pass_sound
+                }
+            };
+
+            sound_channel.play(sound.as_ref().unwrap(), 0).unwrap();
+        }
+
+        let (spaceship_x, spaceship_y) = self.spaceship_position();
+        let is_thrusting = self
+            .world
+            .read::<Velocity>()
+            .get(self.spaceship.entity)
+            .is_some_and(|velocity| velocity.x != 0.0);
+
+        self.thruster_emitter.x = spaceship_x;
+        self.thruster_emitter.y = spaceship_y + self.spaceship.size.1 as f32 / 2.0;
+        self.thruster_emitter.is_emitting = is_thrusting && !self.is_spaceship_hit();
+    }
+
+    fn update_boss(&mut self, delta_time: f32, canvas: &WindowCanvas, sound_channel: &Channel) {
+        profile_scope!("space_scene::boss");
+
+        let player_position = self.spaceship_position();
+        let (boss, boss_definition) = match (&mut self.boss, &self.boss_definition) {
+            (Some(boss), Some(boss_definition)) => (boss, boss_definition),
+            _ => return,
+        };
+
+        let mut is_boss_defeated = false;
+        let mut spawned_aliens = vec![];
+
+        for action in boss.update(
+            delta_time,
+            boss_definition,
+            player_position,
+            canvas.viewport().width(),
+        ) {
+            match action {
+                BossAction::Fire(mut bullet) => {
+                    bullet.velocity_multiplier = self.difficulty.alien_bullet_velocity_multiplier;
+                    systems::spawn_bullet(&mut self.world, Faction::Alien, bullet);
+
+                    sound_channel
+                        .play(self.alien_data.shoot_sound.as_ref().unwrap(), 0)
+                        .unwrap();
+                }
+                BossAction::SpawnMinions {
+                    x,
+                    y,
+                    count,
+                    alien_type_name,
+                } => {
+                    if let Some(type_index) = self.alien_data.alien_types.index_of(&alien_type_name)
+                    {
+                        let alien_type = &self.alien_data.alien_types.types[type_index];
+
+                        for minion_index in 0..count {
+                            let x_offset = (minion_index as f32 - (count - 1) as f32 / 2.0)
+                                * alien_type.width as f32
+                                * 1.5;
+
+                            spawned_aliens.push(((x + x_offset, y), type_index));
+                        }
+                    }
+                }
+                BossAction::Explode { x, y } => {
+                    self.particle_system.burst("alien-explosion", x, y);
+                    self.camera.add_trauma(BOSS_EXPLOSION_TRAUMA);
+
+                    sound_channel
+                        .play(self.alien_data.death_sound.as_ref().unwrap(), 0)
+                        .unwrap();
+                }
+                BossAction::PhaseChange => {
+                    self.particle_system.burst("boss-hit", boss.x, boss.y);
+
+                    sound_channel
+                        .play(self.alien_data.shift_sound.as_ref().unwrap(), 0)
+                        .unwrap();
+                }
+                BossAction::Defeated => {
+                    self.score += self
+                        .difficulty
+                        .scaled_score(boss_definition.bonus_points * boss.appearance);
+                    is_boss_defeated = true;
+                }
+            }
         }
 
-        for bullet in &mut self.spaceship.bullets {
-            bullet.y -= delta_time * BULLET_VELOCITY;
+        if is_boss_defeated {
+            self.boss = None;
         }
 
-        if self.spaceship.shoot_delay > 0.0 {
-            self.spaceship.shoot_delay -= delta_time;
+        for (position, type_index) in spawned_aliens {
+            self.spawn_alien(position, type_index);
         }
     }
 
-    fn update_aliens(&mut self, delta_time: f32, canvas: &WindowCanvas, sound_channel: &Channel) {
-        let mut switch_alien_direction = false;
-        let movement = delta_time * self.alien_data.velocity;
+    fn update_level_events(&mut self, delta_time: f32, canvas: &WindowCanvas) {
+        self.level_time += delta_time;
+
+        if let Some((_, message_time)) = &mut self.level_message {
+            *message_time -= delta_time;
 
-        for alien in &mut self.aliens {
-            match self.alien_data.direction {
-                AlienDirection::Left => {
-                    alien.x -= movement;
+            if *message_time <= 0.0 {
+                self.level_message = None;
+            }
+        }
+
+        while let Some(event) = self.level_events.get(self.next_level_event_index) {
+            if event.time > self.level_time {
+                break;
+            }
 
-                    if alien.x <= self.alien_data.width as f32 / 2.0 {
-                        switch_alien_direction = true;
+            let event_kind = event.kind.clone();
+            self.next_level_event_index += 1;
+
+            match event_kind {
+                LevelEventKind::Message(text) => {
+                    self.level_message = Some((text, LEVEL_MESSAGE_TIME));
+                }
+                LevelEventKind::SpeedUp(amount) => {
+                    self.world.resource_mut::<Fleet>().velocity +=
+                        amount * self.difficulty.alien_velocity_multiplier;
+                }
+                LevelEventKind::Reinforcements { type_index, count } => {
+                    self.spawn_reinforcements(type_index, count, canvas);
+                }
+                LevelEventKind::PowerUp(kind) => {
+                    if let Some(type_index) = self
+                        .power_up_table
+                        .types
+                        .iter()
+                        .position(|power_up_type| power_up_type.kind == kind)
+                    {
+                        let drop_x = self
+                            .world
+                            .resource_mut::<SpaceRng>()
+                            .0
+                            .gen_range(POWER_UP_SIZE, canvas.viewport().width() - POWER_UP_SIZE);
+
+                        systems::spawn_power_up(&mut self.world, (drop_x as f32, 0.0), type_index);
                     }
                 }
-                AlienDirection::Right => {
-                    alien.x += movement;
+            }
+        }
+    }
+
+    fn update_collisions(&mut self, sound_channel: &Channel) {
+        profile_scope!("space_scene::collisions");
+
+        let collision_world = &mut self.collision_world;
+        collision_world.clear();
+
+        {
+            let sprite_sheets = self.world.resource::<Vec<SpriteSheet>>();
+            let hitboxes = self.world.read::<Hitbox>();
+            let transforms = self.world.read::<Transform>();
+            let sprites = self.world.read::<Sprite>();
+
+            for (entity, hitbox) in hitboxes.iter() {
+                let transform = match transforms.get(entity) {
+                    Some(transform) => transform,
+                    None => continue,
+                };
+
+                if systems::is_dying(&self.world, entity) {
+                    continue;
+                }
+
+                let shape = match (hitbox.shape, sprites.get(entity)) {
+                    (HitboxShape::Circle { radius }, _) => ColliderShape::Circle { radius },
+                    (HitboxShape::Sprite, Some(sprite)) => sprite_collider_shape(
+                        &self.sprite_masks,
+                        sprite.texture_index,
+                        sprite
+                            .animator
+                            .current_frame(&sprite_sheets[sprite.texture_index]),
+                        sprite.size,
+                    ),
+                    (HitboxShape::Sprite, None) => continue,
+                };
+
+                let mut collider = Collider::new(
+                    CollisionTag::for_entity(entity, hitbox.layer),
+                    shape,
+                    transform.position(),
+                    hitbox.layer,
+                    hitbox.collides_with,
+                );
+
+                if hitbox.is_swept {
+                    collider = collider.moving_from(transform.previous_position);
+                }
+
+                collision_world.add(collider);
+            }
+        }
+
+        for (bunker_index, bunker) in self.bunkers.iter().enumerate() {
+            collision_world.add(Collider::new(
+                CollisionTag::Bunker(bunker_index),
+                ColliderShape::Mask(Rc::new(bunker.collision_mask())),
+                bunker.center(),
+                BUNKER_LAYER,
+                0,
+            ));
+        }
 
-                    if alien.x >= (canvas.viewport().width() - self.alien_data.width / 2) as f32 {
-                        switch_alien_direction = true;
+        if let Some(boss) = self.boss.as_ref().filter(|boss| boss.is_vulnerable()) {
+            for (part_index, part) in boss.parts.iter().enumerate() {
+                if part.is_destroyed() {
+                    continue;
+                }
+
+                collision_world.add(Collider::new(
+                    CollisionTag::BossPart(part_index),
+                    ColliderShape::Aabb {
+                        width: part.size.0 as f32,
+                        height: part.size.1 as f32,
+                    },
+                    (boss.x + part.offset.0, boss.y + part.offset.1),
+                    BOSS_LAYER,
+                    0,
+                ));
+            }
+        }
+
+        let collision_events = collision_world.detect().to_vec();
+
+        for event in collision_events {
+            match (event.first, event.second) {
+                (CollisionTag::PlayerBullet(bullet), CollisionTag::Alien(alien)) => {
+                    self.on_alien_shot(bullet, alien, sound_channel)
+                }
+                (CollisionTag::PlayerBullet(bullet), CollisionTag::BossPart(part_index)) => {
+                    self.on_boss_part_shot(bullet, part_index, sound_channel)
+                }
+                (CollisionTag::PlayerBullet(bullet), CollisionTag::Bunker(bunker_index))
+                | (CollisionTag::AlienBullet(bullet), CollisionTag::Bunker(bunker_index)) => {
+                    let is_from_below = matches!(event.first, CollisionTag::PlayerBullet(_));
+                    let bullet_rect = match self.world.read::<Sprite>().get(bullet) {
+                        Some(sprite) => sprite.rect_at(event.first_position),
+                        None => continue,
+                    };
+                    let mut lifetimes = self.world.write::<Lifetime>();
+
+                    if let Some(lifetime) = lifetimes.get_mut(bullet) {
+                        if !lifetime.is_expired {
+                            lifetime.is_expired = hit_bunker(
+                                &mut self.bunkers[bunker_index],
+                                &mut self.particle_system,
+                                bullet_rect,
+                                is_from_below,
+                            );
+                        }
                     }
                 }
-                AlienDirection::Down => {
-                    alien.y += movement;
+                (CollisionTag::AlienBullet(bullet), CollisionTag::Spaceship) => {
+                    self.on_spaceship_shot(bullet, sound_channel)
                 }
+                (CollisionTag::Alien(alien), CollisionTag::Spaceship) => {
+                    self.on_spaceship_rammed(alien, sound_channel)
+                }
+                (CollisionTag::Alien(alien), CollisionTag::Bunker(bunker_index)) => {
+                    let transforms = self.world.read::<Transform>();
+                    let sprites = self.world.read::<Sprite>();
+
+                    if let (Some(transform), Some(sprite)) =
+                        (transforms.get(alien), sprites.get(alien))
+                    {
+                        self.bunkers[bunker_index].erode(sprite.rect_at(transform.position()));
+                    }
+                }
+                (CollisionTag::Spaceship, CollisionTag::PowerUp(drop)) => {
+                    self.on_power_up_collected(drop)
+                }
+                _ => (),
             }
+        }
+    }
 
-            alien.shoot_delay -= delta_time;
+    fn refresh_texture_data(&mut self, textures: &[Texture]) {
+        let sprite_sheets = animation::create_sprite_sheets(
+            &self.texture_names,
+            textures,
+            &self.animation_definitions,
+        );
 
-            if alien.shoot_delay <= 0.0 {
-                alien.shoot_delay = ALIEN_SHOOT_INTERVAL;
+        let spaceship_sprite_sheet = &sprite_sheets[self.spaceship.texture_index];
 
-                self.alien_data.bullets.push(Bullet {
-                    x: alien.x as f32,
-                    y: alien.y as f32,
-                    has_hit_something: false,
-                });
+        self.spaceship.size = (
+            spaceship_sprite_sheet.frame_width,
+            spaceship_sprite_sheet.frame_height,
+        );
 
-                sound_channel
-                    .play(self.alien_data.shoot_sound.as_ref().unwrap(), 0)
-                    .unwrap();
-            }
+        self.alien_data.alien_types.resolve_sizes(&sprite_sheets);
+        self.world.insert_resource(sprite_sheets);
 
-            let alien_rect = sdl2::rect::Rect::from_center(
-                sdl2::rect::Point::new(alien.x as i32, alien.y as i32),
-                self.alien_data.width,
-                self.alien_data.height,
-            );
+        let background_texture_data = textures[self.background_texture_index].query();
+        self.background_size = (
+            background_texture_data.width,
+            background_texture_data.height,
+        );
 
-            for bullet in &mut self.spaceship.bullets {
-                let bullet_rect = sdl2::rect::Rect::from_center(
-                    sdl2::rect::Point::new(bullet.x as i32, bullet.y as i32),
-                    self.spaceship.bullet_data.width,
-                    self.spaceship.bullet_data.height,
-                );
+        {
+            let mut bullet_sprites = self.world.resource_mut::<BulletSprites>();
+            let BulletSprites { player, alien } = &mut *bullet_sprites;
 
-                if alien_rect.intersection(bullet_rect).is_some() {
-                    alien.is_hit = true;
-                    bullet.has_hit_something = true;
-                    self.score += ALIEN_BASE_POINTS + (self.current_level - 1);
+            for bullet_data in [player, alien] {
+                let bullet_texture_data = textures[bullet_data.texture_index].query();
 
-                    sound_channel
-                        .play(self.alien_data.death_sound.as_ref().unwrap(), 0)
-                        .unwrap();
-                }
+                bullet_data.width = bullet_texture_data.width;
+                bullet_data.height = bullet_texture_data.height;
             }
+        }
 
-            if alien_rect.intersection(self.spaceship.rect).is_some() {
-                alien.is_hit = true;
-                self.spaceship.is_hit = true;
-                self.score += ALIEN_BASE_POINTS + (self.current_level - 1);
-                self.level_reset_timeout = LEVEL_RESET_TIME;
+        if self.collision_settings.use_pixel_masks {
+            self.create_sprite_masks();
+        }
+    }
 
-                sound_channel
-                    .play(self.alien_data.death_sound.as_ref().unwrap(), 0)
-                    .unwrap();
+    fn resize_sprites(&mut self) {
+        let mut sizes = HashMap::new();
+        sizes.insert(self.spaceship.texture_index, self.spaceship.size);
+
+        for bullet_data in [
+            self.world.resource::<BulletSprites>().player,
+            self.world.resource::<BulletSprites>().alien,
+        ] {
+            sizes.insert(
+                bullet_data.texture_index,
+                (bullet_data.width, bullet_data.height),
+            );
+        }
 
-                sound_channel
-                    .play(self.spaceship.death_sound.as_ref().unwrap(), 0)
-                    .unwrap();
+        for alien_type in &self.alien_data.alien_types.types {
+            sizes.insert(
+                alien_type.texture_index,
+                (alien_type.width, alien_type.height),
+            );
+        }
+
+        for (_, sprite) in self.world.write::<Sprite>().iter_mut() {
+            if let Some(size) = sizes.get(&sprite.texture_index) {
+                sprite.size = *size;
             }
+        }
+    }
 
-            if alien_rect.y() as u32 + alien_rect.height() >= canvas.viewport().height() {
-                self.alien_data.has_hit_bottom = true;
-                self.level_reset_timeout = LEVEL_RESET_TIME;
+    fn texture_indices(&self) -> HashMap<String, usize> {
+        self.texture_names
+            .iter()
+            .enumerate()
+            .map(|(texture_index, texture_name)| (texture_name.clone(), texture_index))
+            .collect()
+    }
 
-                sound_channel
-                    .play(self.alien_data.pass_sound.as_ref().unwrap(), 0)
-                    .unwrap();
+    fn create_sprite_masks(&mut self) {
+        let bullet_sprites = self.world.resource::<BulletSprites>();
+        let mut texture_indices = vec![
+            self.spaceship.texture_index,
+            bullet_sprites.player.texture_index,
+            bullet_sprites.alien.texture_index,
+        ];
+        texture_indices.extend(
+            self.alien_data
+                .alien_types
+                .types
+                .iter()
+                .map(|alien_type| alien_type.texture_index),
+        );
+
+        for texture_index in texture_indices {
+            let texture_filepath =
+                Path::new(TEXTURES_DIRECTORY).join(&self.texture_names[texture_index]);
+
+            match collision::create_sprite_masks(
+                &texture_filepath,
+                &self.world.resource::<Vec<SpriteSheet>>()[texture_index],
+                self.collision_settings.alpha_threshold,
+            ) {
+                Ok(masks) => {
+                    self.sprite_masks.insert(texture_index, masks);
+                }
+                Err(error) => log_warn!(
+                    "Could not create collision mask for {}: {}",
+                    texture_filepath.display(),
+                    error
+                ),
             }
         }
+    }
 
-        if self.alien_data.direction == AlienDirection::Down {
-            if self.alien_data.dropdown_distance > 0.0 {
-                self.alien_data.dropdown_distance -= movement;
-            } else {
-                self.alien_data.direction = self.alien_data.next_direction.unwrap();
-                self.alien_data.next_direction = None;
+    fn on_alien_shot(&mut self, bullet: Entity, alien: Entity, sound_channel: &Channel) {
+        if self.is_expired(bullet) || systems::is_dying(&self.world, alien) {
+            return;
+        }
+
+        let (type_index, (alien_x, alien_y)) = match (
+            self.world.read::<Ai>().get(alien),
+            self.world.read::<Transform>().get(alien),
+        ) {
+            (Some(Ai::Fleet { type_index }), Some(transform)) => {
+                (*type_index, transform.position())
             }
+            _ => return,
+        };
+        let alien_type = &self.alien_data.alien_types.types[type_index];
+
+        let is_piercing = self
+            .world
+            .read::<Projectile>()
+            .get(bullet)
+            .is_some_and(|projectile| projectile.is_piercing);
+
+        if !is_piercing {
+            self.expire(bullet);
         }
 
-        if switch_alien_direction {
-            self.alien_data.next_direction = match self.alien_data.direction {
-                AlienDirection::Left => Some(AlienDirection::Right),
-                AlienDirection::Right => Some(AlienDirection::Left),
-                _ => unreachable!(),
-            };
+        systems::kill(&self.world, alien, self.explosion_texture_index);
+        self.director.record_hit();
+        self.particle_system
+            .burst("alien-explosion", alien_x, alien_y);
+        self.score += self
+            .difficulty
+            .scaled_score(alien_type.points + (self.current_level - 1));
+
+        let power_up_drop = self.power_up_table.roll_drop(
+            self.current_level,
+            self.power_up_drop_multiplier(),
+            &mut self.world.resource_mut::<SpaceRng>().0,
+        );
+
+        if let Some(type_index) = power_up_drop {
+            systems::spawn_power_up(&mut self.world, (alien_x, alien_y), type_index);
+        }
+
+        sound_channel
+            .play(self.alien_data.death_sound.as_ref().unwrap(), 0)
+            .unwrap();
+    }
 
-            self.alien_data.velocity += ALIEN_VELOCITY_INCREMENT;
-            self.alien_data.direction = AlienDirection::Down;
-            self.alien_data.dropdown_distance = ALIEN_DROPDOWN_DISTANCE;
+    fn on_boss_part_shot(&mut self, bullet: Entity, part_index: usize, sound_channel: &Channel) {
+        let is_bullet_expired = self.is_expired(bullet);
+        let bullet_position = match self.world.read::<Transform>().get(bullet) {
+            Some(transform) => transform.position(),
+            None => return,
+        };
+        let boss = match &mut self.boss {
+            Some(boss) => boss,
+            None => return,
+        };
+
+        if is_bullet_expired || !boss.is_vulnerable() || boss.parts[part_index].is_destroyed() {
+            return;
+        }
+
+        if let Some(lifetime) = self.world.write::<Lifetime>().get_mut(bullet) {
+            lifetime.is_expired = true;
+        }
+
+        self.director.record_hit();
+        self.particle_system
+            .burst("boss-hit", bullet_position.0, bullet_position.1);
+        self.camera.add_trauma(BOSS_HIT_TRAUMA);
+
+        if boss.damage(part_index) {
+            let part_rect = boss.part_rect(&boss.parts[part_index]);
+
+            self.score += self.difficulty.scaled_score(boss.parts[part_index].points);
+            self.camera.add_trauma(BOSS_PART_DESTROYED_TRAUMA);
+            self.particle_system.burst(
+                "alien-explosion",
+                part_rect.center().x() as f32,
+                part_rect.center().y() as f32,
+            );
 
             sound_channel
-                .play(self.alien_data.shift_sound.as_ref().unwrap(), 0)
+                .play(self.alien_data.death_sound.as_ref().unwrap(), 0)
                 .unwrap();
         }
+    }
+
+    fn on_spaceship_shot(&mut self, bullet: Entity, sound_channel: &Channel) {
+        if self.is_expired(bullet) || self.is_spaceship_hit() {
+            return;
+        }
 
-        for bullet in &mut self.alien_data.bullets {
-            bullet.y += delta_time * BULLET_VELOCITY;
+        self.expire(bullet);
 
-            let bullet_rect = sdl2::rect::Rect::from_center(
-                sdl2::rect::Point::new(bullet.x as i32, bullet.y as i32),
-                self.spaceship.bullet_data.width,
-                self.spaceship.bullet_data.height,
-            );
+        if !self.is_spaceship_invulnerable() {
+            self.kill_spaceship(sound_channel);
+        }
+    }
 
-            if bullet_rect.intersection(self.spaceship.rect).is_some() {
-                self.spaceship.is_hit = true;
-                self.level_reset_timeout = LEVEL_RESET_TIME;
-                bullet.has_hit_something = true;
+    fn on_spaceship_rammed(&mut self, alien: Entity, sound_channel: &Channel) {
+        if systems::is_dying(&self.world, alien) || self.is_spaceship_hit() {
+            return;
+        }
 
-                sound_channel
-                    .play(self.spaceship.death_sound.as_ref().unwrap(), 0)
-                    .unwrap();
+        let (type_index, (alien_x, alien_y)) = match (
+            self.world.read::<Ai>().get(alien),
+            self.world.read::<Transform>().get(alien),
+        ) {
+            (Some(Ai::Fleet { type_index }), Some(transform)) => {
+                (*type_index, transform.position())
             }
+            _ => return,
+        };
+        let alien_type = &self.alien_data.alien_types.types[type_index];
+
+        systems::kill(&self.world, alien, self.explosion_texture_index);
+        self.particle_system
+            .burst("alien-explosion", alien_x, alien_y);
+        self.score += self
+            .difficulty
+            .scaled_score(alien_type.points + (self.current_level - 1));
+
+        sound_channel
+            .play(self.alien_data.death_sound.as_ref().unwrap(), 0)
+            .unwrap();
+
+        if !self.is_spaceship_invulnerable() {
+            self.kill_spaceship(sound_channel);
+        }
+    }
+
+    fn is_spaceship_invulnerable(&self) -> bool {
+        self.is_god_mode
+            || self
+                .active_power_ups
+                .is_active(PowerUpKind::Shield, &self.power_up_table)
+    }
+
+    fn on_power_up_collected(&mut self, drop: Entity) {
+        if self.is_expired(drop) || self.is_spaceship_hit() {
+            return;
+        }
+
+        let type_index = match self.world.read::<PowerUpDrop>().get(drop) {
+            Some(power_up_drop) => power_up_drop.type_index,
+            None => return,
+        };
+
+        self.expire(drop);
+        self.apply_power_up(type_index);
+    }
+
+    fn apply_power_up(&mut self, type_index: usize) {
+        if self.power_up_table.types[type_index].kind == PowerUpKind::ExtraLife {
+            self.player_lives += 1;
+        }
+
+        self.active_power_ups
+            .apply(type_index, &self.power_up_table);
+    }
+
+    fn kill_all_aliens(&mut self) -> usize {
+        let aliens: Vec<Entity> = self
+            .world
+            .read::<Ai>()
+            .iter()
+            .filter(|(_, ai)| matches!(ai, Ai::Fleet { .. }))
+            .map(|(entity, _)| entity)
+            .filter(|entity| !systems::is_dying(&self.world, *entity))
+            .collect();
+
+        for alien in &aliens {
+            systems::kill(&self.world, *alien, self.explosion_texture_index);
+        }
+
+        self.boss = None;
+
+        aliens.len()
+    }
+
+    fn kill_spaceship(&mut self, sound_channel: &Channel) {
+        let (spaceship_x, spaceship_y) = self.spaceship_position();
+
+        systems::kill(
+            &self.world,
+            self.spaceship.entity,
+            self.explosion_texture_index,
+        );
+        self.level_reset_timeout = LEVEL_RESET_TIME;
+        self.particle_system
+            .burst("player-debris", spaceship_x, spaceship_y);
+        self.camera.add_trauma(PLAYER_DEATH_TRAUMA);
+
+        sound_channel
+            .play(self.spaceship.death_sound.as_ref().unwrap(), 0)
+            .unwrap();
+    }
+
+    fn is_expired(&self, entity: Entity) -> bool {
+        self.world
+            .read::<Lifetime>()
+            .get(entity)
+            .map_or(true, |lifetime| lifetime.is_expired)
+    }
+
+    fn expire(&self, entity: Entity) {
+        if let Some(lifetime) = self.world.write::<Lifetime>().get_mut(entity) {
+            lifetime.is_expired = true;
+        }
+    }
+
+    fn update_particles(&mut self, delta_time: f32) {
+        profile_scope!("space_scene::particles");
+
+        self.thruster_emitter
+            .update(delta_time, &mut self.particle_system);
+        self.particle_system.update(delta_time);
+
+        if let Some(starfield) = &mut self.starfield {
+            starfield.update(delta_time);
         }
     }
 
@@ -352,181 +1174,338 @@ impl<'a> SpaceScene<'a> {
         }
     }
 
-    fn draw_background(&self, canvas: &mut WindowCanvas, background_texture: &Texture) {
-        let background_texture_data = background_texture.query();
+    fn draw_background(&self, renderer: &mut Renderer) {
+        if self.starfield_mode == StarfieldMode::Replace {
+            return;
+        }
 
-        let background_rect = Rect::new(
-            0,
+        let (background_width, background_height) = self.background_size;
+
+        for background_y in &[
             self.background_offset as i32,
-            background_texture_data.width,
-            background_texture_data.height,
-        );
+            self.background_offset as i32 - background_height as i32,
+        ] {
+            renderer.draw_sprite(
+                Layer::Background,
+                SpriteCommand::new(
+                    self.background_texture_index,
+                    None,
+                    Rect::new(0, *background_y, background_width, background_height),
+                ),
+            );
+        }
+    }
 
-        canvas
-            .copy(background_texture, None, background_rect)
-            .unwrap();
+    fn draw_sprite(&self, renderer: &mut Renderer, layer: Layer, entity: Entity) {
+        let sprite_sheets = self.world.resource::<Vec<SpriteSheet>>();
+        let transforms = self.world.read::<Transform>();
+        let sprites = self.world.read::<Sprite>();
 
-        let background_rect = Rect::new(
-            0,
-            self.background_offset as i32 - background_texture_data.height as i32,
-            background_texture_data.width,
-            background_texture_data.height,
+        let (transform, sprite) = match (transforms.get(entity), sprites.get(entity)) {
+            (Some(transform), Some(sprite)) => (transform, sprite),
+            _ => return,
+        };
+
+        if systems::is_dying(&self.world, entity) && sprite.animator.is_finished {
+            return;
+        }
+
+        let sprite_sheet = &sprite_sheets[sprite.texture_index];
+        let sprite_rect = Rect::from_center(
+            Point::new(transform.x as i32, transform.y as i32),
+            sprite_sheet.frame_width,
+            sprite_sheet.frame_height,
         );
 
-        canvas
-            .copy(background_texture, None, background_rect)
-            .unwrap();
+        let rotation = match self.world.read::<Velocity>().get(entity) {
+            Some(velocity)
+                if self.world.read::<Projectile>().contains(entity) && velocity.y != 0.0 =>
+            {
+                -(velocity.x / velocity.y).atan().to_degrees() as f64
+            }
+            _ => 0.0,
+        };
+
+        renderer.draw_sprite(
+            layer,
+            SpriteCommand::new(
+                sprite.texture_index,
+                sprite.animator.source_rect(sprite_sheet),
+                sprite_rect,
+            )
+            .with_rotation(rotation),
+        );
     }
 
-    fn draw_bullets(
-        &self,
-        canvas: &mut WindowCanvas,
-        bullet_texture: &Texture,
-        alien_bullet_texture: &Texture,
-    ) {
-        for bullet in &self.alien_data.bullets {
-            let bullet_rect = sdl2::rect::Rect::from_center(
-                sdl2::rect::Point::new(bullet.x as i32, bullet.y as i32),
-                self.spaceship.bullet_data.width,
-                self.spaceship.bullet_data.height,
-            );
+    fn draw_bullets(&self, renderer: &mut Renderer) {
+        let bullets: Vec<Entity> = self
+            .world
+            .read::<Projectile>()
+            .iter()
+            .map(|(entity, _)| entity)
+            .collect();
 
-            canvas
-                .copy(alien_bullet_texture, None, bullet_rect)
-                .unwrap();
+        for entity in bullets {
+            self.draw_sprite(renderer, Layer::Playfield, entity);
+        }
+    }
+
+    fn draw_aliens(&self, renderer: &mut Renderer) {
+        let aliens: Vec<Entity> = self
+            .world
+            .read::<Ai>()
+            .iter()
+            .filter(|(_, ai)| matches!(ai, Ai::Fleet { .. }))
+            .map(|(entity, _)| entity)
+            .collect();
+
+        for entity in aliens {
+            self.draw_sprite(renderer, Layer::Playfield, entity);
         }
+    }
+
+    fn draw_boss(&self, renderer: &mut Renderer) {
+        let boss = match &self.boss {
+            Some(boss) => boss,
+            None => return,
+        };
+
+        let sprite_sheets = self.world.resource::<Vec<SpriteSheet>>();
+        let alpha = (boss.opacity() * 255.0) as u8;
 
-        for bullet in &self.spaceship.bullets {
-            let bullet_rect = sdl2::rect::Rect::from_center(
-                sdl2::rect::Point::new(bullet.x as i32, bullet.y as i32),
-                self.spaceship.bullet_data.width,
-                self.spaceship.bullet_data.height,
+        for (part_index, part) in boss.parts.iter().enumerate() {
+            if part.is_destroyed() && !(part_index == 0 && boss.state == BossState::Outro) {
+                continue;
+            }
+
+            let damage_tint = (BOSS_DAMAGE_TINT_MIN as f32
+                + (255 - BOSS_DAMAGE_TINT_MIN) as f32 * part.health as f32 / part.max_health as f32)
+                as u8;
+
+            renderer.draw_sprite(
+                Layer::Playfield,
+                SpriteCommand::new(
+                    part.texture_index,
+                    sprite_sheets[part.texture_index].frame_rect(0),
+                    boss.part_rect(part),
+                )
+                .with_tint(Colour::RGB(255, damage_tint, damage_tint))
+                .with_alpha(alpha)
+                .with_flip(part.offset.0 > 0.0, false),
             );
+        }
+    }
 
-            canvas.copy(bullet_texture, None, bullet_rect).unwrap();
+    fn draw_boss_overlay(&self, renderer: &mut Renderer) {
+        const HEALTH_BAR_SIZE: (u32, u32) = (400, 16);
+        const HEALTH_BAR_Y: i32 = 56;
+        const TEXT_SCALE: f32 = 0.35;
+
+        let (boss, boss_definition) = match (&self.boss, &self.boss_definition) {
+            (Some(boss), Some(boss_definition)) => (boss, boss_definition),
+            _ => return,
+        };
+
+        let viewport = renderer.viewport();
+        let health_bar_rect = Rect::new(
+            (viewport.width() - HEALTH_BAR_SIZE.0) as i32 / 2,
+            HEALTH_BAR_Y,
+            HEALTH_BAR_SIZE.0,
+            HEALTH_BAR_SIZE.1,
+        );
+
+        renderer.fill_rect(Layer::Interface, health_bar_rect, Colour::RGB(80, 0, 0));
+
+        if boss.core().health > 0 {
+            renderer.fill_rect(
+                Layer::Interface,
+                Rect::new(
+                    health_bar_rect.x(),
+                    health_bar_rect.y(),
+                    (HEALTH_BAR_SIZE.0 as f32 * boss.health_fraction()) as u32,
+                    HEALTH_BAR_SIZE.1,
+                ),
+                Colour::RED,
+            );
         }
+
+        renderer.draw_rect(Layer::Interface, health_bar_rect, Colour::WHITE);
+
+        let banner_string = match boss.state {
+            BossState::Intro if (boss.state_timer * 4.0) as i32 % 2 == 0 => {
+                format!("WARNING: {} approaching!", boss_definition.name)
+            }
+            BossState::Outro => format!(
+                "{} destroyed! +{}",
+                boss_definition.name,
+                boss_definition.bonus_points * boss.appearance
+            ),
+            _ => return,
+        };
+
+        renderer.draw_text(
+            Layer::Interface,
+            TextCommand::new(
+                banner_string,
+                self.font_index,
+                Colour::RED,
+                Point::new(viewport.width() as i32 / 2, viewport.height() as i32 / 2),
+            )
+            .with_scale(TEXT_SCALE),
+        );
+    }
+
+    fn draw_level_message(&self, renderer: &mut Renderer) {
+        const TEXT_SCALE: f32 = 0.3;
+
+        let message = match &self.level_message {
+            Some((message, _)) => message,
+            None => return,
+        };
+
+        let viewport = renderer.viewport();
+
+        renderer.draw_text(
+            Layer::Interface,
+            TextCommand::new(
+                message.as_str(),
+                self.font_index,
+                Colour::CYAN,
+                Point::new(viewport.width() as i32 / 2, viewport.height() as i32 / 3),
+            )
+            .with_scale(TEXT_SCALE),
+        );
     }
 
-    fn draw_aliens(&self, canvas: &mut WindowCanvas, alien_texture: &Texture) {
-        for alien in &self.aliens {
-            let alien_rect = sdl2::rect::Rect::from_center(
-                sdl2::rect::Point::new(alien.x as i32, alien.y as i32),
-                self.alien_data.width,
-                self.alien_data.height,
+    fn draw_power_ups(&self, renderer: &mut Renderer) {
+        const LABEL_SCALE: f32 = 1.0 / 6.0;
+
+        let transforms = self.world.read::<Transform>();
+
+        for (entity, drop) in self.world.read::<PowerUpDrop>().iter() {
+            let transform = match transforms.get(entity) {
+                Some(transform) => transform,
+                None => continue,
+            };
+            let power_up_type = &self.power_up_table.types[drop.type_index];
+            let drop_rect = Rect::from_center(
+                Point::new(transform.x as i32, transform.y as i32),
+                POWER_UP_SIZE,
+                POWER_UP_SIZE,
             );
 
-            canvas.copy(alien_texture, None, alien_rect).unwrap();
+            renderer.fill_rect(Layer::Foreground, drop_rect, power_up_type.colour);
+            renderer.draw_rect(Layer::Foreground, drop_rect, Colour::WHITE);
+            renderer.draw_text(
+                Layer::Foreground,
+                TextCommand::new(
+                    power_up_type.label.as_str(),
+                    self.font_index,
+                    Colour::BLACK,
+                    drop_rect.center(),
+                )
+                .with_scale(LABEL_SCALE),
+            );
         }
-    }
 
-    fn draw_spaceship(&self, canvas: &mut WindowCanvas, spaceship_texture: &Texture) {
-        if !self.spaceship.is_hit {
-            canvas
-                .copy(spaceship_texture, None, self.spaceship.rect)
-                .unwrap();
+        if self
+            .active_power_ups
+            .is_active(PowerUpKind::Shield, &self.power_up_table)
+            && !self.is_spaceship_hit()
+        {
+            let (spaceship_x, spaceship_y) = self.spaceship_position();
+            let shield_rect = Rect::from_center(
+                Point::new(spaceship_x as i32, spaceship_y as i32),
+                self.spaceship.size.0 + 16,
+                self.spaceship.size.1 + 16,
+            );
+
+            renderer.draw_rect(Layer::Foreground, shield_rect, Colour::CYAN);
         }
     }
 
-    fn draw_text_overlay(
-        &self,
-        canvas: &mut WindowCanvas,
-        texture_creator: &TextureCreator<sdl2::video::WindowContext>,
-        font: &Font,
-    ) {
+    fn draw_power_up_timers(&self, renderer: &mut Renderer, font: &Font) {
         const OVERLAY_OFFSET: i32 = 10;
-        const TEXT_SCALE: u32 = 4;
+        const TEXT_SCALE: f32 = 0.2;
+        const FIRST_TIMER_Y: i32 = 48;
 
-        let lives_text = font
-            .render(format!("Lives: {}", self.player_lives).as_str())
-            .blended(Colour::RED)
-            .unwrap();
-        let lives_texture = texture_creator
-            .create_texture_from_surface(lives_text)
-            .unwrap();
-        let lives_texture_data = lives_texture.query();
+        let timer_x = renderer.viewport().width() as i32 - OVERLAY_OFFSET;
+        let line_height = (font.height() as f32 * TEXT_SCALE) as i32;
+        let mut timer_y = FIRST_TIMER_Y;
 
-        canvas
-            .copy(
-                &lives_texture,
-                None,
-                Rect::new(
-                    OVERLAY_OFFSET,
-                    OVERLAY_OFFSET,
-                    lives_texture_data.width / TEXT_SCALE,
-                    lives_texture_data.height / TEXT_SCALE,
-                ),
-            )
-            .unwrap();
+        for power_up in &self.active_power_ups.power_ups {
+            let power_up_type = &self.power_up_table.types[power_up.type_index];
 
-        let level_text = font
-            .render(format!("Level: {}", self.current_level).as_str())
-            .blended(Colour::WHITE)
-            .unwrap();
-        let level_texture = texture_creator
-            .create_texture_from_surface(level_text)
-            .unwrap();
-        let level_texture_data = level_texture.query();
+            let timer_string = if power_up.stacks > 1 {
+                format!(
+                    "{} x{}: {:.1}s",
+                    power_up_type.display_name, power_up.stacks, power_up.remaining_time
+                )
+            } else {
+                format!(
+                    "{}: {:.1}s",
+                    power_up_type.display_name, power_up.remaining_time
+                )
+            };
 
-        canvas
-            .copy(
-                &level_texture,
-                None,
-                Rect::new(
-                    OVERLAY_OFFSET,
-                    OVERLAY_OFFSET * 2 + lives_texture_data.height as i32 / TEXT_SCALE as i32,
-                    level_texture_data.width / TEXT_SCALE,
-                    level_texture_data.height / TEXT_SCALE,
-                ),
-            )
-            .unwrap();
+            renderer.draw_text(
+                Layer::Interface,
+                TextCommand::new(
+                    timer_string,
+                    self.font_index,
+                    power_up_type.colour,
+                    Point::new(timer_x, timer_y),
+                )
+                .with_anchor(Anchor::TopRight)
+                .with_scale(TEXT_SCALE),
+            );
 
-        let aliens_text = font
-            .render(format!("Aliens: {}", self.aliens.len()).as_str())
-            .blended(Colour::GREEN)
-            .unwrap();
-        let aliens_texture = texture_creator
-            .create_texture_from_surface(aliens_text)
-            .unwrap();
-        let aliens_texture_data = aliens_texture.query();
-
-        canvas
-            .copy(
-                &aliens_texture,
-                None,
-                Rect::from_center(
-                    Point::new(
-                        canvas.viewport().width() as i32 / 2,
-                        OVERLAY_OFFSET + aliens_texture_data.height as i32 / TEXT_SCALE as i32 / 2,
-                    ),
-                    aliens_texture_data.width / TEXT_SCALE,
-                    aliens_texture_data.height / TEXT_SCALE,
-                ),
-            )
-            .unwrap();
+            timer_y += line_height + OVERLAY_OFFSET / 2;
+        }
+    }
 
-        let score_text = font
-            .render(format!("Score: {}", self.score).as_str())
-            .blended(Colour::YELLOW)
-            .unwrap();
-        let score_texture = texture_creator
-            .create_texture_from_surface(score_text)
-            .unwrap();
-        let score_texture_data = score_texture.query();
+    fn draw_text_overlay(&self, renderer: &mut Renderer, font: &Font) {
+        const OVERLAY_OFFSET: i32 = 10;
+        const TEXT_SCALE: f32 = 0.25;
 
-        canvas
-            .copy(
-                &score_texture,
-                None,
-                Rect::new(
-                    canvas.viewport().width() as i32
-                        - OVERLAY_OFFSET
-                        - score_texture_data.width as i32 / TEXT_SCALE as i32,
-                    OVERLAY_OFFSET,
-                    score_texture_data.width / TEXT_SCALE,
-                    score_texture_data.height / TEXT_SCALE,
-                ),
-            )
-            .unwrap();
+        profile_scope!("space_scene::text_overlay");
+
+        let viewport_width = renderer.viewport().width() as i32;
+        let line_height = (font.height() as f32 * TEXT_SCALE) as i32;
+
+        for (text, colour, position, anchor) in [
+            (
+                format!("Lives: {}", self.player_lives),
+                Colour::RED,
+                Point::new(OVERLAY_OFFSET, OVERLAY_OFFSET),
+                Anchor::TopLeft,
+            ),
+            (
+                format!("Level: {} ({})", self.current_level, self.difficulty.name),
+                Colour::WHITE,
+                Point::new(OVERLAY_OFFSET, OVERLAY_OFFSET * 2 + line_height),
+                Anchor::TopLeft,
+            ),
+            (
+                format!("Aliens: {}", systems::alien_count(&self.world, false)),
+                Colour::GREEN,
+                Point::new(viewport_width / 2, OVERLAY_OFFSET + line_height / 2),
+                Anchor::Center,
+            ),
+            (
+                format!("Score: {}", self.score),
+                Colour::YELLOW,
+                Point::new(viewport_width - OVERLAY_OFFSET, OVERLAY_OFFSET),
+                Anchor::TopRight,
+            ),
+        ] {
+            renderer.draw_text(
+                Layer::Interface,
+                TextCommand::new(text, self.font_index, colour, position)
+                    .with_anchor(anchor)
+                    .with_scale(TEXT_SCALE),
+            );
+        }
     }
 }
 
@@ -535,117 +1514,355 @@ impl Scene for SpaceScene<'_> {
         self.is_done
     }
 
+    fn transition(&self) -> Transition {
+        if self.test_level.is_some() {
+            Transition::new(TransitionEffect::Cut, 0.0)
+        } else {
+            Transition::new(TransitionEffect::Wipe, TRANSITION_TIME)
+        }
+    }
+
     fn on_load(
         &mut self,
         sdl_context: &sdl2::Sdl,
-        _canvas: &WindowCanvas,
+        canvas: &WindowCanvas,
         _previous_scene_payload: Option<i32>,
-    ) -> (Vec<String>, Vec<String>) {
+    ) -> ResourcePaths {
         let mut textures = vec![];
+        let mut texture_indices = HashMap::new();
         let mut fonts = vec![];
+        let mut bullet_sprites = BulletSprites::default();
 
-        for (current_index, texture_file) in fs::read_dir("assets/textures").unwrap().enumerate() {
-            let texture_file = texture_file.unwrap();
-            let texture_filepath = texture_file.path();
-            let texture_filepath_string = texture_filepath
-                .file_name()
-                .unwrap()
-                .to_str()
-                .unwrap()
-                .to_owned();
+        for (current_index, texture_filepath) in resources::directory_paths(TEXTURES_DIRECTORY)
+            .into_iter()
+            .enumerate()
+        {
+            let texture_filepath_string = resources::file_name(&texture_filepath);
 
             match texture_filepath_string.as_ref() {
                 "ship.png" => self.spaceship.texture_index = current_index,
-                "bullet.png" => self.spaceship.bullet_data.texture_index = current_index,
-                "alien.png" => self.alien_data.texture_index = current_index,
-                "alien_bullet.png" => self.alien_data.bullet_data.texture_index = current_index,
+                "bullet.png" => bullet_sprites.player.texture_index = current_index,
+                "alien_bullet.png" => bullet_sprites.alien.texture_index = current_index,
                 "background.png" => self.background_texture_index = current_index,
+                "explosion.png" => self.explosion_texture_index = current_index,
                 _ => (),
             }
 
-            textures.push(texture_filepath.to_str().unwrap().to_owned());
+            texture_indices.insert(texture_filepath_string.clone(), current_index);
+            self.texture_names.push(texture_filepath_string);
+            textures.push(texture_filepath);
+        }
+
+        self.world.insert_resource(bullet_sprites);
+        self.animation_definitions = animation::read_animations_file(ANIMATIONS_FILE_NAME).unwrap();
+        self.power_up_table = power_up::read_power_ups_file().unwrap();
+
+        let mut boss_definition = boss::read_boss_file().unwrap();
+        boss_definition
+            .resolve_texture_indices(&texture_indices)
+            .unwrap();
+        self.boss_definition = Some(boss_definition);
+
+        self.alien_data.alien_types = alien_type::read_alien_types_file().unwrap();
+        self.alien_data
+            .alien_types
+            .resolve_texture_indices(&texture_indices)
+            .unwrap();
+
+        if let Some((level, _)) = &self.test_level {
+            self.levels = vec![level.clone()];
+        } else {
+            let (levels, level_errors) = level::read_levels(&self.alien_data.alien_types);
+            self.levels = levels;
+
+            for level_error in level_errors {
+                log_warn!("{}", level_error);
+            }
         }
 
-        for (current_index, font_file) in fs::read_dir("assets/fonts").unwrap().enumerate() {
-            let font_file = font_file.unwrap();
-            let font_filepath = font_file.path();
-            let font_filepath_string = font_filepath
-                .file_name()
-                .unwrap()
-                .to_str()
-                .unwrap()
-                .to_owned();
+        self.director = Director::new(director::read_director_settings().unwrap());
+        self.collision_settings = collision::read_collision_settings().unwrap();
+        self.camera = Camera::new(camera::read_camera_settings().unwrap());
+
+        for (current_index, font_filepath) in resources::directory_paths(FONTS_DIRECTORY)
+            .into_iter()
+            .enumerate()
+        {
+            let font_filepath_string = resources::file_name(&font_filepath);
 
             if font_filepath_string == "Recursive.ttf" {
                 self.font_index = current_index;
             }
 
-            fonts.push(font_filepath.to_str().unwrap().to_owned());
+            fonts.push(font_filepath);
         }
 
-        for sound_file in fs::read_dir("assets/sounds/effects/game").unwrap() {
-            let sound_file = sound_file.unwrap();
-            let sound_filepath = sound_file.path();
-            let sound_filepath_string = sound_filepath
-                .file_name()
-                .unwrap()
-                .to_str()
-                .unwrap()
-                .to_owned();
+        let sounds = resources::directory_paths(SOUNDS_DIRECTORY);
 
-            let loaded_sound_chunk = Some(Chunk::from_file(sound_filepath).unwrap());
+        let particle_settings = particles::read_particle_settings(PARTICLES_FILE_NAME).unwrap();
+        self.starfield_mode = particle_settings.starfield_mode;
 
-            match sound_filepath_string.as_ref() {
-                "player_shoot.wav" => {
-                    self.spaceship.shoot_sound = loaded_sound_chunk;
-                }
-                "player_death.wav" => {
-                    self.spaceship.death_sound = loaded_sound_chunk;
-                }
-                "alien_shoot.wav" => {
-                    self.alien_data.shoot_sound = loaded_sound_chunk;
-                }
-                "alien_death.wav" => {
-                    self.alien_data.death_sound = loaded_sound_chunk;
-                }
-                "alien_pass.wav" => {
-                    self.alien_data.pass_sound = loaded_sound_chunk;
+        let mut particle_budget = particle_settings.max_count;
+
+        if self.starfield_mode != StarfieldMode::Off {
+            let starfield = Starfield::new(
+                &particle_settings.star_layers,
+                particle_budget / 2,
+                (canvas.viewport().width(), canvas.viewport().height()),
+            );
+
+            particle_budget -= starfield.star_count();
+            self.starfield = Some(starfield);
+        }
+
+        self.particle_system = ParticleSystem::new(particle_budget, particle_settings.emitters);
+
+        sdl_context.mouse().show_cursor(false);
+        self.music = Some(
+            Music::from_static_bytes(vfs::read_static("assets/sounds/music/Werq.mp3").unwrap())
+                .unwrap(),
+        );
+
+        ResourcePaths {
+            textures,
+            fonts,
+            sounds,
+        }
+    }
+
+    fn on_sounds_loaded(&mut self, mut sounds: HashMap<String, Chunk>) {
+        self.spaceship.shoot_sound = sounds.remove("player_shoot.wav");
+        self.spaceship.death_sound = sounds.remove("player_death.wav");
+        self.alien_data.shoot_sound = sounds.remove("alien_shoot.wav");
+        self.alien_data.death_sound = sounds.remove("alien_death.wav");
+        self.alien_data.pass_sound = sounds.remove("alien_pass.wav");
+        self.alien_data.shift_sound = sounds.remove("alien_shift.wav");
+        self.level_win_sound = sounds.remove("level_win.wav");
+    }
+
+    fn on_late_load(&mut self, canvas: &WindowCanvas, textures: &[Texture], _fonts: &[Font]) {
+        self.refresh_texture_data(textures);
+
+        self.setup_objects(canvas);
+        self.music.as_ref().unwrap().play(-1).unwrap();
+    }
+
+    fn on_textures_reloaded(&mut self, textures: &[Texture]) {
+        self.refresh_texture_data(textures);
+        self.resize_sprites();
+    }
+
+    fn on_file_changed(&mut self, path: &Path, textures: &[Texture]) -> Result<(), Box<dyn Error>> {
+        if path
+            .extension()
+            .is_some_and(|extension| extension == "level")
+        {
+            if self.test_level.is_none() {
+                let (levels, level_errors) = level::read_levels(&self.alien_data.alien_types);
+
+                if !level_errors.is_empty() {
+                    let messages: Vec<String> =
+                        level_errors.iter().map(|error| error.to_string()).collect();
+
+                    return Err(messages.join("; ").into());
                 }
-                "alien_shift.wav" => {
-                    self.alien_data.shift_sound = loaded_sound_chunk;
+
+                self.levels = levels;
+            }
+
+            return Ok(());
+        }
+
+        match path.file_name().and_then(|file_name| file_name.to_str()) {
+            Some("config.json") => {
+                self.director
+                    .set_settings(director::read_director_settings()?);
+                self.collision_settings = collision::read_collision_settings()?;
+                self.camera = Camera::new(camera::read_camera_settings()?);
+            }
+            Some("animations.json") => {
+                self.animation_definitions = animation::read_animations_file(ANIMATIONS_FILE_NAME)?;
+                self.refresh_texture_data(textures);
+                self.resize_sprites();
+            }
+            Some("aliens.json") => {
+                let mut alien_types = alien_type::read_alien_types_file()?;
+                alien_types.resolve_texture_indices(&self.texture_indices())?;
+
+                if alien_types.types.len() != self.alien_data.alien_types.types.len() {
+                    return Err("alien types can only be edited, not added or removed".into());
                 }
-                "level_win.wav" => {
-                    self.level_win_sound = loaded_sound_chunk;
+
+                self.alien_data.alien_types = alien_types;
+                self.refresh_texture_data(textures);
+                self.resize_sprites();
+            }
+            Some("boss.json") => {
+                let mut boss_definition = boss::read_boss_file()?;
+                boss_definition.resolve_texture_indices(&self.texture_indices())?;
+                self.boss_definition = Some(boss_definition);
+            }
+            Some("power_ups.json") => {
+                let power_up_table = power_up::read_power_ups_file()?;
+
+                if power_up_table.types.len() != self.power_up_table.types.len() {
+                    return Err("power-ups can only be edited, not added or removed".into());
                 }
-                _ => (),
+
+                self.power_up_table = power_up_table;
             }
+            _ => (),
         }
 
-        sdl_context.mouse().show_cursor(false);
-        self.music = Some(Music::from_file("assets/sounds/music/Werq.mp3").unwrap());
+        Ok(())
+    }
 
-        (textures, fonts)
+    fn console_commands(&self) -> Vec<ConsoleCommand> {
+        CONSOLE_COMMANDS.to_vec()
     }
 
-    fn on_late_load(&mut self, canvas: &WindowCanvas, textures: &[Texture], _fonts: &[Font]) {
-        let spaceship_texture_data = &textures[self.spaceship.texture_index].query();
-        let bullet_texture_data = &textures[self.spaceship.bullet_data.texture_index].query();
-        let alien_texture_data = &textures[self.alien_data.texture_index].query();
-        let alien_bullet_texture_data =
-            &textures[self.alien_data.bullet_data.texture_index].query();
+    fn console_variables(&self) -> Vec<ConsoleVariable> {
+        vec![
+            ConsoleVariable {
+                name: SPACESHIP_VELOCITY_VARIABLE,
+                default: SPACESHIP_VELOCITY,
+            },
+            ConsoleVariable {
+                name: ALIEN_SHOOT_INTERVAL_VARIABLE,
+                default: ALIEN_SHOOT_INTERVAL,
+            },
+        ]
+    }
 
-        self.spaceship_size = (spaceship_texture_data.width, spaceship_texture_data.height);
+    fn run_console_command(
+        &mut self,
+        name: &str,
+        arguments: &[&str],
+        canvas: &WindowCanvas,
+    ) -> Result<String, String> {
+        let parse_count = |argument: &str| {
+            argument
+                .parse::<u32>()
+                .ok()
+                .filter(|count| *count > 0)
+                .ok_or_else(|| format!("\"{}\" is not a positive whole number.", argument))
+        };
+
+        match (name, arguments) {
+            ("god", []) => {
+                self.is_god_mode = !self.is_god_mode;
+
+                Ok(format!(
+                    "God mode {}.",
+                    if self.is_god_mode { "on" } else { "off" }
+                ))
+            }
+            ("level", [level]) => {
+                self.current_level = parse_count(level)?;
+                self.restart_level(canvas);
 
-        self.spaceship.bullet_data.width = bullet_texture_data.width;
-        self.spaceship.bullet_data.height = bullet_texture_data.height;
+                Ok(format!("Jumped to level {}.", self.current_level))
+            }
+            ("spawn", [BOSS_SPAWN_NAME]) | ("spawn", [BOSS_SPAWN_ALIAS]) => {
+                if self.boss.is_some() {
+                    return Err("The boss is already here.".to_string());
+                }
 
-        self.alien_data.width = alien_texture_data.width;
-        self.alien_data.height = alien_texture_data.height;
-        self.alien_data.bullet_data.width = alien_bullet_texture_data.width;
-        self.alien_data.bullet_data.height = alien_bullet_texture_data.height;
+                let boss_name = self
+                    .boss_definition
+                    .as_ref()
+                    .map(|boss_definition| boss_definition.name.clone())
+                    .ok_or("No boss is defined.")?;
 
-        self.setup_objects(canvas);
-        self.music.as_ref().unwrap().play(-1).unwrap();
+                self.spawn_boss(canvas);
+
+                Ok(format!("Spawned the {}.", boss_name))
+            }
+            ("spawn", [type_name]) | ("spawn", [type_name, _]) => {
+                let type_index =
+                    self.alien_data
+                        .alien_types
+                        .index_of(type_name)
+                        .ok_or_else(|| {
+                            let mut type_names: Vec<&str> = self
+                                .alien_data
+                                .alien_types
+                                .types
+                                .iter()
+                                .map(|alien_type| alien_type.name.as_str())
+                                .collect();
+                            type_names.push(BOSS_SPAWN_NAME);
+
+                            format!(
+                                "Unknown alien type \"{}\". Try one of: {}.",
+                                type_name,
+                                type_names.join(", ")
+                            )
+                        })?;
+                let count = arguments.get(1).map_or(Ok(1), |count| parse_count(count))?;
+
+                self.spawn_reinforcements(type_index, count, canvas);
+
+                Ok(format!("Spawned {} {}.", count, type_name))
+            }
+            ("lives", [lives]) => {
+                self.player_lives = parse_count(lives)?;
+
+                Ok(format!("Lives set to {}.", self.player_lives))
+            }
+            ("kill_all", []) => Ok(format!("Killed {} aliens.", self.kill_all_aliens())),
+            ("give", [power_up_name]) => {
+                let kind = PowerUpKind::from_name(power_up_name)
+                    .ok_or_else(|| format!("Unknown power-up \"{}\".", power_up_name))?;
+                let type_index = self
+                    .power_up_table
+                    .types
+                    .iter()
+                    .position(|power_up| power_up.kind == kind)
+                    .ok_or_else(|| {
+                        format!("No power-up of kind \"{}\" is defined.", kind.name())
+                    })?;
+
+                self.apply_power_up(type_index);
+
+                Ok(format!(
+                    "Gave {}.",
+                    self.power_up_table.types[type_index].display_name
+                ))
+            }
+            ("seed", [seed]) => {
+                self.rng_seed = seed
+                    .parse()
+                    .map_err(|_| format!("\"{}\" is not a valid seed.", seed))?;
+                self.world
+                    .insert_resource(SpaceRng(StdRng::seed_from_u64(self.rng_seed)));
+                self.restart_level(canvas);
+
+                Ok(format!(
+                    "Restarted level {} with seed {}.",
+                    self.current_level, self.rng_seed
+                ))
+            }
+            _ => Err(CONSOLE_COMMANDS
+                .iter()
+                .find(|command| command.name == name)
+                .map_or_else(
+                    || format!("Unknown command \"{}\".", name),
+                    |command| format!("Usage: {}", command.usage),
+                )),
+        }
+    }
+
+    fn on_console_variable_changed(&mut self, name: &str, value: f32) {
+        match name {
+            SPACESHIP_VELOCITY_VARIABLE => self.spaceship_velocity = value,
+            ALIEN_SHOOT_INTERVAL_VARIABLE => {
+                self.alien_shoot_interval = f32::max(value, 0.01);
+                self.world.resource_mut::<Fleet>().fire_interval_scale =
+                    self.alien_shoot_interval / ALIEN_SHOOT_INTERVAL;
+            }
+            _ => (),
+        }
     }
 
     fn on_unload(&mut self, sdl_context: &sdl2::Sdl) -> Option<i32> {
@@ -703,30 +1920,32 @@ impl Scene for SpaceScene<'_> {
             return;
         }
 
-        if self.is_done && !self.spaceship.is_hit {
+        if self.is_done && !self.is_spaceship_hit() && !self.exit_to_editor(scene_queue) {
             scene_queue.push_back(Box::new(MainMenuScene::new()));
         }
 
         if self.level_reset_timeout <= 0.0 {
-            self.update_spaceship(delta_time, canvas, sound_channel);
-            self.update_aliens(delta_time, canvas, sound_channel);
-            self.update_background(delta_time, canvas);
+            let enemy_time_scale = self.active_power_ups.enemy_time_scale(&self.power_up_table);
+            let enemy_delta_time = delta_time * enemy_time_scale;
 
-            let bullet_delete_threshold = -2.0 * self.spaceship.bullet_data.height as f32;
-            self.spaceship
-                .bullets
-                .retain(|bullet| bullet.y > bullet_delete_threshold && !bullet.has_hit_something);
+            self.world.insert_resource(EnemyTimeScale(enemy_time_scale));
 
-            self.aliens.retain(|alien| !alien.is_hit);
-            let bullet_delete_threshold =
-                canvas.viewport().height() as f32 - bullet_delete_threshold;
-            self.alien_data
-                .bullets
-                .retain(|bullet| bullet.y < bullet_delete_threshold && !bullet.has_hit_something);
+            self.update_objects(delta_time, sound_channel);
+            self.update_boss(enemy_delta_time, canvas, sound_channel);
+            self.active_power_ups.update(delta_time);
+            self.update_collisions(sound_channel);
+            self.update_level_events(enemy_delta_time, canvas);
+            self.update_background(delta_time, canvas);
+            self.director.update(delta_time);
+
+            systems::despawn_system(&mut self.world);
 
-            if self.aliens.is_empty() {
-                self.score += LEVEL_COMPLETE_POINTS * self.current_level;
+            if self.is_level_clear() {
+                self.score += self
+                    .difficulty
+                    .scaled_score(LEVEL_COMPLETE_POINTS * self.current_level);
                 self.level_reset_timeout = LEVEL_RESET_TIME;
+                self.camera.punch_zoom(LEVEL_CLEAR_ZOOM);
                 sound_channel
                     .play(self.level_win_sound.as_ref().unwrap(), 0)
                     .unwrap();
@@ -734,6 +1953,10 @@ impl Scene for SpaceScene<'_> {
         } else {
             self.level_reset_timeout -= delta_time;
         }
+
+        systems::animation_system(&mut self.world, delta_time);
+        self.update_particles(delta_time);
+        self.camera.update(delta_time);
     }
 
     fn late_update(
@@ -744,47 +1967,159 @@ impl Scene for SpaceScene<'_> {
         _sound_channel: &Channel,
     ) {
         if self.level_reset_timeout <= 0.0 {
-            if self.aliens.is_empty() {
+            if self.is_level_clear() && self.exit_to_editor(scene_queue) {
+                return;
+            }
+
+            if self.is_level_clear() {
+                self.director.on_level_clear(self.current_level);
                 self.current_level += 1;
                 self.player_lives += 1;
 
                 self.setup_objects(canvas);
-            } else if self.spaceship.is_hit || self.alien_data.has_hit_bottom {
+            } else if self.is_spaceship_hit() || self.world.resource::<Fleet>().has_hit_bottom {
                 self.player_lives -= 1;
+                self.active_power_ups.clear();
+                self.director.on_player_death(self.current_level);
 
                 if self.player_lives > 0 {
                     self.setup_objects(canvas);
-                } else {
+                } else if !self.exit_to_editor(scene_queue) {
                     self.is_done = true;
-                    scene_queue.push_back(Box::new(GameOverScene::new()));
+                    scene_queue.push_back(Box::new(GameOverScene::new(
+                        self.difficulty.clone(),
+                        self.current_level,
+                    )));
                 }
             }
         }
     }
 
-    fn draw(
-        &mut self,
-        canvas: &mut WindowCanvas,
-        texture_creator: &TextureCreator<sdl2::video::WindowContext>,
-        textures: &[sdl2::render::Texture],
-        fonts: &[Font],
-    ) {
+    fn draw(&mut self, renderer: &mut Renderer, fonts: &[Font]) {
         if !self.has_window_focus {
             return;
         }
 
-        canvas.set_draw_color(BACKGROUND_COLOUR);
-        canvas.clear();
+        let font = &fonts[self.font_index];
 
-        self.draw_background(canvas, &textures[self.background_texture_index]);
-        self.draw_bullets(
-            canvas,
-            &textures[self.spaceship.bullet_data.texture_index],
-            &textures[self.alien_data.bullet_data.texture_index],
-        );
-        self.draw_aliens(canvas, &textures[self.alien_data.texture_index]);
-        self.draw_spaceship(canvas, &textures[self.spaceship.texture_index]);
+        renderer.clear(BACKGROUND_COLOUR);
+        renderer.set_camera(self.camera.transform());
+
+        self.draw_background(renderer);
+
+        if let Some(starfield) = &self.starfield {
+            starfield.draw(renderer);
+        }
+
+        self.draw_bullets(renderer);
+        self.draw_aliens(renderer);
+
+        for bunker in &self.bunkers {
+            bunker.draw(renderer);
+        }
+
+        self.draw_boss(renderer);
+        self.draw_sprite(renderer, Layer::Foreground, self.spaceship.entity);
+        self.draw_power_ups(renderer);
+        self.particle_system.draw(renderer);
+
+        self.draw_text_overlay(renderer, font);
+        self.draw_power_up_timers(renderer, font);
+        self.draw_boss_overlay(renderer);
+        self.draw_level_message(renderer);
+    }
+
+    fn draw_hitboxes(&self, renderer: &mut Renderer) {
+        renderer.set_camera(self.camera.transform());
+        self.collision_world.draw_debug(renderer, Colour::CYAN);
+    }
+
+    fn debug_lines(&self) -> Vec<String> {
+        let fleet = self.world.resource::<Fleet>();
+        let projectiles = self.world.read::<Projectile>();
+        let bullet_count = |faction: Faction| {
+            projectiles
+                .iter()
+                .filter(|(_, projectile)| projectile.faction == faction)
+                .count()
+        };
+
+        let mut lines = vec![
+            format!(
+                "Level: {}  Seed: {}  God mode: {}",
+                self.current_level,
+                self.rng_seed,
+                if self.is_god_mode { "on" } else { "off" }
+            ),
+            format!(
+                "Aliens: {}  Player bullets: {}  Alien bullets: {}  Colliders: {}",
+                systems::alien_count(&self.world, false),
+                bullet_count(Faction::Player),
+                bullet_count(Faction::Alien),
+                self.collision_world.collider_count()
+            ),
+            format!("Particles: {}", self.particle_system.active_count()),
+            format!(
+                "Fleet velocity: {:.1}  Direction: {:?}",
+                fleet.velocity, fleet.direction
+            ),
+            format!(
+                "Director: {}  Accuracy: {:.0}%  Skill: {:+.2}",
+                if self.director.is_enabled() {
+                    "enabled"
+                } else {
+                    "disabled"
+                },
+                self.director.accuracy() * 100.0,
+                self.director.skill
+            ),
+            format!(
+                "Fire interval x{:.2}  Velocity increment x{:.2}  Drop chance x{:.2}",
+                self.director.fire_interval_multiplier,
+                self.director.velocity_increment_multiplier,
+                self.director.power_up_drop_multiplier
+            ),
+        ];
+        lines.extend(self.director.decisions.iter().cloned());
+
+        lines
+    }
+}
+
+fn hit_bunker(
+    bunker: &mut Bunker,
+    particle_system: &mut ParticleSystem,
+    bullet_rect: Rect,
+    is_from_below: bool,
+) -> bool {
+    match bunker.hit(bullet_rect, is_from_below) {
+        Some(block_rect) => {
+            particle_system.burst(
+                "bunker-impact",
+                block_rect.center().x() as f32,
+                block_rect.center().y() as f32,
+            );
+
+            true
+        }
+        None => false,
+    }
+}
 
-        self.draw_text_overlay(canvas, texture_creator, &fonts[self.font_index]);
+fn sprite_collider_shape(
+    sprite_masks: &HashMap<usize, Vec<Rc<PixelMask>>>,
+    texture_index: usize,
+    frame: u32,
+    size: (u32, u32),
+) -> ColliderShape {
+    match sprite_masks
+        .get(&texture_index)
+        .and_then(|masks| masks.get(frame as usize))
+    {
+        Some(mask) => ColliderShape::Mask(mask.clone()),
+        None => ColliderShape::Aabb {
+            width: size.0 as f32,
+            height: size.1 as f32,
+        },
     }
 }
@@ -1,15 +1,18 @@
-use rand::Rng;
+pub mod alien_type;
+
 use sdl2::mixer::Chunk;
 
-use super::spaceship::bullet::{Bullet, BulletData};
+use self::alien_type::AlienTypeTable;
 
-pub const ALIEN_ROW_COUNT: u32 = 4;
 pub const INITIAL_ALIEN_VELOCITY: f32 = 100.0;
 pub const PER_LEVEL_ALIEN_VELOCITY_INCREASE: f32 = 20.0;
 pub const ALIEN_VELOCITY_INCREMENT: f32 = 10.0;
 pub const ALIEN_DROPDOWN_DISTANCE: f32 = 40.0;
 pub const ALIEN_SHOOT_INTERVAL: f32 = 10.0;
 pub const ALIEN_BASE_POINTS: u32 = 25;
+pub const ALIEN_STEP_DISTANCE: f32 = 20.0;
+pub const DEFAULT_MAX_ALIEN_BULLETS: usize = 3;
+pub const DEFAULT_TARGETED_SHOT_CHANCE: f32 = 0.4;
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum AlienDirection {
@@ -18,43 +21,51 @@ pub enum AlienDirection {
     Down,
 }
 
-#[derive(Debug)]
-pub struct Alien {
-    pub x: f32,
-    pub y: f32,
-
-    pub shoot_delay: f32,
-    pub is_hit: bool,
-}
+pub struct AlienData {
+    pub alien_types: AlienTypeTable,
+    pub fire_interval: Option<f32>,
 
-impl Alien {
-    pub fn new(x: f32, y: f32) -> Alien {
-        Alien {
-            x,
-            y,
-            shoot_delay: rand::thread_rng().gen::<f32>() * ALIEN_SHOOT_INTERVAL,
-            is_hit: false,
-        }
-    }
+    pub shoot_sound: Option<Chunk>,
+    pub death_sound: Option<Chunk>,
+    pub pass_sound: Option<Chunk>,
+    pub shift_sound: Option<Chunk>,
 }
 
-pub struct AlienData {
-    pub width: u32,
-    pub height: u32,
-
+pub struct Fleet {
     pub velocity: f32,
+    pub velocity_increment: f32,
+    pub level_dropdown_distance: f32,
+    pub fire_delay: f32,
+    pub fire_interval_scale: f32,
+    pub max_bullets: usize,
+    pub targeted_shot_chance: f32,
+    pub bullet_velocity_multiplier: f32,
+    pub column_width: f32,
     pub direction: AlienDirection,
     pub next_direction: Option<AlienDirection>,
     pub dropdown_distance: f32,
+    pub step_distance: f32,
 
     pub has_hit_bottom: bool,
+}
 
-    pub texture_index: usize,
-    pub bullet_data: BulletData,
-    pub bullets: Vec<Bullet>,
-
-    pub shoot_sound: Option<Chunk>,
-    pub death_sound: Option<Chunk>,
-    pub pass_sound: Option<Chunk>,
-    pub shift_sound: Option<Chunk>,
+impl Default for Fleet {
+    fn default() -> Fleet {
+        Fleet {
+            velocity: INITIAL_ALIEN_VELOCITY,
+            velocity_increment: ALIEN_VELOCITY_INCREMENT,
+            level_dropdown_distance: ALIEN_DROPDOWN_DISTANCE,
+            fire_delay: 0.0,
+            fire_interval_scale: 1.0,
+            max_bullets: DEFAULT_MAX_ALIEN_BULLETS,
+            targeted_shot_chance: DEFAULT_TARGETED_SHOT_CHANCE,
+            bullet_velocity_multiplier: 1.0,
+            column_width: 1.0,
+            direction: AlienDirection::Right,
+            next_direction: None,
+            dropdown_distance: 0.0,
+            step_distance: 0.0,
+            has_hit_bottom: false,
+        }
+    }
 }
@@ -0,0 +1,190 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs;
+
+use super::{ALIEN_BASE_POINTS, ALIEN_SHOOT_INTERVAL};
+use crate::game::animation::SpriteSheet;
+use crate::game::vfs;
+use crate::scenes::space_scene::spaceship::bullet::BulletKind;
+
+const ALIEN_TYPES_FILE_NAME: &str = "config/aliens.json";
+
+#[derive(Debug)]
+pub struct AlienType {
+    pub name: String,
+    pub symbol: char,
+
+    pub width: u32,
+    pub height: u32,
+    pub hitbox_override: Option<(u32, u32)>,
+
+    pub points: u32,
+    pub shoot_interval: f32,
+    pub bullet_kind: BulletKind,
+
+    pub texture_name: String,
+    pub texture_index: usize,
+}
+
+pub struct AlienTypeTable {
+    pub types: Vec<AlienType>,
+    pub row_types: Vec<usize>,
+}
+
+impl AlienTypeTable {
+    pub fn resolve_texture_indices(
+        &mut self,
+        texture_indices: &HashMap<String, usize>,
+    ) -> Result<(), Box<dyn Error>> {
+        for alien_type in &mut self.types {
+            alien_type.texture_index =
+                *texture_indices
+                    .get(&alien_type.texture_name)
+                    .ok_or_else(|| {
+                        format!(
+                            "Alien type \"{}\" uses unknown texture \"{}\".",
+                            alien_type.name, alien_type.texture_name
+                        )
+                    })?;
+        }
+
+        Ok(())
+    }
+
+    pub fn resolve_sizes(&mut self, sprite_sheets: &[SpriteSheet]) {
+        for alien_type in &mut self.types {
+            let alien_sprite_sheet = &sprite_sheets[alien_type.texture_index];
+
+            let (width, height) = alien_type.hitbox_override.unwrap_or((
+                alien_sprite_sheet.frame_width,
+                alien_sprite_sheet.frame_height,
+            ));
+            alien_type.width = width;
+            alien_type.height = height;
+        }
+    }
+
+    pub fn index_of(&self, name: &str) -> Option<usize> {
+        self.types
+            .iter()
+            .position(|alien_type| alien_type.name == name)
+    }
+
+    pub fn index_of_symbol(&self, symbol: char) -> Option<usize> {
+        self.types
+            .iter()
+            .position(|alien_type| alien_type.symbol == symbol)
+    }
+
+    pub fn cell_size(&self) -> (u32, u32) {
+        self.types
+            .iter()
+            .fold((0, 0), |(width, height), alien_type| {
+                (
+                    u32::max(width, alien_type.width),
+                    u32::max(height, alien_type.height),
+                )
+            })
+    }
+}
+
+pub fn read_alien_types_file() -> Result<AlienTypeTable, Box<dyn Error>> {
+    let json_string = fs::read_to_string(vfs::resolve(ALIEN_TYPES_FILE_NAME))?;
+    let alien_data: serde_json::Value = serde_json::from_str(&json_string[..])?;
+
+    let mut types = vec![];
+
+    for type_data in alien_data["types"]
+        .as_array()
+        .ok_or("Alien types file is missing a \"types\" array.")?
+    {
+        let name = type_data["name"]
+            .as_str()
+            .ok_or("Alien type is missing a name.")?
+            .to_string();
+
+        let bullet_name = type_data["bullet"].as_str().unwrap_or("standard");
+        let bullet_kind = BulletKind::from_name(bullet_name).ok_or_else(|| {
+            format!(
+                "Alien type \"{}\" has unknown bullet kind \"{}\".",
+                name, bullet_name
+            )
+        })?;
+
+        let symbol = type_data["symbol"]
+            .as_str()
+            .and_then(|symbol| symbol.chars().next())
+            .unwrap_or_else(|| name.chars().next().unwrap_or('?').to_ascii_uppercase());
+
+        if symbol == '.' || symbol.is_whitespace() {
+            return Err(format!("Alien type \"{}\" has invalid symbol '{}'.", name, symbol).into());
+        }
+
+        if types
+            .iter()
+            .any(|alien_type: &AlienType| alien_type.symbol == symbol)
+        {
+            return Err(format!(
+                "Alien type \"{}\" reuses formation symbol '{}'.",
+                name, symbol
+            )
+            .into());
+        }
+
+        let hitbox_override = if type_data["hitbox"].is_object() {
+            Some((
+                type_data["hitbox"]["x"].as_u64().ok_or_else(|| {
+                    format!("Alien type \"{}\" has an invalid hitbox width.", name)
+                })? as u32,
+                type_data["hitbox"]["y"].as_u64().ok_or_else(|| {
+                    format!("Alien type \"{}\" has an invalid hitbox height.", name)
+                })? as u32,
+            ))
+        } else {
+            None
+        };
+
+        types.push(AlienType {
+            texture_name: type_data["texture"]
+                .as_str()
+                .ok_or_else(|| format!("Alien type \"{}\" is missing a texture.", name))?
+                .to_string(),
+            name,
+            symbol,
+            width: 0,
+            height: 0,
+            hitbox_override,
+            points: type_data["points"]
+                .as_u64()
+                .map_or(ALIEN_BASE_POINTS, |points| points as u32),
+            shoot_interval: type_data["shoot-interval"]
+                .as_f64()
+                .map_or(ALIEN_SHOOT_INTERVAL, |interval| interval as f32),
+            bullet_kind,
+            texture_index: 0,
+        });
+    }
+
+    if types.is_empty() {
+        return Err("Alien types file must define at least one alien type.".into());
+    }
+
+    let mut table = AlienTypeTable {
+        types,
+        row_types: vec![],
+    };
+
+    for row_type in alien_data["rows"]
+        .as_array()
+        .ok_or("Alien types file is missing a \"rows\" array.")?
+    {
+        let row_type_name = row_type.as_str().unwrap_or_default();
+        let type_index = table
+            .index_of(row_type_name)
+            .ok_or_else(|| format!("Fleet row uses unknown alien type \"{}\".", row_type_name))?;
+
+        table.row_types.push(type_index);
+    }
+
+    Ok(table)
+}
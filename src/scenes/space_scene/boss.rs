@@ -0,0 +1,503 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs;
+
+use rand::Rng;
+use sdl2::rect::{Point, Rect};
+
+use super::spaceship::bullet::{Bullet, BulletKind, BULLET_VELOCITY};
+use crate::game::animation::SpriteSheet;
+use crate::game::random;
+use crate::game::vfs;
+
+const BOSS_FILE_NAME: &str = "config/boss.json";
+
+const BOSS_INTRO_TIME: f32 = 2.5;
+const BOSS_OUTRO_TIME: f32 = 2.5;
+const BOSS_OUTRO_EXPLOSION_INTERVAL: f32 = 0.15;
+const BOSS_TARGET_Y: f32 = 160.0;
+
+#[derive(Clone, Debug)]
+pub enum AttackPattern {
+    Aimed,
+    Spread { count: u32, angle: f32 },
+    Minions { count: u32, alien_type_name: String },
+}
+
+#[derive(Clone, Debug)]
+pub struct Attack {
+    pub pattern: AttackPattern,
+    pub interval: f32,
+}
+
+#[derive(Clone, Debug)]
+pub struct BossPhase {
+    pub health_threshold: f32,
+    pub velocity: f32,
+    pub attacks: Vec<Attack>,
+}
+
+#[derive(Clone, Debug)]
+pub struct BossPartDefinition {
+    pub texture_name: String,
+    pub texture_index: usize,
+
+    pub offset: (f32, f32),
+    pub scale: f32,
+    pub health: u32,
+    pub points: u32,
+}
+
+pub struct BossDefinition {
+    pub name: String,
+    pub level_interval: u32,
+    pub bonus_points: u32,
+    pub health_per_appearance: u32,
+
+    pub core: BossPartDefinition,
+    pub turrets: Vec<BossPartDefinition>,
+    pub phases: Vec<BossPhase>,
+}
+
+impl BossDefinition {
+    pub fn is_boss_level(&self, level: u32) -> bool {
+        self.level_interval > 0 && level % self.level_interval == 0
+    }
+
+    pub fn resolve_texture_indices(
+        &mut self,
+        texture_indices: &HashMap<String, usize>,
+    ) -> Result<(), Box<dyn Error>> {
+        for part in std::iter::once(&mut self.core).chain(self.turrets.iter_mut()) {
+            part.texture_index = *texture_indices.get(&part.texture_name).ok_or_else(|| {
+                format!("Boss part uses unknown texture \"{}\".", part.texture_name)
+            })?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BossState {
+    Intro,
+    Fighting,
+    Outro,
+    Defeated,
+}
+
+#[derive(Debug)]
+pub struct BossPart {
+    pub offset: (f32, f32),
+    pub size: (u32, u32),
+    pub texture_index: usize,
+
+    pub health: u32,
+    pub max_health: u32,
+    pub points: u32,
+}
+
+impl BossPart {
+    pub fn is_destroyed(&self) -> bool {
+        self.health == 0
+    }
+}
+
+pub enum BossAction {
+    Fire(Bullet),
+    SpawnMinions {
+        x: f32,
+        y: f32,
+        count: u32,
+        alien_type_name: String,
+    },
+    Explode {
+        x: f32,
+        y: f32,
+    },
+    PhaseChange,
+    Defeated,
+}
+
+pub struct Boss {
+    pub x: f32,
+    pub y: f32,
+    pub state: BossState,
+    pub state_timer: f32,
+    pub appearance: u32,
+
+    pub parts: Vec<BossPart>,
+    pub phase_index: usize,
+
+    direction: f32,
+    attack_timers: Vec<f32>,
+    explosion_timer: f32,
+}
+
+impl Boss {
+    pub fn new(
+        definition: &BossDefinition,
+        appearance: u32,
+        sprite_sheets: &[SpriteSheet],
+        viewport_width: u32,
+    ) -> Boss {
+        let create_part = |part_definition: &BossPartDefinition, health: u32| {
+            let sprite_sheet = &sprite_sheets[part_definition.texture_index];
+
+            BossPart {
+                offset: part_definition.offset,
+                size: (
+                    (sprite_sheet.frame_width as f32 * part_definition.scale) as u32,
+                    (sprite_sheet.frame_height as f32 * part_definition.scale) as u32,
+                ),
+                texture_index: part_definition.texture_index,
+                health,
+                max_health: health,
+                points: part_definition.points,
+            }
+        };
+
+        let core_health = definition.core.health
+            + appearance.saturating_sub(1) * definition.health_per_appearance;
+        let mut parts = vec![create_part(&definition.core, core_health)];
+
+        for turret in &definition.turrets {
+            parts.push(create_part(turret, turret.health));
+        }
+
+        let mut boss = Boss {
+            x: viewport_width as f32 / 2.0,
+            y: 0.0,
+            state: BossState::Intro,
+            state_timer: BOSS_INTRO_TIME,
+            appearance,
+            parts,
+            phase_index: 0,
+            direction: 1.0,
+            attack_timers: vec![],
+            explosion_timer: 0.0,
+        };
+
+        boss.y = -(boss.bounds().height() as f32);
+        boss.reset_attack_timers(definition);
+
+        boss
+    }
+
+    pub fn core(&self) -> &BossPart {
+        &self.parts[0]
+    }
+
+    pub fn health_fraction(&self) -> f32 {
+        if self.core().max_health == 0 {
+            return 0.0;
+        }
+
+        self.core().health as f32 / self.core().max_health as f32
+    }
+
+    pub fn part_rect(&self, part: &BossPart) -> Rect {
+        Rect::from_center(
+            Point::new(
+                (self.x + part.offset.0) as i32,
+                (self.y + part.offset.1) as i32,
+            ),
+            part.size.0,
+            part.size.1,
+        )
+    }
+
+    pub fn bounds(&self) -> Rect {
+        self.parts
+            .iter()
+            .map(|part| self.part_rect(part))
+            .fold(self.part_rect(self.core()), |bounds, part_rect| {
+                bounds.union(part_rect)
+            })
+    }
+
+    pub fn opacity(&self) -> f32 {
+        match self.state {
+            BossState::Outro => f32::max(self.state_timer / BOSS_OUTRO_TIME, 0.0),
+            _ => 1.0,
+        }
+    }
+
+    pub fn is_vulnerable(&self) -> bool {
+        self.state == BossState::Fighting
+    }
+
+    pub fn damage(&mut self, part_index: usize) -> bool {
+        let part = &mut self.parts[part_index];
+        part.health = part.health.saturating_sub(1);
+
+        if part.is_destroyed() && part_index == 0 {
+            self.state = BossState::Outro;
+            self.state_timer = BOSS_OUTRO_TIME;
+        }
+
+        part.is_destroyed()
+    }
+
+    pub fn update(
+        &mut self,
+        delta_time: f32,
+        definition: &BossDefinition,
+        player_position: (f32, f32),
+        viewport_width: u32,
+    ) -> Vec<BossAction> {
+        let mut actions = vec![];
+
+        match self.state {
+            BossState::Intro => {
+                self.state_timer -= delta_time;
+
+                let progress = 1.0 - f32::max(self.state_timer, 0.0) / BOSS_INTRO_TIME;
+                let start_y = -(self.bounds().height() as f32);
+                self.y = start_y + (BOSS_TARGET_Y - start_y) * progress;
+
+                if self.state_timer <= 0.0 {
+                    self.state = BossState::Fighting;
+                }
+            }
+            BossState::Fighting => {
+                self.update_phase(definition, &mut actions);
+                self.update_movement(delta_time, definition, viewport_width);
+                self.update_attacks(delta_time, definition, player_position, &mut actions);
+            }
+            BossState::Outro => {
+                self.state_timer -= delta_time;
+                self.explosion_timer -= delta_time;
+
+                if self.explosion_timer <= 0.0 {
+                    self.explosion_timer = BOSS_OUTRO_EXPLOSION_INTERVAL;
+
+                    let mut rng = random::cosmetic_rng();
+                    let bounds = self.bounds();
+
+                    actions.push(BossAction::Explode {
+                        x: bounds.x() as f32 + rng.gen::<f32>() * bounds.width() as f32,
+                        y: bounds.y() as f32 + rng.gen::<f32>() * bounds.height() as f32,
+                    });
+                }
+
+                if self.state_timer <= 0.0 {
+                    self.state = BossState::Defeated;
+                    actions.push(BossAction::Defeated);
+                }
+            }
+            BossState::Defeated => (),
+        }
+
+        actions
+    }
+
+    fn reset_attack_timers(&mut self, definition: &BossDefinition) {
+        self.attack_timers = definition.phases[self.phase_index]
+            .attacks
+            .iter()
+            .map(|attack| attack.interval)
+            .collect();
+    }
+
+    fn update_phase(&mut self, definition: &BossDefinition, actions: &mut Vec<BossAction>) {
+        let health_fraction = self.health_fraction();
+        let phase_index = definition
+            .phases
+            .iter()
+            .rposition(|phase| health_fraction <= phase.health_threshold)
+            .unwrap_or(0);
+
+        if phase_index != self.phase_index {
+            self.phase_index = phase_index;
+            self.reset_attack_timers(definition);
+
+            actions.push(BossAction::PhaseChange);
+        }
+    }
+
+    fn update_movement(
+        &mut self,
+        delta_time: f32,
+        definition: &BossDefinition,
+        viewport_width: u32,
+    ) {
+        let velocity = definition.phases[self.phase_index].velocity;
+        self.x += self.direction * velocity * delta_time;
+
+        let bounds = self.bounds();
+
+        if bounds.left() <= 0 {
+            self.direction = 1.0;
+        } else if bounds.right() >= viewport_width as i32 {
+            self.direction = -1.0;
+        }
+    }
+
+    fn update_attacks(
+        &mut self,
+        delta_time: f32,
+        definition: &BossDefinition,
+        player_position: (f32, f32),
+        actions: &mut Vec<BossAction>,
+    ) {
+        let core_position = (self.x + self.core().offset.0, self.y + self.core().offset.1);
+        let bullet_velocity = BULLET_VELOCITY * BulletKind::Standard.velocity_multiplier();
+        let minion_spawn_y = self.bounds().bottom() as f32;
+
+        let mut spread_origins: Vec<(f32, f32)> = self.parts[1..]
+            .iter()
+            .filter(|part| !part.is_destroyed())
+            .map(|part| (self.x + part.offset.0, self.y + part.offset.1))
+            .collect();
+
+        if spread_origins.is_empty() {
+            spread_origins.push(core_position);
+        }
+
+        for (attack, attack_timer) in definition.phases[self.phase_index]
+            .attacks
+            .iter()
+            .zip(self.attack_timers.iter_mut())
+        {
+            *attack_timer -= delta_time;
+
+            if *attack_timer > 0.0 {
+                continue;
+            }
+
+            *attack_timer = attack.interval;
+
+            match &attack.pattern {
+                AttackPattern::Aimed => {
+                    let mut bullet =
+                        Bullet::new(core_position.0, core_position.1, BulletKind::Standard);
+                    let y_distance = f32::max(player_position.1 - core_position.1, 1.0);
+                    bullet.x_velocity =
+                        (player_position.0 - core_position.0) / y_distance * bullet_velocity;
+
+                    actions.push(BossAction::Fire(bullet));
+                }
+                AttackPattern::Spread { count, angle } => {
+                    for origin in &spread_origins {
+                        for bullet_index in 0..*count {
+                            let bullet_angle = if *count > 1 {
+                                -angle / 2.0 + angle * bullet_index as f32 / (*count - 1) as f32
+                            } else {
+                                0.0
+                            };
+
+                            let mut bullet = Bullet::new(origin.0, origin.1, BulletKind::Standard);
+                            bullet.x_velocity = bullet_angle.to_radians().tan() * bullet_velocity;
+
+                            actions.push(BossAction::Fire(bullet));
+                        }
+                    }
+                }
+                AttackPattern::Minions {
+                    count,
+                    alien_type_name,
+                } => {
+                    actions.push(BossAction::SpawnMinions {
+                        x: core_position.0,
+                        y: minion_spawn_y,
+                        count: *count,
+                        alien_type_name: alien_type_name.clone(),
+                    });
+                }
+            }
+        }
+    }
+}
+
+fn read_part_definition(
+    part_data: &serde_json::Value,
+) -> Result<BossPartDefinition, Box<dyn Error>> {
+    Ok(BossPartDefinition {
+        texture_name: part_data["texture"]
+            .as_str()
+            .ok_or("Boss part is missing a texture.")?
+            .to_string(),
+        texture_index: 0,
+        offset: (
+            part_data["offset"]["x"].as_f64().unwrap_or(0.0) as f32,
+            part_data["offset"]["y"].as_f64().unwrap_or(0.0) as f32,
+        ),
+        scale: part_data["scale"].as_f64().unwrap_or(1.0) as f32,
+        health: part_data["health"].as_u64().unwrap_or(1) as u32,
+        points: part_data["points"].as_u64().unwrap_or(0) as u32,
+    })
+}
+
+fn read_attack(attack_data: &serde_json::Value) -> Result<Attack, Box<dyn Error>> {
+    let pattern_name = attack_data["pattern"]
+        .as_str()
+        .ok_or("Boss attack is missing a pattern.")?;
+
+    let pattern = match pattern_name {
+        "aimed" => AttackPattern::Aimed,
+        "spread" => AttackPattern::Spread {
+            count: attack_data["count"].as_u64().unwrap_or(3) as u32,
+            angle: attack_data["angle"].as_f64().unwrap_or(45.0) as f32,
+        },
+        "minions" => AttackPattern::Minions {
+            count: attack_data["count"].as_u64().unwrap_or(1) as u32,
+            alien_type_name: attack_data["alien-type"]
+                .as_str()
+                .ok_or("Minion attack is missing an alien type.")?
+                .to_string(),
+        },
+        _ => return Err(format!("Unknown boss attack pattern \"{}\".", pattern_name).into()),
+    };
+
+    Ok(Attack {
+        pattern,
+        interval: attack_data["interval"].as_f64().unwrap_or(1.0) as f32,
+    })
+}
+
+pub fn read_boss_file() -> Result<BossDefinition, Box<dyn Error>> {
+    let json_string = fs::read_to_string(vfs::resolve(BOSS_FILE_NAME))?;
+    let boss_data: serde_json::Value = serde_json::from_str(&json_string[..])?;
+
+    let turrets = boss_data["turrets"]
+        .as_array()
+        .unwrap_or(&vec![])
+        .iter()
+        .map(read_part_definition)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut phases = vec![];
+
+    for phase_data in boss_data["phases"]
+        .as_array()
+        .ok_or("Boss file is missing a \"phases\" array.")?
+    {
+        phases.push(BossPhase {
+            health_threshold: phase_data["health-threshold"].as_f64().unwrap_or(1.0) as f32,
+            velocity: phase_data["velocity"].as_f64().unwrap_or(0.0) as f32,
+            attacks: phase_data["attacks"]
+                .as_array()
+                .unwrap_or(&vec![])
+                .iter()
+                .map(read_attack)
+                .collect::<Result<Vec<_>, _>>()?,
+        });
+    }
+
+    if phases.is_empty() {
+        return Err("Boss must have at least one phase.".into());
+    }
+
+    phases.sort_by(|a, b| b.health_threshold.partial_cmp(&a.health_threshold).unwrap());
+
+    Ok(BossDefinition {
+        name: boss_data["name"].as_str().unwrap_or("Boss").to_string(),
+        level_interval: boss_data["level-interval"].as_u64().unwrap_or(0) as u32,
+        bonus_points: boss_data["bonus-points"].as_u64().unwrap_or(0) as u32,
+        health_per_appearance: boss_data["health-per-appearance"].as_u64().unwrap_or(0) as u32,
+        core: read_part_definition(&boss_data["core"])?,
+        turrets,
+        phases,
+    })
+}
@@ -0,0 +1,130 @@
+use sdl2::{pixels::Color as Colour, rect::Rect};
+
+use crate::game::collision::PixelMask;
+use crate::game::renderer::{Layer, Renderer};
+
+pub const BUNKER_BLOCK_SIZE: u32 = 8;
+pub const BUNKER_BOTTOM_OFFSET: u32 = 170;
+pub const BUNKER_COLOUR: Colour = Colour::RGB(76, 255, 0);
+pub const BUNKER_SHAPE: [&str; 6] = [
+    "..######..",
+    ".########.",
+    "##########",
+    "##########",
+    "###....###",
+    "##......##",
+];
+
+#[derive(Debug)]
+pub struct Bunker {
+    pub x: i32,
+    pub y: i32,
+
+    blocks: Vec<Vec<bool>>,
+}
+
+impl Bunker {
+    pub fn new(center_x: i32, center_y: i32) -> Bunker {
+        let (width, height) = Bunker::size();
+
+        Bunker {
+            x: center_x - width as i32 / 2,
+            y: center_y - height as i32 / 2,
+            blocks: BUNKER_SHAPE
+                .iter()
+                .map(|row| row.chars().map(|block| block == '#').collect())
+                .collect(),
+        }
+    }
+
+    pub fn at_position(position: f32, viewport_size: (u32, u32)) -> Bunker {
+        Bunker::new(
+            (position * viewport_size.0 as f32) as i32,
+            (viewport_size.1 - BUNKER_BOTTOM_OFFSET) as i32,
+        )
+    }
+
+    pub fn size() -> (u32, u32) {
+        (
+            BUNKER_SHAPE[0].len() as u32 * BUNKER_BLOCK_SIZE,
+            BUNKER_SHAPE.len() as u32 * BUNKER_BLOCK_SIZE,
+        )
+    }
+
+    pub fn rect(&self) -> Rect {
+        let (width, height) = Bunker::size();
+
+        Rect::new(self.x, self.y, width, height)
+    }
+
+    pub fn center(&self) -> (f32, f32) {
+        let center = self.rect().center();
+
+        (center.x() as f32, center.y() as f32)
+    }
+
+    pub fn collision_mask(&self) -> PixelMask {
+        PixelMask::from_rows(&self.blocks, BUNKER_BLOCK_SIZE)
+    }
+
+    fn block_rect(&self, row: usize, column: usize) -> Rect {
+        Rect::new(
+            self.x + (column as u32 * BUNKER_BLOCK_SIZE) as i32,
+            self.y + (row as u32 * BUNKER_BLOCK_SIZE) as i32,
+            BUNKER_BLOCK_SIZE,
+            BUNKER_BLOCK_SIZE,
+        )
+    }
+
+    pub fn hit(&mut self, rect: Rect, is_from_below: bool) -> Option<Rect> {
+        self.rect().intersection(rect)?;
+
+        let row_count = self.blocks.len();
+
+        for row_offset in 0..row_count {
+            let row = if is_from_below {
+                row_count - 1 - row_offset
+            } else {
+                row_offset
+            };
+
+            for column in 0..self.blocks[row].len() {
+                let block_rect = self.block_rect(row, column);
+
+                if self.blocks[row][column] && block_rect.intersection(rect).is_some() {
+                    self.blocks[row][column] = false;
+
+                    return Some(block_rect);
+                }
+            }
+        }
+
+        None
+    }
+
+    pub fn erode(&mut self, rect: Rect) {
+        if self.rect().intersection(rect).is_none() {
+            return;
+        }
+
+        for row in 0..self.blocks.len() {
+            for column in 0..self.blocks[row].len() {
+                if self.block_rect(row, column).intersection(rect).is_some() {
+                    self.blocks[row][column] = false;
+                }
+            }
+        }
+    }
+
+    pub fn draw(&self, renderer: &mut Renderer) {
+        for (row, blocks) in self.blocks.iter().enumerate() {
+            for (column, _) in blocks.iter().enumerate().filter(|(_, block)| **block) {
+                renderer.fill_rect(
+                    Layer::Playfield,
+                    self.block_rect(row, column),
+                    BUNKER_COLOUR,
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,79 @@
+use super::spaceship::bullet::BulletKind;
+
+pub const PLAYER_LAYER: u32 = 1 << 0;
+pub const PLAYER_BULLET_LAYER: u32 = 1 << 1;
+pub const ALIEN_LAYER: u32 = 1 << 2;
+pub const ALIEN_BULLET_LAYER: u32 = 1 << 3;
+pub const BUNKER_LAYER: u32 = 1 << 4;
+pub const BOSS_LAYER: u32 = 1 << 5;
+pub const POWER_UP_LAYER: u32 = 1 << 6;
+
+#[derive(Clone, Copy, Debug)]
+pub struct Player;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Faction {
+    Player,
+    Alien,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum HitboxShape {
+    Sprite,
+    Circle { radius: f32 },
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Hitbox {
+    pub layer: u32,
+    pub collides_with: u32,
+    pub shape: HitboxShape,
+    pub is_swept: bool,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Health {
+    pub points: u32,
+}
+
+impl Health {
+    pub fn is_depleted(&self) -> bool {
+        self.points == 0
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Weapon {
+    pub bullet_kind: BulletKind,
+    pub interval: f32,
+    pub cooldown: f32,
+    pub is_firing: bool,
+
+    pub spread: (u32, f32),
+    pub is_piercing: bool,
+}
+
+impl Weapon {
+    pub fn new(bullet_kind: BulletKind, interval: f32) -> Weapon {
+        Weapon {
+            bullet_kind,
+            interval,
+            cooldown: 0.0,
+            is_firing: false,
+            spread: (0, 0.0),
+            is_piercing: false,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum Ai {
+    Fleet { type_index: usize },
+    Zigzag { origin_x: f32 },
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Projectile {
+    pub faction: Faction,
+    pub is_piercing: bool,
+}
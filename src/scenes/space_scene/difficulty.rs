@@ -0,0 +1,138 @@
+use std::error::Error;
+use std::fs;
+
+use super::INITIAL_PLAYER_LIVES;
+use crate::game;
+use crate::game::logging::log_warn;
+
+const DIFFICULTY_FILE_NAME: &str = "config/difficulty.json";
+const CUSTOM_DIFFICULTY_FILE_NAME: &str = "custom_difficulty.json";
+
+const CUSTOM_DIFFICULTY_NAME: &str = "Custom";
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Difficulty {
+    pub name: String,
+
+    pub player_lives: u32,
+    pub alien_velocity_multiplier: f32,
+    pub alien_fire_interval_multiplier: f32,
+    pub alien_bullet_velocity_multiplier: f32,
+    pub power_up_drop_multiplier: f32,
+    pub score_multiplier: f32,
+}
+
+impl Default for Difficulty {
+    fn default() -> Difficulty {
+        Difficulty {
+            name: String::from("Normal"),
+            player_lives: INITIAL_PLAYER_LIVES,
+            alien_velocity_multiplier: 1.0,
+            alien_fire_interval_multiplier: 1.0,
+            alien_bullet_velocity_multiplier: 1.0,
+            power_up_drop_multiplier: 1.0,
+            score_multiplier: 1.0,
+        }
+    }
+}
+
+impl Difficulty {
+    pub fn scaled_score(&self, points: u32) -> u32 {
+        (points as f32 * self.score_multiplier).round() as u32
+    }
+}
+
+pub struct DifficultySettings {
+    pub presets: Vec<Difficulty>,
+    pub custom: Difficulty,
+}
+
+fn read_difficulty(difficulty_data: &serde_json::Value, name: &str) -> Difficulty {
+    let default = Difficulty::default();
+    let read_multiplier = |key: &str, default: f32| {
+        difficulty_data[key]
+            .as_f64()
+            .map_or(default, |multiplier| multiplier as f32)
+    };
+
+    Difficulty {
+        name: name.to_string(),
+        player_lives: difficulty_data["lives"]
+            .as_u64()
+            .map_or(default.player_lives, |lives| u64::max(lives, 1) as u32),
+        alien_velocity_multiplier: read_multiplier(
+            "alien-velocity",
+            default.alien_velocity_multiplier,
+        ),
+        alien_fire_interval_multiplier: read_multiplier(
+            "alien-fire-interval",
+            default.alien_fire_interval_multiplier,
+        ),
+        alien_bullet_velocity_multiplier: read_multiplier(
+            "alien-bullet-velocity",
+            default.alien_bullet_velocity_multiplier,
+        ),
+        power_up_drop_multiplier: read_multiplier(
+            "power-up-drop-chance",
+            default.power_up_drop_multiplier,
+        ),
+        score_multiplier: read_multiplier("score", default.score_multiplier),
+    }
+}
+
+pub fn read_difficulty_file() -> Result<DifficultySettings, Box<dyn Error>> {
+    let json_string = fs::read_to_string(DIFFICULTY_FILE_NAME)?;
+    let difficulty_data: serde_json::Value = serde_json::from_str(&json_string[..])?;
+
+    let mut presets = vec![];
+
+    for preset_data in difficulty_data["presets"]
+        .as_array()
+        .ok_or("Difficulty file is missing a \"presets\" array.")?
+    {
+        let name = preset_data["name"]
+            .as_str()
+            .ok_or("Difficulty preset is missing a name.")?;
+
+        presets.push(read_difficulty(preset_data, name));
+    }
+
+    let custom_difficulty_filepath = game::saves_directory().join(CUSTOM_DIFFICULTY_FILE_NAME);
+    let custom_data = match fs::read_to_string(&custom_difficulty_filepath) {
+        Ok(json_string) => serde_json::from_str(&json_string[..]).unwrap_or_else(|error| {
+            log_warn!(
+                "Ignoring invalid {}: {}",
+                custom_difficulty_filepath.display(),
+                error
+            );
+
+            difficulty_data["custom"].clone()
+        }),
+        Err(_) => difficulty_data["custom"].clone(),
+    };
+
+    Ok(DifficultySettings {
+        presets,
+        custom: read_difficulty(&custom_data, CUSTOM_DIFFICULTY_NAME),
+    })
+}
+
+pub fn write_custom_difficulty(custom: &Difficulty) -> Result<(), Box<dyn Error>> {
+    let custom_data = serde_json::json!({
+        "lives": custom.player_lives,
+        "alien-velocity": custom.alien_velocity_multiplier,
+        "alien-fire-interval": custom.alien_fire_interval_multiplier,
+        "alien-bullet-velocity": custom.alien_bullet_velocity_multiplier,
+        "power-up-drop-chance": custom.power_up_drop_multiplier,
+        "score": custom.score_multiplier,
+    });
+
+    let saves_directory = game::saves_directory();
+    fs::create_dir_all(&saves_directory)?;
+    fs::write(
+        saves_directory.join(CUSTOM_DIFFICULTY_FILE_NAME),
+        serde_json::to_string_pretty(&custom_data)?,
+    )?;
+
+    Ok(())
+}
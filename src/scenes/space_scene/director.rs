@@ -0,0 +1,211 @@
+use std::error::Error;
+
+use crate::game;
+use crate::game::logging::log_debug;
+
+const DIRECTOR_LOG_LENGTH: usize = 4;
+
+#[derive(Clone, Debug)]
+pub struct DirectorSettings {
+    pub is_enabled: bool,
+
+    pub target_accuracy: f32,
+    pub target_clear_time: f32,
+    pub target_deaths: f32,
+    pub adjustment_step: f32,
+
+    pub fire_interval_range: (f32, f32),
+    pub velocity_increment_range: (f32, f32),
+    pub power_up_drop_range: (f32, f32),
+}
+
+impl Default for DirectorSettings {
+    fn default() -> DirectorSettings {
+        DirectorSettings {
+            is_enabled: false,
+            target_accuracy: 0.5,
+            target_clear_time: 60.0,
+            target_deaths: 1.0,
+            adjustment_step: 0.1,
+            fire_interval_range: (1.0, 1.0),
+            velocity_increment_range: (1.0, 1.0),
+            power_up_drop_range: (1.0, 1.0),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct AttemptStats {
+    shots_fired: u32,
+    shots_hit: u32,
+    elapsed_time: f32,
+}
+
+pub struct Director {
+    settings: DirectorSettings,
+    stats: AttemptStats,
+    level_deaths: u32,
+
+    pub skill: f32,
+    pub fire_interval_multiplier: f32,
+    pub velocity_increment_multiplier: f32,
+    pub power_up_drop_multiplier: f32,
+
+    pub decisions: Vec<String>,
+}
+
+impl Director {
+    pub fn new(settings: DirectorSettings) -> Director {
+        Director {
+            settings,
+            stats: AttemptStats::default(),
+            level_deaths: 0,
+            skill: 0.0,
+            fire_interval_multiplier: 1.0,
+            velocity_increment_multiplier: 1.0,
+            power_up_drop_multiplier: 1.0,
+            decisions: vec![],
+        }
+    }
+
+    pub fn set_settings(&mut self, settings: DirectorSettings) {
+        self.settings = settings;
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.settings.is_enabled
+    }
+
+    pub fn accuracy(&self) -> f32 {
+        let accuracy = if self.stats.shots_fired == 0 {
+            self.settings.target_accuracy
+        } else {
+            self.stats.shots_hit as f32 / self.stats.shots_fired as f32
+        };
+
+        clamp(accuracy, (0.0, 1.0))
+    }
+
+    pub fn record_shots(&mut self, count: u32) {
+        self.stats.shots_fired += count;
+    }
+
+    pub fn record_hit(&mut self) {
+        self.stats.shots_hit += 1;
+    }
+
+    pub fn update(&mut self, delta_time: f32) {
+        self.stats.elapsed_time += delta_time;
+    }
+
+    pub fn on_player_death(&mut self, level: u32) {
+        self.level_deaths += 1;
+        self.evaluate(level, false);
+    }
+
+    pub fn on_level_clear(&mut self, level: u32) {
+        self.evaluate(level, true);
+        self.level_deaths = 0;
+    }
+
+    fn evaluate(&mut self, level: u32, is_level_clear: bool) {
+        let settings = &self.settings;
+
+        let accuracy_score =
+            (self.accuracy() - settings.target_accuracy) / f32::max(settings.target_accuracy, 0.01);
+        let death_score = settings.target_deaths - self.level_deaths as f32;
+
+        let skill = if is_level_clear {
+            let time_score = (settings.target_clear_time - self.stats.elapsed_time)
+                / f32::max(settings.target_clear_time, 1.0);
+
+            (accuracy_score + time_score + death_score) / 3.0
+        } else {
+            (accuracy_score + death_score) / 2.0
+        };
+
+        self.skill = clamp(skill, (-1.0, 1.0));
+
+        if settings.is_enabled {
+            let adjustment = self.skill * settings.adjustment_step;
+
+            self.fire_interval_multiplier = clamp(
+                self.fire_interval_multiplier - adjustment,
+                settings.fire_interval_range,
+            );
+            self.velocity_increment_multiplier = clamp(
+                self.velocity_increment_multiplier + adjustment,
+                settings.velocity_increment_range,
+            );
+            self.power_up_drop_multiplier = clamp(
+                self.power_up_drop_multiplier - adjustment,
+                settings.power_up_drop_range,
+            );
+        }
+
+        let decision = format!(
+            "Level {} {}: accuracy {:.0}%, {} deaths, {:.1}s -> skill {:+.2}, fire interval x{:.2}, velocity increment x{:.2}, drop chance x{:.2}",
+            level,
+            if is_level_clear { "clear" } else { "death" },
+            self.accuracy() * 100.0,
+            self.level_deaths,
+            self.stats.elapsed_time,
+            self.skill,
+            self.fire_interval_multiplier,
+            self.velocity_increment_multiplier,
+            self.power_up_drop_multiplier
+        );
+
+        if settings.is_enabled {
+            log_debug!("{}", decision);
+        }
+
+        if self.decisions.len() >= DIRECTOR_LOG_LENGTH {
+            self.decisions.remove(0);
+        }
+
+        self.decisions.push(decision);
+        self.stats = AttemptStats::default();
+    }
+}
+
+fn clamp(value: f32, range: (f32, f32)) -> f32 {
+    f32::min(f32::max(value, range.0), range.1)
+}
+
+fn read_range(range_data: &serde_json::Value) -> (f32, f32) {
+    (
+        range_data["min"].as_f64().unwrap_or(1.0) as f32,
+        range_data["max"].as_f64().unwrap_or(1.0) as f32,
+    )
+}
+
+pub fn read_director_settings() -> Result<DirectorSettings, Box<dyn Error>> {
+    let director_data = game::read_config_section("director")?;
+    let default = DirectorSettings::default();
+
+    if director_data.is_null() {
+        return Ok(default);
+    }
+
+    Ok(DirectorSettings {
+        is_enabled: director_data["enabled"]
+            .as_bool()
+            .unwrap_or(default.is_enabled),
+        target_accuracy: director_data["target-accuracy"]
+            .as_f64()
+            .map_or(default.target_accuracy, |accuracy| accuracy as f32),
+        target_clear_time: director_data["target-clear-time"]
+            .as_f64()
+            .map_or(default.target_clear_time, |time| time as f32),
+        target_deaths: director_data["target-deaths"]
+            .as_f64()
+            .map_or(default.target_deaths, |deaths| deaths as f32),
+        adjustment_step: director_data["adjustment-step"]
+            .as_f64()
+            .map_or(default.adjustment_step, |step| step as f32),
+        fire_interval_range: read_range(&director_data["fire-interval"]),
+        velocity_increment_range: read_range(&director_data["velocity-increment"]),
+        power_up_drop_range: read_range(&director_data["power-up-drop-chance"]),
+    })
+}
@@ -0,0 +1,444 @@
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+use super::alien::alien_type::AlienTypeTable;
+use super::alien::{
+    ALIEN_DROPDOWN_DISTANCE, ALIEN_VELOCITY_INCREMENT, DEFAULT_MAX_ALIEN_BULLETS,
+    DEFAULT_TARGETED_SHOT_CHANCE, INITIAL_ALIEN_VELOCITY,
+};
+use super::power_up::PowerUpKind;
+use crate::game::vfs;
+
+pub const LEVELS_DIRECTORY: &str = "assets/levels";
+pub const LEVEL_FILE_EXTENSION: &str = "level";
+pub const EMPTY_CELL_SYMBOL: char = '.';
+
+const DEFAULT_BUNKER_POSITIONS: [f32; 4] = [0.2, 0.4, 0.6, 0.8];
+
+#[derive(Debug)]
+pub struct LevelError {
+    pub file: String,
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for LevelError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "{}:{}: {}", self.file, self.line, self.message)
+    }
+}
+
+impl Error for LevelError {}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum LevelEventKind {
+    Message(String),
+    SpeedUp(f32),
+    Reinforcements { type_index: usize, count: u32 },
+    PowerUp(PowerUpKind),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct LevelEvent {
+    pub time: f32,
+    pub kind: LevelEventKind,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Level {
+    pub name: String,
+
+    pub velocity: f32,
+    pub velocity_increment: f32,
+    pub dropdown_distance: f32,
+    pub fire_interval: Option<f32>,
+    pub max_alien_bullets: usize,
+    pub targeted_shot_chance: f32,
+
+    pub formation: Vec<Vec<Option<usize>>>,
+    pub bunkers: Vec<f32>,
+    pub events: Vec<LevelEvent>,
+
+    pub is_boss_level: bool,
+}
+
+impl Level {
+    pub fn new(name: &str) -> Level {
+        Level {
+            name: name.to_string(),
+            velocity: INITIAL_ALIEN_VELOCITY,
+            velocity_increment: ALIEN_VELOCITY_INCREMENT,
+            dropdown_distance: ALIEN_DROPDOWN_DISTANCE,
+            fire_interval: None,
+            max_alien_bullets: DEFAULT_MAX_ALIEN_BULLETS,
+            targeted_shot_chance: DEFAULT_TARGETED_SHOT_CHANCE,
+            formation: vec![],
+            bunkers: DEFAULT_BUNKER_POSITIONS.to_vec(),
+            events: vec![],
+            is_boss_level: false,
+        }
+    }
+
+    pub fn column_count(&self) -> usize {
+        self.formation
+            .iter()
+            .map(|row| row.len())
+            .max()
+            .unwrap_or(0)
+    }
+
+    pub fn alien_position(
+        &self,
+        row: usize,
+        column: usize,
+        cell_size: (u32, u32),
+        viewport_width: u32,
+    ) -> (f32, f32) {
+        let (cell_width, cell_height) = (cell_size.0 as f32, cell_size.1 as f32);
+        let first_alien_x = (viewport_width as f32 - self.column_count() as f32 * cell_width * 2.0)
+            / 2.0
+            + cell_width;
+
+        (
+            first_alien_x + cell_width * 2.0 * column as f32,
+            cell_height + cell_height * 1.5 * row as f32,
+        )
+    }
+
+    pub fn to_level_string(&self, alien_types: &AlienTypeTable) -> String {
+        let mut lines = vec![
+            format!("name {}", self.name),
+            format!("velocity {}", self.velocity),
+            format!("velocity-increment {}", self.velocity_increment),
+            format!("dropdown-distance {}", self.dropdown_distance),
+        ];
+
+        if let Some(fire_interval) = self.fire_interval {
+            lines.push(format!("fire-interval {}", fire_interval));
+        }
+
+        lines.push(format!("max-alien-bullets {}", self.max_alien_bullets));
+        lines.push(format!(
+            "targeted-shot-chance {}",
+            self.targeted_shot_chance
+        ));
+
+        if self.is_boss_level {
+            lines.push("boss".to_string());
+        }
+
+        if !self.bunkers.is_empty() {
+            let positions: Vec<String> = self
+                .bunkers
+                .iter()
+                .map(|position| position.to_string())
+                .collect();
+
+            lines.push(format!("bunkers {}", positions.join(" ")));
+        }
+
+        if !self.formation.is_empty() {
+            lines.push(String::new());
+            lines.push("formation".to_string());
+
+            for row in &self.formation {
+                let cells: Vec<String> = row
+                    .iter()
+                    .map(|cell| match cell {
+                        Some(type_index) => alien_types.types[*type_index].symbol.to_string(),
+                        None => EMPTY_CELL_SYMBOL.to_string(),
+                    })
+                    .collect();
+
+                lines.push(cells.join(" "));
+            }
+
+            lines.push("end".to_string());
+        }
+
+        if !self.events.is_empty() {
+            lines.push(String::new());
+        }
+
+        for event in &self.events {
+            lines.push(match &event.kind {
+                LevelEventKind::Message(text) => format!("event {} message {}", event.time, text),
+                LevelEventKind::SpeedUp(amount) => {
+                    format!("event {} speed-up {}", event.time, amount)
+                }
+                LevelEventKind::Reinforcements { type_index, count } => format!(
+                    "event {} reinforcements {} {}",
+                    event.time, alien_types.types[*type_index].name, count
+                ),
+                LevelEventKind::PowerUp(kind) => {
+                    format!("event {} power-up {}", event.time, kind.name())
+                }
+            });
+        }
+
+        lines.push(String::new());
+        lines.join("\n")
+    }
+}
+
+pub fn default_column_count(cell_width: u32, viewport_width: u32) -> usize {
+    ((viewport_width - cell_width) / (cell_width * 2)) as usize
+}
+
+pub fn parse_level(
+    file_name: &str,
+    level_string: &str,
+    alien_types: &AlienTypeTable,
+) -> Result<Level, Vec<LevelError>> {
+    let mut level = Level::new(file_name);
+    let mut errors = vec![];
+    let mut formation_start_line = None;
+
+    for (line_index, line) in level_string.lines().enumerate() {
+        let line_number = line_index + 1;
+        let line = line.trim();
+        let mut report = |message: String| {
+            errors.push(LevelError {
+                file: file_name.to_string(),
+                line: line_number,
+                message,
+            })
+        };
+
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if formation_start_line.is_some() {
+            if line == "end" {
+                formation_start_line = None;
+
+                continue;
+            }
+
+            let mut row = vec![];
+
+            for symbol in line.chars().filter(|symbol| !symbol.is_whitespace()) {
+                if symbol == EMPTY_CELL_SYMBOL {
+                    row.push(None);
+                } else {
+                    match alien_types.index_of_symbol(symbol) {
+                        Some(type_index) => row.push(Some(type_index)),
+                        None => report(format!("Unknown alien symbol '{}'.", symbol)),
+                    }
+                }
+            }
+
+            level.formation.push(row);
+
+            continue;
+        }
+
+        let (key, value) = match line.find(char::is_whitespace) {
+            Some(split_index) => (&line[..split_index], line[split_index..].trim()),
+            None => (line, ""),
+        };
+
+        match key {
+            "name" => level.name = value.to_string(),
+            "velocity" => match parse_number(value) {
+                Ok(velocity) => level.velocity = velocity,
+                Err(message) => report(message),
+            },
+            "velocity-increment" => match parse_number(value) {
+                Ok(velocity_increment) => level.velocity_increment = velocity_increment,
+                Err(message) => report(message),
+            },
+            "dropdown-distance" => match parse_number(value) {
+                Ok(dropdown_distance) => level.dropdown_distance = dropdown_distance,
+                Err(message) => report(message),
+            },
+            "fire-interval" => match parse_number(value) {
+                Ok(fire_interval) if fire_interval > 0.0 => {
+                    level.fire_interval = Some(fire_interval)
+                }
+                Ok(_) => report("Fire interval must be greater than zero.".to_string()),
+                Err(message) => report(message),
+            },
+            "max-alien-bullets" => match parse_number(value) {
+                Ok(max_alien_bullets)
+                    if max_alien_bullets >= 1.0 && max_alien_bullets.fract() == 0.0 =>
+                {
+                    level.max_alien_bullets = max_alien_bullets as usize
+                }
+                Ok(_) => {
+                    report("Max alien bullets must be a whole number of at least 1.".to_string())
+                }
+                Err(message) => report(message),
+            },
+            "targeted-shot-chance" => match parse_number(value) {
+                Ok(targeted_shot_chance) if targeted_shot_chance <= 1.0 => {
+                    level.targeted_shot_chance = targeted_shot_chance
+                }
+                Ok(_) => report("Targeted shot chance must be between 0 and 1.".to_string()),
+                Err(message) => report(message),
+            },
+            "boss" => level.is_boss_level = true,
+            "bunkers" => {
+                level.bunkers.clear();
+
+                for position in value.split_whitespace() {
+                    match parse_number(position) {
+                        Ok(position) if (0.0..=1.0).contains(&position) => {
+                            level.bunkers.push(position)
+                        }
+                        Ok(position) => report(format!(
+                            "Bunker position {} is outside the 0 to 1 range.",
+                            position
+                        )),
+                        Err(message) => report(message),
+                    }
+                }
+            }
+            "formation" => {
+                if !level.formation.is_empty() {
+                    report("Level defines more than one formation.".to_string());
+                }
+
+                formation_start_line = Some(line_number);
+            }
+            "event" => match parse_event(value, alien_types) {
+                Ok(event) => level.events.push(event),
+                Err(message) => report(message),
+            },
+            _ => report(format!("Unknown key \"{}\".", key)),
+        }
+    }
+
+    if let Some(line) = formation_start_line {
+        errors.push(LevelError {
+            file: file_name.to_string(),
+            line,
+            message: "Formation is missing its closing \"end\".".to_string(),
+        });
+    }
+
+    if !level.is_boss_level && level.formation.iter().flatten().all(Option::is_none) {
+        errors.push(LevelError {
+            file: file_name.to_string(),
+            line: level_string.lines().count(),
+            message: "Level has no aliens and is not a boss level.".to_string(),
+        });
+    }
+
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    level
+        .events
+        .sort_by(|first, second| first.time.partial_cmp(&second.time).unwrap());
+
+    Ok(level)
+}
+
+fn parse_number(value: &str) -> Result<f32, String> {
+    value
+        .parse::<f32>()
+        .ok()
+        .filter(|number| number.is_finite() && *number >= 0.0)
+        .ok_or_else(|| format!("Expected a non-negative number, found \"{}\".", value))
+}
+
+fn parse_event(value: &str, alien_types: &AlienTypeTable) -> Result<LevelEvent, String> {
+    let mut arguments = value.splitn(3, char::is_whitespace);
+
+    let time = parse_number(arguments.next().unwrap_or_default())?;
+    let kind_name = arguments.next().ok_or("Event is missing a kind.")?;
+    let parameters = arguments.next().unwrap_or_default().trim();
+
+    let kind = match kind_name {
+        "message" => {
+            if parameters.is_empty() {
+                return Err("Message event is missing its text.".to_string());
+            }
+
+            LevelEventKind::Message(parameters.to_string())
+        }
+        "speed-up" => LevelEventKind::SpeedUp(parse_number(parameters)?),
+        "reinforcements" => {
+            let mut parameters = parameters.split_whitespace();
+            let type_name = parameters
+                .next()
+                .ok_or("Reinforcements event is missing an alien type.")?;
+            let count = parameters.next().unwrap_or("1");
+
+            LevelEventKind::Reinforcements {
+                type_index: alien_types
+                    .index_of(type_name)
+                    .ok_or_else(|| format!("Unknown alien type \"{}\".", type_name))?,
+                count: count
+                    .parse()
+                    .map_err(|_| format!("Expected an alien count, found \"{}\".", count))?,
+            }
+        }
+        "power-up" => LevelEventKind::PowerUp(
+            PowerUpKind::from_name(parameters)
+                .ok_or_else(|| format!("Unknown power-up \"{}\".", parameters))?,
+        ),
+        _ => return Err(format!("Unknown event kind \"{}\".", kind_name)),
+    };
+
+    Ok(LevelEvent { time, kind })
+}
+
+pub fn read_level_file(
+    level_filepath: &str,
+    alien_types: &AlienTypeTable,
+) -> Result<Level, Vec<LevelError>> {
+    let level_string = vfs::read_to_string(level_filepath).map_err(|error| {
+        vec![LevelError {
+            file: level_filepath.to_string(),
+            line: 0,
+            message: error.to_string(),
+        }]
+    })?;
+
+    parse_level(level_filepath, &level_string, alien_types)
+}
+
+pub fn write_level_file(
+    level_filepath: &str,
+    level: &Level,
+    alien_types: &AlienTypeTable,
+) -> Result<(), Box<dyn Error>> {
+    fs::write(
+        vfs::resolve(level_filepath),
+        level.to_level_string(alien_types),
+    )?;
+
+    Ok(())
+}
+
+pub fn level_filepaths() -> Vec<String> {
+    vfs::read_dir(LEVELS_DIRECTORY)
+        .into_iter()
+        .filter(|level_filepath| {
+            Path::new(level_filepath)
+                .extension()
+                .is_some_and(|extension| extension == LEVEL_FILE_EXTENSION)
+        })
+        .collect()
+}
+
+pub fn read_levels(alien_types: &AlienTypeTable) -> (Vec<Level>, Vec<LevelError>) {
+    let mut levels = vec![];
+    let mut errors: Vec<LevelError> = vec![];
+
+    for level_filepath in level_filepaths() {
+        match read_level_file(&level_filepath, alien_types) {
+            Ok(level) if errors.is_empty() => levels.push(level),
+            Ok(_) => (),
+            Err(level_errors) => errors.extend(level_errors),
+        }
+    }
+
+    (levels, errors)
+}
@@ -0,0 +1,289 @@
+use std::error::Error;
+use std::fs;
+
+use rand::Rng;
+use sdl2::pixels::Color as Colour;
+
+use crate::game::particles;
+use crate::game::vfs;
+
+pub const POWER_UP_FALL_VELOCITY: f32 = 150.0;
+pub const POWER_UP_SIZE: u32 = 28;
+
+const POWER_UPS_FILE_NAME: &str = "config/power_ups.json";
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PowerUpKind {
+    RapidFire,
+    SpreadShot,
+    PiercingLaser,
+    Shield,
+    ExtraLife,
+    TimeSlow,
+}
+
+impl PowerUpKind {
+    pub fn from_name(name: &str) -> Option<PowerUpKind> {
+        let name = name
+            .chars()
+            .filter(|character| !matches!(character, '-' | '_' | ' '))
+            .collect::<String>()
+            .to_lowercase();
+
+        match name.as_str() {
+            "rapidfire" => Some(PowerUpKind::RapidFire),
+            "spreadshot" => Some(PowerUpKind::SpreadShot),
+            "piercinglaser" => Some(PowerUpKind::PiercingLaser),
+            "shield" => Some(PowerUpKind::Shield),
+            "extralife" => Some(PowerUpKind::ExtraLife),
+            "timeslow" => Some(PowerUpKind::TimeSlow),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            PowerUpKind::RapidFire => "rapid-fire",
+            PowerUpKind::SpreadShot => "spread-shot",
+            PowerUpKind::PiercingLaser => "piercing-laser",
+            PowerUpKind::Shield => "shield",
+            PowerUpKind::ExtraLife => "extra-life",
+            PowerUpKind::TimeSlow => "time-slow",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum StackRule {
+    Refresh,
+    Extend,
+    Stack,
+}
+
+impl StackRule {
+    pub fn from_name(name: &str) -> Option<StackRule> {
+        match name {
+            "refresh" => Some(StackRule::Refresh),
+            "extend" => Some(StackRule::Extend),
+            "stack" => Some(StackRule::Stack),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct PowerUpType {
+    pub kind: PowerUpKind,
+    pub display_name: String,
+    pub label: String,
+    pub colour: Colour,
+
+    pub duration: f32,
+    pub stack_rule: StackRule,
+    pub max_stacks: u32,
+    pub max_duration: f32,
+
+    pub strength: f32,
+    pub weight: u32,
+}
+
+pub struct PowerUpTable {
+    pub types: Vec<PowerUpType>,
+
+    pub base_drop_chance: f32,
+    pub per_level_drop_chance: f32,
+    pub max_drop_chance: f32,
+}
+
+impl PowerUpTable {
+    pub fn empty() -> PowerUpTable {
+        PowerUpTable {
+            types: vec![],
+            base_drop_chance: 0.0,
+            per_level_drop_chance: 0.0,
+            max_drop_chance: 0.0,
+        }
+    }
+
+    pub fn drop_chance(&self, level: u32, difficulty_multiplier: f32) -> f32 {
+        f32::min(
+            self.base_drop_chance + (level - 1) as f32 * self.per_level_drop_chance,
+            self.max_drop_chance,
+        ) * difficulty_multiplier
+    }
+
+    pub fn roll_drop(
+        &self,
+        level: u32,
+        difficulty_multiplier: f32,
+        rng: &mut impl Rng,
+    ) -> Option<usize> {
+        let total_weight: u32 = self.types.iter().map(|power_up| power_up.weight).sum();
+
+        if total_weight == 0 || rng.gen::<f32>() >= self.drop_chance(level, difficulty_multiplier) {
+            return None;
+        }
+
+        let mut roll = rng.gen_range(0, total_weight);
+
+        for (type_index, power_up) in self.types.iter().enumerate() {
+            if roll < power_up.weight {
+                return Some(type_index);
+            }
+
+            roll -= power_up.weight;
+        }
+
+        None
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct PowerUpDrop {
+    pub type_index: usize,
+}
+
+#[derive(Debug)]
+pub struct ActivePowerUp {
+    pub type_index: usize,
+    pub remaining_time: f32,
+    pub stacks: u32,
+}
+
+#[derive(Debug, Default)]
+pub struct ActivePowerUps {
+    pub power_ups: Vec<ActivePowerUp>,
+}
+
+impl ActivePowerUps {
+    pub fn apply(&mut self, type_index: usize, power_up_table: &PowerUpTable) {
+        let power_up_type = &power_up_table.types[type_index];
+
+        if power_up_type.duration <= 0.0 {
+            return;
+        }
+
+        let existing_power_up = self
+            .power_ups
+            .iter_mut()
+            .find(|power_up| power_up.type_index == type_index);
+
+        match existing_power_up {
+            Some(power_up) => match power_up_type.stack_rule {
+                StackRule::Refresh => {
+                    power_up.remaining_time = power_up_type.duration;
+                }
+                StackRule::Extend => {
+                    power_up.remaining_time = f32::min(
+                        power_up.remaining_time + power_up_type.duration,
+                        power_up_type.max_duration,
+                    );
+                }
+                StackRule::Stack => {
+                    power_up.remaining_time = power_up_type.duration;
+                    power_up.stacks = u32::min(power_up.stacks + 1, power_up_type.max_stacks);
+                }
+            },
+            None => self.power_ups.push(ActivePowerUp {
+                type_index,
+                remaining_time: power_up_type.duration,
+                stacks: 1,
+            }),
+        }
+    }
+
+    pub fn update(&mut self, delta_time: f32) {
+        for power_up in &mut self.power_ups {
+            power_up.remaining_time -= delta_time;
+        }
+
+        self.power_ups
+            .retain(|power_up| power_up.remaining_time > 0.0);
+    }
+
+    pub fn clear(&mut self) {
+        self.power_ups.clear();
+    }
+
+    pub fn active(&self, kind: PowerUpKind, power_up_table: &PowerUpTable) -> Option<(u32, f32)> {
+        self.power_ups
+            .iter()
+            .find(|power_up| power_up_table.types[power_up.type_index].kind == kind)
+            .map(|power_up| {
+                (
+                    power_up.stacks,
+                    power_up_table.types[power_up.type_index].strength,
+                )
+            })
+    }
+
+    pub fn is_active(&self, kind: PowerUpKind, power_up_table: &PowerUpTable) -> bool {
+        self.active(kind, power_up_table).is_some()
+    }
+
+    pub fn shoot_delay_multiplier(&self, power_up_table: &PowerUpTable) -> f32 {
+        self.active(PowerUpKind::RapidFire, power_up_table)
+            .map_or(1.0, |(stacks, strength)| strength.powi(stacks as i32))
+    }
+
+    pub fn spread_shot(&self, power_up_table: &PowerUpTable) -> (u32, f32) {
+        self.active(PowerUpKind::SpreadShot, power_up_table)
+            .map_or((0, 0.0), |(stacks, strength)| (stacks, strength))
+    }
+
+    pub fn enemy_time_scale(&self, power_up_table: &PowerUpTable) -> f32 {
+        self.active(PowerUpKind::TimeSlow, power_up_table)
+            .map_or(1.0, |(_, strength)| strength)
+    }
+}
+
+pub fn read_power_ups_file() -> Result<PowerUpTable, Box<dyn Error>> {
+    let json_string = fs::read_to_string(vfs::resolve(POWER_UPS_FILE_NAME))?;
+    let power_up_data: serde_json::Value = serde_json::from_str(&json_string[..])?;
+
+    let mut types = vec![];
+
+    for type_data in power_up_data["types"]
+        .as_array()
+        .ok_or("Power-ups file is missing a \"types\" array.")?
+    {
+        let name = type_data["name"]
+            .as_str()
+            .ok_or("Power-up type is missing a name.")?;
+        let stack_rule_name = type_data["stacking"].as_str().unwrap_or("refresh");
+        let duration = type_data["duration"].as_f64().unwrap_or(0.0) as f32;
+
+        types.push(PowerUpType {
+            kind: PowerUpKind::from_name(name)
+                .ok_or_else(|| format!("Unknown power-up type \"{}\".", name))?,
+            display_name: type_data["display-name"]
+                .as_str()
+                .unwrap_or(name)
+                .to_string(),
+            label: type_data["label"].as_str().unwrap_or("?").to_string(),
+            colour: particles::read_colour(&type_data["colour"]),
+            duration,
+            stack_rule: StackRule::from_name(stack_rule_name).ok_or_else(|| {
+                format!(
+                    "Power-up \"{}\" has unknown stacking rule \"{}\".",
+                    name, stack_rule_name
+                )
+            })?,
+            max_stacks: type_data["max-stacks"].as_u64().unwrap_or(1) as u32,
+            max_duration: type_data["max-duration"]
+                .as_f64()
+                .map_or(duration, |max_duration| max_duration as f32),
+            strength: type_data["strength"].as_f64().unwrap_or(1.0) as f32,
+            weight: type_data["weight"].as_u64().unwrap_or(1) as u32,
+        });
+    }
+
+    Ok(PowerUpTable {
+        types,
+        base_drop_chance: power_up_data["drop-chance"]["base"].as_f64().unwrap_or(0.0) as f32,
+        per_level_drop_chance: power_up_data["drop-chance"]["per-level"]
+            .as_f64()
+            .unwrap_or(0.0) as f32,
+        max_drop_chance: power_up_data["drop-chance"]["max"].as_f64().unwrap_or(1.0) as f32,
+    })
+}
@@ -1,6 +1,39 @@
 pub const BULLET_VELOCITY: f32 = 650.0;
 
-#[derive(Debug)]
+pub const ZIGZAG_FREQUENCY: f32 = 12.0;
+pub const ZIGZAG_AMPLITUDE: f32 = 8.0;
+const AIMED_MAX_X_VELOCITY: f32 = 250.0;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BulletKind {
+    Standard,
+    Zigzag,
+    Plunger,
+    Aimed,
+}
+
+impl BulletKind {
+    pub fn from_name(name: &str) -> Option<BulletKind> {
+        match name {
+            "standard" => Some(BulletKind::Standard),
+            "zigzag" => Some(BulletKind::Zigzag),
+            "plunger" => Some(BulletKind::Plunger),
+            "aimed" => Some(BulletKind::Aimed),
+            _ => None,
+        }
+    }
+
+    pub fn velocity_multiplier(self) -> f32 {
+        match self {
+            BulletKind::Standard => 1.0,
+            BulletKind::Zigzag => 0.7,
+            BulletKind::Plunger => 1.4,
+            BulletKind::Aimed => 0.9,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
 pub struct BulletData {
     pub width: u32,
     pub height: u32,
@@ -8,10 +41,41 @@ pub struct BulletData {
     pub texture_index: usize,
 }
 
+#[derive(Debug, Default)]
+pub struct BulletSprites {
+    pub player: BulletData,
+    pub alien: BulletData,
+}
+
 #[derive(Debug)]
 pub struct Bullet {
     pub x: f32,
     pub y: f32,
 
-    pub has_hit_something: bool,
+    pub kind: BulletKind,
+    pub x_velocity: f32,
+    pub velocity_multiplier: f32,
+
+    pub is_piercing: bool,
+}
+
+impl Bullet {
+    pub fn new(x: f32, y: f32, kind: BulletKind) -> Bullet {
+        Bullet {
+            x,
+            y,
+            kind,
+            x_velocity: 0.0,
+            velocity_multiplier: 1.0,
+            is_piercing: false,
+        }
+    }
+
+    pub fn aim_at(&mut self, target_x: f32, target_y: f32) {
+        let y_velocity = BULLET_VELOCITY * self.kind.velocity_multiplier();
+        let time_to_target = f32::max(target_y - self.y, 1.0) / y_velocity;
+
+        self.x_velocity = ((target_x - self.x) / time_to_target)
+            .clamp(-AIMED_MAX_X_VELOCITY, AIMED_MAX_X_VELOCITY);
+    }
 }
@@ -0,0 +1,952 @@
+use std::collections::BTreeMap;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use super::alien::alien_type::AlienType;
+use super::alien::{AlienDirection, Fleet, ALIEN_STEP_DISTANCE};
+use super::components::*;
+use super::power_up::{PowerUpDrop, POWER_UP_FALL_VELOCITY, POWER_UP_SIZE};
+use super::spaceship::bullet::{
+    Bullet, BulletKind, BulletSprites, BULLET_VELOCITY, ZIGZAG_AMPLITUDE, ZIGZAG_FREQUENCY,
+};
+use super::spaceship::SPACESHIP_SHOOT_DELAY;
+use crate::game::animation::SpriteSheet;
+use crate::game::ecs::components::{Lifetime, Sprite, Transform, Velocity};
+use crate::game::ecs::{Entity, Schedule, Storage, World};
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Arena {
+    pub width: u32,
+    pub height: u32,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct EnemyTimeScale(pub f32);
+
+pub struct SpaceRng(pub StdRng);
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SpaceEvent {
+    PlayerFired { x: f32, y: f32, shots: u32 },
+    AlienFired,
+    FleetShifted,
+    FleetLanded,
+}
+
+pub fn create_world(rng_seed: u64) -> World {
+    let mut world = World::new();
+
+    world.register::<Transform>();
+    world.register::<Velocity>();
+    world.register::<Sprite>();
+    world.register::<Lifetime>();
+    world.register::<Player>();
+    world.register::<Hitbox>();
+    world.register::<Health>();
+    world.register::<Weapon>();
+    world.register::<Ai>();
+    world.register::<Projectile>();
+    world.register::<PowerUpDrop>();
+
+    world.insert_resource(Arena::default());
+    world.insert_resource(EnemyTimeScale(1.0));
+    world.insert_resource(SpaceRng(StdRng::seed_from_u64(rng_seed)));
+    world.insert_resource(Fleet::default());
+    world.insert_resource(BulletSprites::default());
+    world.insert_resource(Vec::<SpriteSheet>::new());
+    world.insert_resource(Vec::<SpaceEvent>::new());
+
+    world
+}
+
+pub fn create_schedule() -> Schedule {
+    Schedule::new()
+        .with_system(player_movement_system)
+        .with_system(player_weapon_system)
+        .with_system(fleet_movement_system)
+        .with_system(fleet_fire_system)
+        .with_system(movement_system)
+        .with_system(lifetime_system)
+        .with_system(ai_system)
+}
+
+pub fn spawn_spaceship(
+    world: &mut World,
+    position: (f32, f32),
+    texture_index: usize,
+    size: (u32, u32),
+) -> Entity {
+    let entity = world.spawn();
+
+    world.insert(entity, Player);
+    world.insert(entity, Transform::new(position.0, position.1));
+    world.insert(entity, Velocity::default());
+    world.insert(entity, Sprite::new(texture_index, size));
+    world.insert(
+        entity,
+        Hitbox {
+            layer: PLAYER_LAYER,
+            collides_with: POWER_UP_LAYER,
+            shape: HitboxShape::Sprite,
+            is_swept: false,
+        },
+    );
+    world.insert(entity, Health { points: 1 });
+    world.insert(
+        entity,
+        Weapon::new(BulletKind::Standard, SPACESHIP_SHOOT_DELAY),
+    );
+
+    entity
+}
+
+pub fn spawn_alien(
+    world: &mut World,
+    position: (f32, f32),
+    type_index: usize,
+    alien_type: &AlienType,
+    shoot_interval: f32,
+) -> Entity {
+    let entity = world.spawn();
+
+    world.insert(entity, Transform::new(position.0, position.1));
+    world.insert(
+        entity,
+        Sprite::new(
+            alien_type.texture_index,
+            (alien_type.width, alien_type.height),
+        ),
+    );
+    world.insert(
+        entity,
+        Hitbox {
+            layer: ALIEN_LAYER,
+            collides_with: PLAYER_LAYER | BUNKER_LAYER,
+            shape: HitboxShape::Sprite,
+            is_swept: false,
+        },
+    );
+    world.insert(entity, Health { points: 1 });
+    world.insert(entity, Weapon::new(alien_type.bullet_kind, shoot_interval));
+    world.insert(entity, Ai::Fleet { type_index });
+
+    entity
+}
+
+pub fn spawn_bullet(world: &mut World, faction: Faction, bullet: Bullet) -> Entity {
+    let bullet_sprites = world.resource::<BulletSprites>();
+    let (bullet_data, layer, collides_with, y_direction) = match faction {
+        Faction::Player => (
+            bullet_sprites.player,
+            PLAYER_BULLET_LAYER,
+            ALIEN_LAYER | BOSS_LAYER | BUNKER_LAYER,
+            -1.0,
+        ),
+        Faction::Alien => (
+            bullet_sprites.alien,
+            ALIEN_BULLET_LAYER,
+            PLAYER_LAYER | BUNKER_LAYER,
+            1.0,
+        ),
+    };
+    drop(bullet_sprites);
+
+    let entity = world.spawn();
+
+    world.insert(entity, Transform::new(bullet.x, bullet.y));
+    world.insert(
+        entity,
+        Velocity {
+            x: bullet.x_velocity * bullet.velocity_multiplier,
+            y: y_direction
+                * bullet.velocity_multiplier
+                * BULLET_VELOCITY
+                * bullet.kind.velocity_multiplier(),
+        },
+    );
+    world.insert(
+        entity,
+        Sprite::new(
+            bullet_data.texture_index,
+            (bullet_data.width, bullet_data.height),
+        ),
+    );
+    world.insert(
+        entity,
+        Hitbox {
+            layer,
+            collides_with,
+            shape: HitboxShape::Sprite,
+            is_swept: true,
+        },
+    );
+    world.insert(entity, Lifetime::default());
+    world.insert(
+        entity,
+        Projectile {
+            faction,
+            is_piercing: bullet.is_piercing,
+        },
+    );
+
+    if bullet.kind == BulletKind::Zigzag {
+        world.insert(entity, Ai::Zigzag { origin_x: bullet.x });
+    }
+
+    entity
+}
+
+pub fn spawn_power_up(world: &mut World, position: (f32, f32), type_index: usize) -> Entity {
+    let entity = world.spawn();
+
+    world.insert(entity, Transform::new(position.0, position.1));
+    world.insert(
+        entity,
+        Velocity {
+            x: 0.0,
+            y: POWER_UP_FALL_VELOCITY,
+        },
+    );
+    world.insert(
+        entity,
+        Hitbox {
+            layer: POWER_UP_LAYER,
+            collides_with: 0,
+            shape: HitboxShape::Circle {
+                radius: POWER_UP_SIZE as f32 / 2.0,
+            },
+            is_swept: false,
+        },
+    );
+    world.insert(entity, Lifetime::default());
+    world.insert(entity, PowerUpDrop { type_index });
+
+    entity
+}
+
+pub fn kill(world: &World, entity: Entity, explosion_texture_index: usize) {
+    if let Some(health) = world.write::<Health>().get_mut(entity) {
+        health.points = 0;
+    }
+
+    if let Some(sprite) = world.write::<Sprite>().get_mut(entity) {
+        sprite.texture_index = explosion_texture_index;
+        sprite.animator.play("explode");
+    }
+}
+
+pub fn is_dying(world: &World, entity: Entity) -> bool {
+    world
+        .read::<Health>()
+        .get(entity)
+        .is_some_and(|health| health.is_depleted())
+}
+
+pub fn player_position(world: &World) -> Option<(f32, f32)> {
+    let players = world.read::<Player>();
+    let transforms = world.read::<Transform>();
+
+    let position = players
+        .iter()
+        .find_map(|(entity, _)| transforms.get(entity).map(Transform::position));
+
+    position
+}
+
+pub fn alien_count(world: &World, is_dying_included: bool) -> usize {
+    world
+        .read::<Ai>()
+        .iter()
+        .filter(|(entity, ai)| {
+            matches!(ai, Ai::Fleet { .. }) && (is_dying_included || !is_dying(world, *entity))
+        })
+        .count()
+}
+
+fn front_line(world: &World, column_width: f32) -> Vec<Entity> {
+    let ais = world.read::<Ai>();
+    let transforms = world.read::<Transform>();
+    let mut front_line: BTreeMap<i32, (Entity, f32)> = BTreeMap::new();
+
+    for (entity, ai) in ais.iter() {
+        if !matches!(ai, Ai::Fleet { .. }) || is_dying(world, entity) {
+            continue;
+        }
+
+        let transform = match transforms.get(entity) {
+            Some(transform) => transform,
+            None => continue,
+        };
+        let column = (transform.x / column_width).floor() as i32;
+
+        match front_line.get(&column) {
+            Some((_, front_y)) if *front_y >= transform.y => (),
+            _ => {
+                front_line.insert(column, (entity, transform.y));
+            }
+        }
+    }
+
+    front_line.into_values().map(|(entity, _)| entity).collect()
+}
+
+fn scaled_delta_time(
+    projectiles: &Storage<Projectile>,
+    entity: Entity,
+    delta_time: f32,
+    enemy_time_scale: f32,
+) -> f32 {
+    match projectiles.get(entity) {
+        Some(projectile) if projectile.faction == Faction::Alien => delta_time * enemy_time_scale,
+        _ => delta_time,
+    }
+}
+
+fn player_movement_system(world: &mut World, delta_time: f32) {
+    let arena = *world.resource::<Arena>();
+    let players = world.read::<Player>();
+    let velocities = world.read::<Velocity>();
+    let mut transforms = world.write::<Transform>();
+    let mut sprites = world.write::<Sprite>();
+
+    for (entity, _) in players.iter() {
+        let (velocity, transform, sprite) = match (
+            velocities.get(entity),
+            transforms.get_mut(entity),
+            sprites.get_mut(entity),
+        ) {
+            (Some(velocity), Some(transform), Some(sprite)) => (velocity, transform, sprite),
+            _ => continue,
+        };
+
+        let half_width = sprite.size.0 as f32 / 2.0;
+
+        transform.previous_position = transform.position();
+        transform.x = (transform.x + velocity.x * delta_time)
+            .clamp(half_width, arena.width as f32 - half_width);
+
+        if velocity.x != 0.0 {
+            sprite.animator.play("thrust");
+        } else {
+            sprite.animator.play("idle");
+        }
+    }
+}
+
+fn player_weapon_system(world: &mut World, delta_time: f32) {
+    let mut bullets = vec![];
+
+    {
+        let players = world.read::<Player>();
+        let transforms = world.read::<Transform>();
+        let sprites = world.read::<Sprite>();
+        let mut weapons = world.write::<Weapon>();
+        let mut events = world.resource_mut::<Vec<SpaceEvent>>();
+
+        for (entity, _) in players.iter() {
+            let (transform, sprite, weapon) = match (
+                transforms.get(entity),
+                sprites.get(entity),
+                weapons.get_mut(entity),
+            ) {
+                (Some(transform), Some(sprite), Some(weapon)) => (transform, sprite, weapon),
+                _ => continue,
+            };
+
+            if weapon.is_firing {
+                let bullet_x = transform.x;
+                let bullet_y = transform.y - sprite.size.1 as f32 / 2.0;
+                let (spread_stacks, spread_velocity) = weapon.spread;
+
+                for spread_index in -(spread_stacks as i32)..=spread_stacks as i32 {
+                    let mut bullet = Bullet::new(bullet_x, bullet_y, weapon.bullet_kind);
+                    bullet.x_velocity = spread_index as f32 * spread_velocity;
+                    bullet.is_piercing = weapon.is_piercing;
+
+                    bullets.push(bullet);
+                }
+
+                weapon.is_firing = false;
+                events.push(SpaceEvent::PlayerFired {
+                    x: bullet_x,
+                    y: bullet_y,
+                    shots: spread_stacks * 2 + 1,
+                });
+            }
+
+            if weapon.cooldown > 0.0 {
+                weapon.cooldown -= delta_time;
+            }
+        }
+    }
+
+    for bullet in bullets {
+        spawn_bullet(world, Faction::Player, bullet);
+    }
+}
+
+fn fleet_movement_system(world: &mut World, delta_time: f32) {
+    let delta_time = delta_time * world.resource::<EnemyTimeScale>().0;
+    let arena = *world.resource::<Arena>();
+    let sprite_sheets = world.resource::<Vec<SpriteSheet>>();
+    let mut fleet = world.resource_mut::<Fleet>();
+    let mut events = world.resource_mut::<Vec<SpaceEvent>>();
+    let ais = world.read::<Ai>();
+    let mut transforms = world.write::<Transform>();
+    let mut sprites = world.write::<Sprite>();
+
+    let movement = delta_time * fleet.velocity;
+    let mut switch_direction = false;
+
+    fleet.step_distance += movement;
+
+    let is_stepping = fleet.step_distance >= ALIEN_STEP_DISTANCE;
+
+    if is_stepping {
+        fleet.step_distance -= ALIEN_STEP_DISTANCE;
+    }
+
+    for (entity, ai) in ais.iter() {
+        if !matches!(ai, Ai::Fleet { .. }) || is_dying(world, entity) {
+            continue;
+        }
+
+        let (transform, sprite) = match (transforms.get_mut(entity), sprites.get_mut(entity)) {
+            (Some(transform), Some(sprite)) => (transform, sprite),
+            _ => continue,
+        };
+
+        transform.previous_position = transform.position();
+
+        match fleet.direction {
+            AlienDirection::Left => {
+                transform.x -= movement;
+
+                if transform.x <= sprite.size.0 as f32 / 2.0 {
+                    switch_direction = true;
+                }
+            }
+            AlienDirection::Right => {
+                transform.x += movement;
+
+                if transform.x >= (arena.width - sprite.size.0 / 2) as f32 {
+                    switch_direction = true;
+                }
+            }
+            AlienDirection::Down => {
+                transform.y += movement;
+            }
+        }
+
+        if transform.y + sprite.size.1 as f32 / 2.0 >= arena.height as f32 {
+            fleet.has_hit_bottom = true;
+            events.push(SpaceEvent::FleetLanded);
+        }
+
+        if is_stepping {
+            sprite.animator.step(&sprite_sheets[sprite.texture_index]);
+        }
+    }
+
+    if fleet.direction == AlienDirection::Down {
+        if fleet.dropdown_distance > 0.0 {
+            fleet.dropdown_distance -= movement;
+        } else {
+            fleet.direction = fleet.next_direction.unwrap();
+            fleet.next_direction = None;
+        }
+    }
+
+    if switch_direction {
+        fleet.next_direction = match fleet.direction {
+            AlienDirection::Left => Some(AlienDirection::Right),
+            AlienDirection::Right => Some(AlienDirection::Left),
+            _ => unreachable!(),
+        };
+
+        fleet.velocity += fleet.velocity_increment;
+        fleet.direction = AlienDirection::Down;
+        fleet.dropdown_distance = fleet.level_dropdown_distance;
+
+        events.push(SpaceEvent::FleetShifted);
+    }
+}
+
+fn fleet_fire_system(world: &mut World, delta_time: f32) {
+    let delta_time = delta_time * world.resource::<EnemyTimeScale>().0;
+    let column_width = {
+        let mut fleet = world.resource_mut::<Fleet>();
+        fleet.fire_delay -= delta_time;
+
+        if fleet.fire_delay > 0.0 {
+            return;
+        }
+
+        fleet.column_width
+    };
+
+    let front_line = front_line(world, column_width);
+
+    if front_line.is_empty() {
+        return;
+    }
+
+    let (player_x, player_y) = match player_position(world) {
+        Some(position) => position,
+        None => return,
+    };
+
+    let bullet = {
+        let mut fleet = world.resource_mut::<Fleet>();
+        let transforms = world.read::<Transform>();
+        let sprites = world.read::<Sprite>();
+        let weapons = world.read::<Weapon>();
+        let projectiles = world.read::<Projectile>();
+
+        let mut space_rng = world.resource_mut::<SpaceRng>();
+        let rng = &mut space_rng.0;
+        let is_targeted = rng.gen::<f32>() < fleet.targeted_shot_chance;
+        let distance_to_player = |entity: &Entity| {
+            transforms
+                .get(*entity)
+                .map_or(f32::MAX, |transform| (transform.x - player_x).abs())
+        };
+
+        let shooter = if is_targeted {
+            *front_line
+                .iter()
+                .min_by(|first, second| {
+                    distance_to_player(first)
+                        .partial_cmp(&distance_to_player(second))
+                        .unwrap()
+                })
+                .unwrap()
+        } else {
+            front_line[rng.gen_range(0, front_line.len())]
+        };
+
+        let (transform, sprite, weapon) = match (
+            transforms.get(shooter),
+            sprites.get(shooter),
+            weapons.get(shooter),
+        ) {
+            (Some(transform), Some(sprite), Some(weapon)) => (transform, sprite, weapon),
+            _ => return,
+        };
+
+        fleet.fire_delay = weapon.interval * fleet.fire_interval_scale / front_line.len() as f32;
+
+        let alien_bullet_count = projectiles
+            .iter()
+            .filter(|(_, projectile)| projectile.faction == Faction::Alien)
+            .count();
+
+        if alien_bullet_count >= fleet.max_bullets {
+            return;
+        }
+
+        let bullet_kind = if is_targeted {
+            BulletKind::Aimed
+        } else {
+            weapon.bullet_kind
+        };
+
+        let mut bullet = Bullet::new(
+            transform.x,
+            transform.y + sprite.size.1 as f32 / 2.0,
+            bullet_kind,
+        );
+        bullet.velocity_multiplier = fleet.bullet_velocity_multiplier;
+
+        if bullet_kind == BulletKind::Aimed {
+            bullet.aim_at(player_x, player_y);
+        }
+
+        bullet
+    };
+
+    spawn_bullet(world, Faction::Alien, bullet);
+    world
+        .resource_mut::<Vec<SpaceEvent>>()
+        .push(SpaceEvent::AlienFired);
+}
+
+fn movement_system(world: &mut World, delta_time: f32) {
+    let enemy_time_scale = world.resource::<EnemyTimeScale>().0;
+    let players = world.read::<Player>();
+    let projectiles = world.read::<Projectile>();
+    let velocities = world.read::<Velocity>();
+    let mut transforms = world.write::<Transform>();
+
+    for (entity, velocity) in velocities.iter() {
+        if players.contains(entity) {
+            continue;
+        }
+
+        let transform = match transforms.get_mut(entity) {
+            Some(transform) => transform,
+            None => continue,
+        };
+        let delta_time = scaled_delta_time(&projectiles, entity, delta_time, enemy_time_scale);
+
+        transform.previous_position = transform.position();
+        transform.x += velocity.x * delta_time;
+        transform.y += velocity.y * delta_time;
+    }
+}
+
+fn lifetime_system(world: &mut World, delta_time: f32) {
+    let enemy_time_scale = world.resource::<EnemyTimeScale>().0;
+    let projectiles = world.read::<Projectile>();
+    let mut lifetimes = world.write::<Lifetime>();
+
+    for (entity, lifetime) in lifetimes.iter_mut() {
+        lifetime.age += scaled_delta_time(&projectiles, entity, delta_time, enemy_time_scale);
+    }
+}
+
+fn ai_system(world: &mut World, delta_time: f32) {
+    let enemy_time_scale = world.resource::<EnemyTimeScale>().0;
+    let projectiles = world.read::<Projectile>();
+    let velocities = world.read::<Velocity>();
+    let lifetimes = world.read::<Lifetime>();
+    let mut ais = world.write::<Ai>();
+    let mut transforms = world.write::<Transform>();
+
+    for (entity, ai) in ais.iter_mut() {
+        if let Ai::Zigzag { origin_x } = ai {
+            let (velocity, lifetime, transform) = match (
+                velocities.get(entity),
+                lifetimes.get(entity),
+                transforms.get_mut(entity),
+            ) {
+                (Some(velocity), Some(lifetime), Some(transform)) => {
+                    (velocity, lifetime, transform)
+                }
+                _ => continue,
+            };
+
+            *origin_x +=
+                velocity.x * scaled_delta_time(&projectiles, entity, delta_time, enemy_time_scale);
+            transform.x = *origin_x + (lifetime.age * ZIGZAG_FREQUENCY).sin() * ZIGZAG_AMPLITUDE;
+        }
+    }
+}
+
+pub fn despawn_system(world: &mut World) {
+    let mut expired = vec![];
+
+    {
+        let arena = *world.resource::<Arena>();
+        let bullet_margin = 2.0 * world.resource::<BulletSprites>().player.height as f32;
+        let lifetimes = world.read::<Lifetime>();
+        let transforms = world.read::<Transform>();
+        let projectiles = world.read::<Projectile>();
+        let power_up_drops = world.read::<PowerUpDrop>();
+        let ais = world.read::<Ai>();
+        let sprites = world.read::<Sprite>();
+
+        for (entity, lifetime) in lifetimes.iter() {
+            let y = transforms.get(entity).map_or(0.0, |transform| transform.y);
+            let is_offscreen = match projectiles.get(entity) {
+                Some(projectile) if projectile.faction == Faction::Player => y <= -bullet_margin,
+                Some(_) => y >= arena.height as f32 + bullet_margin,
+                None if power_up_drops.contains(entity) => {
+                    y >= (arena.height + POWER_UP_SIZE) as f32
+                }
+                None => false,
+            };
+
+            if lifetime.is_expired || is_offscreen {
+                expired.push(entity);
+            }
+        }
+
+        for (entity, ai) in ais.iter() {
+            let has_exploded = sprites
+                .get(entity)
+                .is_some_and(|sprite| sprite.animator.is_finished);
+
+            if matches!(ai, Ai::Fleet { .. }) && is_dying(world, entity) && has_exploded {
+                expired.push(entity);
+            }
+        }
+    }
+
+    for entity in expired {
+        world.despawn(entity);
+    }
+}
+
+pub fn animation_system(world: &mut World, delta_time: f32) {
+    let sprite_sheets = world.resource::<Vec<SpriteSheet>>();
+    let ais = world.read::<Ai>();
+    let mut sprites = world.write::<Sprite>();
+
+    for (entity, sprite) in sprites.iter_mut() {
+        let is_stepped =
+            matches!(ais.get(entity), Some(Ai::Fleet { .. })) && !is_dying(world, entity);
+
+        if !is_stepped {
+            sprite
+                .animator
+                .update(delta_time, &sprite_sheets[sprite.texture_index]);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALIEN_SIZE: (u32, u32) = (32, 32);
+
+    fn test_world() -> World {
+        let world = create_world(0);
+
+        *world.resource_mut::<Arena>() = Arena {
+            width: 800,
+            height: 600,
+        };
+        world
+            .resource_mut::<Vec<SpriteSheet>>()
+            .push(SpriteSheet::single_frame(ALIEN_SIZE.0, ALIEN_SIZE.1));
+        world.resource_mut::<Fleet>().velocity = 100.0;
+
+        world
+    }
+
+    fn spawn_test_alien(world: &mut World, position: (f32, f32)) -> Entity {
+        let entity = world.spawn();
+
+        world.insert(entity, Transform::new(position.0, position.1));
+        world.insert(entity, Sprite::new(0, ALIEN_SIZE));
+        world.insert(entity, Health { points: 1 });
+        world.insert(entity, Weapon::new(BulletKind::Standard, 1.0));
+        world.insert(entity, Ai::Fleet { type_index: 0 });
+
+        entity
+    }
+
+    fn projectile_count(world: &World, faction: Faction) -> usize {
+        world
+            .read::<Projectile>()
+            .iter()
+            .filter(|(_, projectile)| projectile.faction == faction)
+            .count()
+    }
+
+    #[test]
+    fn fleet_switches_direction_at_the_edge() {
+        let mut world = test_world();
+        spawn_test_alien(&mut world, (780.0, 100.0));
+
+        fleet_movement_system(&mut world, 0.1);
+
+        let fleet = world.resource::<Fleet>();
+        assert_eq!(fleet.direction, AlienDirection::Down);
+        assert_eq!(fleet.next_direction, Some(AlienDirection::Left));
+        assert_eq!(fleet.dropdown_distance, fleet.level_dropdown_distance);
+        assert_eq!(fleet.velocity, 100.0 + fleet.velocity_increment);
+        assert!(world
+            .resource::<Vec<SpaceEvent>>()
+            .contains(&SpaceEvent::FleetShifted));
+    }
+
+    #[test]
+    fn fleet_drops_down_before_reversing() {
+        let mut world = test_world();
+        let alien = spawn_test_alien(&mut world, (400.0, 100.0));
+
+        {
+            let mut fleet = world.resource_mut::<Fleet>();
+            fleet.direction = AlienDirection::Down;
+            fleet.next_direction = Some(AlienDirection::Left);
+            fleet.dropdown_distance = 5.0;
+        }
+
+        fleet_movement_system(&mut world, 0.1);
+        assert_eq!(world.resource::<Fleet>().direction, AlienDirection::Down);
+
+        fleet_movement_system(&mut world, 0.1);
+
+        let fleet = world.resource::<Fleet>();
+        assert_eq!(fleet.direction, AlienDirection::Left);
+        assert_eq!(fleet.next_direction, None);
+
+        let transform = *world.read::<Transform>().get(alien).unwrap();
+        assert_eq!(transform.position(), (400.0, 120.0));
+    }
+
+    #[test]
+    fn fleet_reaching_the_bottom_lands() {
+        let mut world = test_world();
+        spawn_test_alien(&mut world, (400.0, 590.0));
+
+        fleet_movement_system(&mut world, 0.0);
+
+        assert!(world.resource::<Fleet>().has_hit_bottom);
+        assert!(world
+            .resource::<Vec<SpaceEvent>>()
+            .contains(&SpaceEvent::FleetLanded));
+    }
+
+    #[test]
+    fn front_line_is_the_lowest_living_alien_per_column() {
+        let mut world = test_world();
+        spawn_test_alien(&mut world, (25.0, 100.0));
+        let left_front = spawn_test_alien(&mut world, (25.0, 200.0));
+        let right_front = spawn_test_alien(&mut world, (75.0, 100.0));
+        let dying = spawn_test_alien(&mut world, (75.0, 200.0));
+
+        world.write::<Health>().get_mut(dying).unwrap().points = 0;
+
+        let front_line = front_line(&world, 50.0);
+
+        assert_eq!(front_line, vec![left_front, right_front]);
+    }
+
+    #[test]
+    fn fleet_fire_is_reproducible_from_the_seed() {
+        let shooter_positions = || {
+            let mut world = test_world();
+            spawn_spaceship(&mut world, (400.0, 550.0), 0, ALIEN_SIZE);
+
+            for column in 0..8 {
+                spawn_test_alien(&mut world, (25.0 + column as f32 * 50.0, 100.0));
+            }
+
+            (0..20)
+                .map(|_| {
+                    world.resource_mut::<Fleet>().fire_delay = 0.0;
+                    fleet_fire_system(&mut world, 0.0);
+
+                    let bullets = world
+                        .read::<Projectile>()
+                        .iter()
+                        .map(|(entity, _)| entity)
+                        .collect::<Vec<_>>();
+                    let transforms = world.read::<Transform>();
+                    let position = bullets
+                        .last()
+                        .and_then(|bullet| transforms.get(*bullet))
+                        .map(Transform::position);
+                    drop(transforms);
+
+                    for bullet in bullets {
+                        world.despawn(bullet);
+                    }
+
+                    position
+                })
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(shooter_positions(), shooter_positions());
+    }
+
+    #[test]
+    fn fleet_fire_interval_is_scaled() {
+        let mut world = test_world();
+        spawn_spaceship(&mut world, (400.0, 550.0), 0, ALIEN_SIZE);
+        spawn_test_alien(&mut world, (400.0, 100.0));
+        world.resource_mut::<Fleet>().fire_interval_scale = 0.5;
+
+        fleet_fire_system(&mut world, 0.0);
+
+        assert_eq!(world.resource::<Fleet>().fire_delay, 0.5);
+    }
+
+    #[test]
+    fn fleet_fire_respects_max_bullets() {
+        let mut world = test_world();
+        spawn_spaceship(&mut world, (400.0, 550.0), 0, ALIEN_SIZE);
+        spawn_test_alien(&mut world, (400.0, 100.0));
+        world.resource_mut::<Fleet>().max_bullets = 1;
+
+        fleet_fire_system(&mut world, 0.0);
+        assert_eq!(projectile_count(&world, Faction::Alien), 1);
+
+        world.resource_mut::<Fleet>().fire_delay = 0.0;
+        fleet_fire_system(&mut world, 0.0);
+
+        assert_eq!(projectile_count(&world, Faction::Alien), 1);
+        assert_eq!(
+            world
+                .resource::<Vec<SpaceEvent>>()
+                .iter()
+                .filter(|event| **event == SpaceEvent::AlienFired)
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn player_weapon_fires_spread() {
+        let mut world = test_world();
+        let player = spawn_spaceship(&mut world, (400.0, 550.0), 0, ALIEN_SIZE);
+
+        {
+            let mut weapons = world.write::<Weapon>();
+            let weapon = weapons.get_mut(player).unwrap();
+            weapon.spread = (2, 50.0);
+            weapon.is_firing = true;
+        }
+
+        player_weapon_system(&mut world, 0.0);
+
+        let mut x_velocities = world
+            .read::<Velocity>()
+            .iter()
+            .filter(|(entity, _)| world.read::<Projectile>().contains(*entity))
+            .map(|(_, velocity)| velocity.x)
+            .collect::<Vec<_>>();
+        x_velocities.sort_by(|first, second| first.partial_cmp(second).unwrap());
+
+        assert_eq!(x_velocities, vec![-100.0, -50.0, 0.0, 50.0, 100.0]);
+        assert!(!world.read::<Weapon>().get(player).unwrap().is_firing);
+        assert!(matches!(
+            world.resource::<Vec<SpaceEvent>>()[..],
+            [SpaceEvent::PlayerFired { shots: 5, .. }]
+        ));
+    }
+
+    #[test]
+    fn player_weapon_cools_down() {
+        let mut world = test_world();
+        let player = spawn_spaceship(&mut world, (400.0, 550.0), 0, ALIEN_SIZE);
+        world.write::<Weapon>().get_mut(player).unwrap().cooldown = 0.5;
+
+        player_weapon_system(&mut world, 0.25);
+
+        assert_eq!(world.read::<Weapon>().get(player).unwrap().cooldown, 0.25);
+        assert_eq!(projectile_count(&world, Faction::Player), 0);
+    }
+
+    #[test]
+    fn despawn_culls_offscreen_bullets() {
+        let mut world = test_world();
+        let player_bullet = spawn_bullet(
+            &mut world,
+            Faction::Player,
+            Bullet::new(400.0, -100.0, BulletKind::Standard),
+        );
+        let alien_bullet = spawn_bullet(
+            &mut world,
+            Faction::Alien,
+            Bullet::new(400.0, 700.0, BulletKind::Standard),
+        );
+        let onscreen_bullet = spawn_bullet(
+            &mut world,
+            Faction::Alien,
+            Bullet::new(400.0, 300.0, BulletKind::Standard),
+        );
+
+        despawn_system(&mut world);
+
+        assert!(!world.is_alive(player_bullet));
+        assert!(!world.is_alive(alien_bullet));
+        assert!(world.is_alive(onscreen_bullet));
+    }
+}
@@ -0,0 +1,124 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{self, Command};
+
+use sdl2::image::LoadSurface;
+use sdl2::pixels::PixelFormatEnum;
+use sdl2::surface::Surface;
+
+const SEED: &str = "1";
+const CAPTURE_FRAME: &str = "120";
+const CAPTURE_FILE_NAME: &str = "frame-000120.png";
+const REFERENCE_IMAGE: &str = "tests/golden/play-seed-1-frame-120.png";
+const UPDATE_GOLDEN_VARIABLE: &str = "UPDATE_GOLDEN";
+
+// Allows for small differences between SDL versions without hiding real regressions.
+const MAX_DIFFERENT_PIXEL_FRACTION: f32 = 0.001;
+
+struct Image {
+    width: u32,
+    height: u32,
+    pixels: Vec<u32>,
+}
+
+fn capture_frame(run_name: &str) -> PathBuf {
+    let capture_directory =
+        env::temp_dir().join(format!("golden-images-{}-{}", process::id(), run_name));
+    let _ = fs::remove_dir_all(&capture_directory);
+
+    let status = Command::new(env!("CARGO_BIN_EXE_game-engine"))
+        .args(["--headless", "--play", "--seed", SEED])
+        .args(["--capture-frame", CAPTURE_FRAME, "--frames", CAPTURE_FRAME])
+        .arg("--capture-directory")
+        .arg(&capture_directory)
+        .env("SDL_VIDEODRIVER", "dummy")
+        .env("SDL_AUDIODRIVER", "dummy")
+        .status()
+        .expect("Could not run the game");
+
+    assert!(status.success(), "The headless run failed with {}", status);
+
+    capture_directory.join(CAPTURE_FILE_NAME)
+}
+
+fn load_image(path: &Path) -> Image {
+    let surface = Surface::from_file(path)
+        .and_then(|surface| surface.convert_format(PixelFormatEnum::ARGB8888))
+        .unwrap_or_else(|error| panic!("Could not load {}: {}", path.display(), error));
+    let (width, height, pitch) = (surface.width(), surface.height(), surface.pitch());
+
+    let pixels = surface.with_lock(|bytes| {
+        bytes
+            .chunks(pitch as usize)
+            .flat_map(|row| {
+                row.chunks_exact(4)
+                    .take(width as usize)
+                    .map(|pixel| u32::from_ne_bytes([pixel[0], pixel[1], pixel[2], pixel[3]]))
+            })
+            .collect()
+    });
+
+    Image {
+        width,
+        height,
+        pixels,
+    }
+}
+
+fn different_pixel_fraction(first: &Image, second: &Image) -> f32 {
+    assert_eq!(
+        (first.width, first.height),
+        (second.width, second.height),
+        "The images have different sizes"
+    );
+
+    let different_pixel_count = first
+        .pixels
+        .iter()
+        .zip(&second.pixels)
+        .filter(|(first_pixel, second_pixel)| first_pixel != second_pixel)
+        .count();
+
+    different_pixel_count as f32 / first.pixels.len() as f32
+}
+
+#[test]
+fn seeded_headless_runs_are_identical() {
+    let first = load_image(&capture_frame("first"));
+    let second = load_image(&capture_frame("second"));
+
+    assert_eq!(different_pixel_fraction(&first, &second), 0.0);
+}
+
+#[test]
+fn seeded_headless_run_matches_reference() {
+    let capture_filepath = capture_frame("reference");
+    let reference_filepath = Path::new(env!("CARGO_MANIFEST_DIR")).join(REFERENCE_IMAGE);
+
+    if env::var_os(UPDATE_GOLDEN_VARIABLE).is_some() {
+        fs::create_dir_all(reference_filepath.parent().unwrap()).unwrap();
+        fs::copy(&capture_filepath, &reference_filepath).unwrap();
+
+        return;
+    }
+
+    assert!(
+        reference_filepath.is_file(),
+        "{} is missing, see tests/golden/README.md to create it",
+        REFERENCE_IMAGE
+    );
+
+    let fraction = different_pixel_fraction(
+        &load_image(&capture_filepath),
+        &load_image(&reference_filepath),
+    );
+
+    assert!(
+        fraction <= MAX_DIFFERENT_PIXEL_FRACTION,
+        "{:.2}% of pixels differ from {}, run with {}=1 to update it",
+        fraction * 100.0,
+        REFERENCE_IMAGE,
+        UPDATE_GOLDEN_VARIABLE
+    );
+}